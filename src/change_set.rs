@@ -0,0 +1,193 @@
+use std::fs;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use crate::history::{self, HistoryEntry};
+use crate::setting_writer::{SettingWriter, overrides_path, write_config_file};
+use crate::util::diff::{DiffLine, unified_diff};
+
+/// Unchanged lines shown around each change of the diff preview
+const DIFF_CONTEXT: usize = 3;
+
+/// An edit of the overrides file
+pub enum Edit {
+    Write(SettingWriter),
+    Remove(SettingWriter),
+}
+
+impl Edit {
+    fn apply(&self, lines: &mut Vec<String>) -> anyhow::Result<()> {
+        match self {
+            Edit::Write(writer) => writer.write_lines(lines),
+            Edit::Remove(writer) => {
+                writer.remove_lines(lines);
+                Ok(())
+            }
+        }
+    }
+
+    fn summary(&self) -> String {
+        match self {
+            Edit::Write(writer) => writer.summary(),
+            Edit::Remove(writer) => format!("Remove {}", writer.summary()),
+        }
+    }
+}
+
+/// An edit waiting to be written, in the order the pages made them
+pub struct PendingChange {
+    pub id: usize,
+    pub summary: String,
+    edit: Edit,
+    /// Apply the written line to the running compositor once it is saved
+    live: bool,
+}
+
+struct ChangeSet {
+    next_id: usize,
+    /// Bumped whenever the staged content changes, so views can cache what they derive from it
+    revision: u64,
+    changes: Vec<PendingChange>,
+}
+
+/// Edits every page made since the last apply. Pages keep previewing values live through
+/// `hyprctl keyword`, the overrides file is only touched by `apply`.
+static CHANGE_SET: Mutex<ChangeSet> = Mutex::new(ChangeSet {
+    next_id: 0,
+    revision: 0,
+    changes: Vec::new(),
+});
+
+fn change_set() -> MutexGuard<'static, ChangeSet> {
+    CHANGE_SET.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Lines of the overrides file on disk and with every change applied in order
+fn staged_lines(changes: &[PendingChange]) -> anyhow::Result<(String, Vec<String>)> {
+    let current = fs::read_to_string(overrides_path()?)?;
+    let mut lines: Vec<String> = current.lines().map(|l| l.to_string()).collect();
+
+    for change in changes {
+        change.edit.apply(&mut lines)?;
+    }
+
+    Ok((current, lines))
+}
+
+/// Add an edit to the change set. It is checked against the changes staged before it, an edit
+/// that can't be applied is never staged.
+pub fn stage(edit: Edit) -> anyhow::Result<()> {
+    stage_edit(edit, false)
+}
+
+/// Add an edit that is also applied to the running compositor once it is saved, for lines such
+/// as binds that are not previewed while they are pending
+pub fn stage_live(edit: Edit) -> anyhow::Result<()> {
+    stage_edit(edit, true)
+}
+
+fn stage_edit(edit: Edit, live: bool) -> anyhow::Result<()> {
+    let mut set = change_set();
+    let (_, mut lines) = staged_lines(&set.changes)?;
+    edit.apply(&mut lines)?;
+
+    let id = set.next_id;
+    set.next_id += 1;
+    set.revision += 1;
+    set.changes.push(PendingChange {
+        id,
+        summary: edit.summary(),
+        edit,
+        live,
+    });
+
+    Ok(())
+}
+
+/// `(id, summary)` of every pending change
+pub fn pending_changes() -> Vec<(usize, String)> {
+    change_set()
+        .changes
+        .iter()
+        .map(|change| (change.id, change.summary.clone()))
+        .collect()
+}
+
+pub fn pending_count() -> usize {
+    change_set().changes.len()
+}
+
+/// Revision of the change set, it changes whenever a change is staged, discarded or applied
+pub fn revision() -> u64 {
+    change_set().revision
+}
+
+pub fn discard(id: usize) {
+    let mut set = change_set();
+    set.changes.retain(|change| change.id != id);
+    set.revision += 1;
+}
+
+pub fn discard_all() {
+    let mut set = change_set();
+    set.changes.clear();
+    set.revision += 1;
+}
+
+/// Content of the overrides file as it will be once the pending changes are applied, so pages
+/// listing its lines show what they staged
+pub fn read_overrides() -> anyhow::Result<String> {
+    let (_, lines) = staged_lines(&change_set().changes)?;
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Unified diff of the overrides file on disk against the staged one
+pub fn diff() -> anyhow::Result<Vec<DiffLine>> {
+    let (current, lines) = staged_lines(&change_set().changes)?;
+    Ok(unified_diff(&current, &lines.join("\n"), DIFF_CONTEXT))
+}
+
+/// Write every pending change at once. The new file is written next to the old one and renamed
+/// over it, so a failed write leaves the overrides file untouched and the changes pending.
+/// Every change is recorded in the history on its own, so it can be undone one at a time.
+pub fn apply() -> anyhow::Result<usize> {
+    let mut set = change_set();
+    if set.changes.is_empty() {
+        return Ok(0);
+    }
+
+    let path = overrides_path()?;
+    let mut content = fs::read_to_string(&path)?;
+    let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+    let mut entries = Vec::new();
+
+    for change in &set.changes {
+        change.edit.apply(&mut lines)?;
+
+        let after = lines.join("\n") + "\n";
+        if after != content {
+            entries.push(HistoryEntry::new(
+                change.summary.clone(),
+                path.clone(),
+                content,
+                after.clone(),
+            ));
+        }
+        content = after;
+    }
+
+    write_config_file(&path, &content)?;
+    history::record(entries);
+
+    for change in set.changes.iter().filter(|change| change.live) {
+        if let Edit::Write(writer) = &change.edit
+            && let Err(e) = writer.apply_live()
+        {
+            eprintln!("{}", e);
+        }
+    }
+
+    let count = set.changes.len();
+    set.changes.clear();
+    set.revision += 1;
+    Ok(count)
+}
@@ -0,0 +1,164 @@
+use std::fs;
+use std::path::PathBuf;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::SystemTime;
+
+use crate::option_registry::find_option;
+use crate::setting_writer::write_config_file;
+use crate::util::diff::{DiffLine, unified_diff};
+use crate::util::option::set_keyword;
+
+/// Entries kept for undo, the oldest are dropped first
+const MAX_ENTRIES: usize = 100;
+
+/// A change written to a config file. It is undone by writing back the content it replaced,
+/// as long as nothing else changed the file since.
+#[derive(Clone)]
+pub struct HistoryEntry {
+    pub summary: String,
+    pub file: PathBuf,
+    /// Lines the change replaced
+    pub old_lines: Vec<String>,
+    /// Lines the change wrote
+    pub new_lines: Vec<String>,
+    pub timestamp: SystemTime,
+    before: String,
+    after: String,
+}
+
+impl HistoryEntry {
+    pub fn new(summary: String, file: PathBuf, before: String, after: String) -> Self {
+        let mut old_lines = Vec::new();
+        let mut new_lines = Vec::new();
+        for line in unified_diff(&before, &after, 0) {
+            match line {
+                DiffLine::Removed(line) => old_lines.push(line),
+                DiffLine::Added(line) => new_lines.push(line),
+                _ => {}
+            }
+        }
+
+        Self {
+            summary,
+            file,
+            old_lines,
+            new_lines,
+            timestamp: SystemTime::now(),
+            before,
+            after,
+        }
+    }
+
+    /// Time since the change was written, e.g. `5 min ago`
+    pub fn age(&self) -> String {
+        let seconds = self.timestamp.elapsed().unwrap_or_default().as_secs();
+
+        match seconds {
+            0..60 => "just now".to_string(),
+            60..3600 => format!("{} min ago", seconds / 60),
+            _ => format!("{} h ago", seconds / 3600),
+        }
+    }
+}
+
+struct History {
+    undo: Vec<HistoryEntry>,
+    redo: Vec<HistoryEntry>,
+}
+
+static HISTORY: Mutex<History> = Mutex::new(History {
+    undo: Vec::new(),
+    redo: Vec::new(),
+});
+
+fn history() -> MutexGuard<'static, History> {
+    HISTORY.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Remember written changes, a new change can't be redone past so the redo stack is cleared
+pub fn record(entries: Vec<HistoryEntry>) {
+    let mut history = history();
+    history.undo.extend(entries);
+    history.redo.clear();
+
+    let excess = history.undo.len().saturating_sub(MAX_ENTRIES);
+    history.undo.drain(..excess);
+}
+
+/// Changes that can be undone, newest first
+pub fn undo_entries() -> Vec<HistoryEntry> {
+    history().undo.iter().rev().cloned().collect()
+}
+
+/// Summary of the change `undo` reverts next
+pub fn last_change() -> Option<String> {
+    history().undo.last().map(|entry| entry.summary.clone())
+}
+
+/// Changes that can be redone, next one first
+pub fn redo_entries() -> Vec<HistoryEntry> {
+    history().redo.iter().rev().cloned().collect()
+}
+
+/// Write `content` over the file of an entry that expects to find `expected` there
+fn restore(entry: &HistoryEntry, expected: &str, content: &str) -> anyhow::Result<()> {
+    let current = fs::read_to_string(&entry.file)?;
+    if current != expected {
+        return Err(anyhow::anyhow!(
+            "{} was changed since \"{}\", it can't be reverted",
+            entry.file.display(),
+            entry.summary
+        ));
+    }
+
+    write_config_file(&entry.file, content)
+}
+
+/// Apply the option lines of a change to the running compositor right away. Lines that aren't
+/// options, or options that no longer have a line, are picked up when Hyprland reloads.
+fn apply_live(lines: &[String]) {
+    for line in lines {
+        if let Some((path, value)) = line.split_once('=')
+            && let Some(option) = find_option(path.trim())
+            && let Err(e) = set_keyword(option.path, value.trim())
+        {
+            eprintln!("{}", e);
+        }
+    }
+}
+
+/// Revert the newest change, returning its summary or `None` if there is nothing to undo
+pub fn undo() -> anyhow::Result<Option<String>> {
+    let mut history = history();
+    let Some(entry) = history.undo.pop() else {
+        return Ok(None);
+    };
+
+    if let Err(e) = restore(&entry, &entry.after, &entry.before) {
+        history.undo.push(entry);
+        return Err(e);
+    }
+
+    apply_live(&entry.old_lines);
+    let summary = entry.summary.clone();
+    history.redo.push(entry);
+    Ok(Some(summary))
+}
+
+/// Write the last undone change again, returning its summary or `None` if there is nothing to redo
+pub fn redo() -> anyhow::Result<Option<String>> {
+    let mut history = history();
+    let Some(entry) = history.redo.pop() else {
+        return Ok(None);
+    };
+
+    if let Err(e) = restore(&entry, &entry.before, &entry.after) {
+        history.redo.push(entry);
+        return Err(e);
+    }
+
+    apply_live(&entry.new_lines);
+    let summary = entry.summary.clone();
+    history.undo.push(entry);
+    Ok(Some(summary))
+}
@@ -2,38 +2,104 @@ use std::io::Write;
 use std::rc::Rc;
 
 use dirs::home_dir;
+use gpui::prelude::FluentBuilder;
 use gpui::*;
+use gpui_component::button::Button;
 use gpui_component::*;
 use serde::Deserialize;
 use std::path::Path;
 
+mod change_set;
+mod history;
+mod option_registry;
 mod setting;
 mod setting_writer;
 mod ui;
 mod util;
 
+use crate::option_registry::OptionSpec;
 use crate::setting_writer::{HYPR_OVERRIDES_PATH, HYPR_SETTING_PATH};
+use crate::ui::all_options::AllOptions;
+use crate::ui::animation_settings::AnimationSettings;
+use crate::ui::appearance_settings::AppearanceSettings;
+use crate::ui::autostart_settings::AutostartSettings;
+use crate::ui::backup_settings::BackupSettings;
+use crate::ui::blur_shadow_settings::BlurShadowSettings;
+use crate::ui::command_palette::{CommandPalette, CommandPaletteEvent, ToggleCommandPalette};
+use crate::ui::cursor_settings::CursorSettings;
+use crate::ui::env_settings::EnvSettings;
+use crate::ui::gesture_settings::GestureSettings;
+use crate::ui::keybind_settings::KeybindSettings;
 use crate::ui::keyboard_settings::KeyboardSettings;
+use crate::ui::layer_rule_settings::LayerRuleSettings;
+use crate::ui::layout_settings::LayoutSettings;
+use crate::ui::misc_settings::MiscSettings;
 use crate::ui::monitor_visualizer::MonitorVisualizer;
 use crate::ui::mouse_settings::MouseSettings;
+use crate::ui::pending_changes::{PendingChanges, RedoChange, UndoChange};
 use crate::ui::section_container::{section_divider, section_title};
 use crate::ui::sidebar::create_sidebar;
+use crate::ui::submap_settings::SubmapSettings;
+use crate::ui::tablet_settings::TabletSettings;
+use crate::ui::tooltip::with_tooltip;
+use crate::ui::touchpad_settings::TouchpadSettings;
+use crate::ui::window_rule_settings::WindowRuleSettings;
+use crate::ui::workspace_rule_settings::WorkspaceRuleSettings;
+use crate::util::backup::backup_once;
 use crate::util::monitor;
+use crate::util::search::{SearchTarget, option_section};
 
 #[derive(Clone, Copy, PartialEq)]
 pub enum ActiveSection {
     Monitors,
     Keyboard,
     Mouse,
+    Touchpad,
+    Tablet,
+    Cursor,
+    Appearance,
+    BlurShadow,
+    Animations,
+    Keybinds,
+    Submaps,
+    WindowRules,
+    LayerRules,
+    WorkspaceRules,
+    Autostart,
+    Environment,
+    Layouts,
+    Misc,
+    AllOptions,
+    PendingChanges,
+    Backups,
 }
 
-impl ToString for ActiveSection {
-    fn to_string(&self) -> String {
-        match self {
-            ActiveSection::Monitors => "Monitors".to_string(),
-            ActiveSection::Keyboard => "Keyboard".to_string(),
-            ActiveSection::Mouse => "Mouse".to_string(),
-        }
+impl std::fmt::Display for ActiveSection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            ActiveSection::Monitors => "Monitors",
+            ActiveSection::Keyboard => "Keyboard",
+            ActiveSection::Mouse => "Mouse",
+            ActiveSection::Touchpad => "Touchpad",
+            ActiveSection::Tablet => "Tablet",
+            ActiveSection::Cursor => "Cursor",
+            ActiveSection::Appearance => "Appearance",
+            ActiveSection::BlurShadow => "Blur & shadow",
+            ActiveSection::Animations => "Animations",
+            ActiveSection::Keybinds => "Keybinds",
+            ActiveSection::Submaps => "Submaps",
+            ActiveSection::WindowRules => "Window Rules",
+            ActiveSection::LayerRules => "Layer Rules",
+            ActiveSection::WorkspaceRules => "Workspace Rules",
+            ActiveSection::Autostart => "Autostart",
+            ActiveSection::Environment => "Environment",
+            ActiveSection::Layouts => "Layouts",
+            ActiveSection::Misc => "Misc",
+            ActiveSection::AllOptions => "All options",
+            ActiveSection::PendingChanges => "Pending changes",
+            ActiveSection::Backups => "Backups",
+        };
+        write!(f, "{}", name)
     }
 }
 
@@ -41,23 +107,135 @@ pub struct Hyprsetting {
     monitor_visualizer: Entity<MonitorVisualizer>,
     keyboard_settings: Entity<KeyboardSettings>,
     mouse_settings: Entity<MouseSettings>,
+    touchpad_settings: Entity<TouchpadSettings>,
+    gesture_settings: Entity<GestureSettings>,
+    tablet_settings: Entity<TabletSettings>,
+    cursor_settings: Entity<CursorSettings>,
+    appearance_settings: Entity<AppearanceSettings>,
+    blur_shadow_settings: Entity<BlurShadowSettings>,
+    animation_settings: Entity<AnimationSettings>,
+    keybind_settings: Entity<KeybindSettings>,
+    submap_settings: Entity<SubmapSettings>,
+    window_rule_settings: Entity<WindowRuleSettings>,
+    layer_rule_settings: Entity<LayerRuleSettings>,
+    workspace_rule_settings: Entity<WorkspaceRuleSettings>,
+    autostart_settings: Entity<AutostartSettings>,
+    env_settings: Entity<EnvSettings>,
+    layout_settings: Entity<LayoutSettings>,
+    misc_settings: Entity<MiscSettings>,
+    all_options: Entity<AllOptions>,
+    pending_changes: Entity<PendingChanges>,
+    command_palette: Entity<CommandPalette>,
+    palette_open: bool,
+    focus_handle: FocusHandle,
+    backup_settings: Entity<BackupSettings>,
     active_section: ActiveSection,
 }
 
 impl Hyprsetting {
     pub fn set_active_section(&mut self, section: ActiveSection, cx: &mut Context<Self>) {
         self.active_section = section;
+        self.highlight(None, cx);
+        cx.notify();
+    }
+
+    /// Outline the control of an option on the page editing it, `None` clears every outline
+    fn highlight(&mut self, path: Option<&'static str>, cx: &mut Context<Self>) {
+        self.keyboard_settings
+            .update(cx, |settings, cx| settings.highlight(path, cx));
+        self.mouse_settings
+            .update(cx, |settings, cx| settings.highlight(path, cx));
+        self.touchpad_settings
+            .update(cx, |settings, cx| settings.highlight(path, cx));
+        self.gesture_settings
+            .update(cx, |settings, cx| settings.highlight(path, cx));
+        self.tablet_settings
+            .update(cx, |settings, cx| settings.highlight(path, cx));
+        self.appearance_settings
+            .update(cx, |settings, cx| settings.highlight(path, cx));
+        self.blur_shadow_settings
+            .update(cx, |settings, cx| settings.highlight(path, cx));
+        self.layout_settings
+            .update(cx, |settings, cx| settings.highlight(path, cx));
+        self.misc_settings
+            .update(cx, |settings, cx| settings.highlight(path, cx));
+    }
+
+    fn toggle_command_palette(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.palette_open = !self.palette_open;
+        if self.palette_open {
+            self.command_palette
+                .update(cx, |palette, cx| palette.open(window, cx));
+        } else {
+            window.focus(&self.focus_handle);
+        }
+        cx.notify();
+    }
+
+    fn select_search_target(
+        &mut self,
+        target: SearchTarget,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.palette_open = false;
+        window.focus(&self.focus_handle);
+
+        match target {
+            SearchTarget::Page(section) => self.set_active_section(section, cx),
+            SearchTarget::Option(option) => self.show_option(option, window, cx),
+            SearchTarget::Action(action) => {
+                if let Err(e) = action.run() {
+                    eprintln!("{}", e);
+                }
+            }
+        }
         cx.notify();
     }
+
+    /// Jump to the page editing an option and point out its control
+    fn show_option(
+        &mut self,
+        option: &'static OptionSpec,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        let section = option_section(option.path);
+        self.set_active_section(section, cx);
+
+        if section == ActiveSection::AllOptions {
+            self.all_options.update(cx, |all_options, cx| {
+                all_options.show_option(option, window, cx)
+            });
+        } else {
+            self.highlight(Some(option.path), cx);
+        }
+    }
 }
 
 impl Render for Hyprsetting {
     fn render(&mut self, _: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let active_section = self.active_section;
+        // pages stage their edits without telling this view, but any notify re-renders it
+        let pending_count = change_set::pending_count();
+        let last_saved = history::last_change();
 
         div()
             .size_full()
             .flex()
+            .relative()
+            .track_focus(&self.focus_handle)
+            .on_action(cx.listener(|this, _: &ToggleCommandPalette, window, cx| {
+                this.toggle_command_palette(window, cx);
+            }))
+            .on_action(cx.listener(|this, _: &UndoChange, _, cx| {
+                this.pending_changes
+                    .update(cx, |changes, cx| changes.undo(cx));
+            }))
+            .on_action(cx.listener(|this, _: &RedoChange, _, cx| {
+                this.pending_changes
+                    .update(cx, |changes, cx| changes.redo(cx));
+            }))
             .bg(cx.theme().background)
             .child(create_sidebar(active_section, cx))
             .child(
@@ -68,10 +246,49 @@ impl Render for Hyprsetting {
                     .gap_4()
                     .p_4()
                     .overflow_hidden()
-                    .child(section_title(
-                        format!("{} settings", active_section.to_string()),
-                        cx,
-                    ))
+                    .child(
+                        div()
+                            .h_flex()
+                            .items_center()
+                            .justify_between()
+                            .child(section_title(format!("{} settings", active_section), cx))
+                            .child(
+                                div()
+                                    .h_flex()
+                                    .gap_2()
+                                    .when_some(last_saved, |this, summary| {
+                                        this.child(with_tooltip(
+                                            format!("Undo {} (Ctrl+Z)", summary),
+                                            Button::new("undo-last-change").label("Undo").on_click(
+                                                cx.listener(|this, _, _, cx| {
+                                                    this.pending_changes
+                                                        .update(cx, |changes, cx| changes.undo(cx));
+                                                }),
+                                            ),
+                                            cx,
+                                        ))
+                                    })
+                                    .when(pending_count > 0, |this| {
+                                        this.child(
+                                            Button::new("review-pending-changes")
+                                                .label(format!("Review {} changes", pending_count))
+                                                .on_click(cx.listener(|this, _, _, cx| {
+                                                    this.set_active_section(
+                                                        ActiveSection::PendingChanges,
+                                                        cx,
+                                                    );
+                                                })),
+                                        )
+                                    })
+                                    .child(
+                                        Button::new("open-command-palette")
+                                            .label("Search (Ctrl+K)")
+                                            .on_click(cx.listener(|this, _, window, cx| {
+                                                this.toggle_command_palette(window, cx);
+                                            })),
+                                    ),
+                            ),
+                    )
                     .child(section_divider(cx))
                     .child(match active_section {
                         ActiveSection::Monitors => div()
@@ -92,8 +309,133 @@ impl Render for Hyprsetting {
                             .gap_4()
                             .child(section_title("Mouse", cx))
                             .child(self.mouse_settings.clone()),
+                        ActiveSection::Touchpad => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Touchpad", cx))
+                            .child(self.touchpad_settings.clone())
+                            .child(section_title("Gestures", cx))
+                            .child(self.gesture_settings.clone()),
+                        ActiveSection::Tablet => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Tablet & touchscreen", cx))
+                            .child(self.tablet_settings.clone()),
+                        ActiveSection::Cursor => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Cursor", cx))
+                            .child(self.cursor_settings.clone()),
+                        ActiveSection::Appearance => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Appearance", cx))
+                            .child(self.appearance_settings.clone()),
+                        ActiveSection::BlurShadow => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Blur & shadow", cx))
+                            .child(self.blur_shadow_settings.clone()),
+                        ActiveSection::Animations => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Animations", cx))
+                            .child(self.animation_settings.clone()),
+                        ActiveSection::Keybinds => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Keybinds", cx))
+                            .child(self.keybind_settings.clone()),
+                        ActiveSection::Submaps => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Submaps", cx))
+                            .child(self.submap_settings.clone()),
+                        ActiveSection::WindowRules => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Window Rules", cx))
+                            .child(self.window_rule_settings.clone()),
+                        ActiveSection::LayerRules => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Layer Rules", cx))
+                            .child(self.layer_rule_settings.clone()),
+                        ActiveSection::WorkspaceRules => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Workspace Rules", cx))
+                            .child(self.workspace_rule_settings.clone()),
+                        ActiveSection::Autostart => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Autostart", cx))
+                            .child(self.autostart_settings.clone()),
+                        ActiveSection::Environment => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Environment Variables", cx))
+                            .child(self.env_settings.clone()),
+                        ActiveSection::Layouts => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Layouts", cx))
+                            .child(self.layout_settings.clone()),
+                        ActiveSection::Misc => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Misc & XWayland", cx))
+                            .child(self.misc_settings.clone()),
+                        ActiveSection::AllOptions => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("All Options", cx))
+                            .child(self.all_options.clone()),
+                        ActiveSection::PendingChanges => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Pending Changes", cx))
+                            .child(self.pending_changes.clone()),
+                        ActiveSection::Backups => div()
+                            .flex()
+                            .flex_col()
+                            .gap_4()
+                            .child(section_title("Backups", cx))
+                            .child(self.backup_settings.clone()),
                     }),
             )
+            .when(self.palette_open, |this| {
+                this.child(
+                    div()
+                        .absolute()
+                        .top_0()
+                        .left_0()
+                        .size_full()
+                        .flex()
+                        .justify_center()
+                        .pt(px(80.0))
+                        .bg(cx.theme().overlay)
+                        .occlude()
+                        .child(self.command_palette.clone()),
+                )
+            })
     }
 }
 
@@ -119,6 +461,9 @@ fn main() {
 
     create_overrides().expect("Failed to create Hyprland overrides setting file");
 
+    // load option descriptions before any page reads or writes an option
+    option_registry::registry();
+
     let app = Application::new();
 
     app.run(move |cx| {
@@ -126,6 +471,8 @@ fn main() {
         gpui_component::init(cx);
 
         init(cx);
+        ui::command_palette::init(cx);
+        ui::pending_changes::init(cx);
 
         cx.spawn(async move |cx| {
             let window_options = WindowOptions {
@@ -146,10 +493,92 @@ fn main() {
 
                     let mouse_settings = cx.new(|cx| MouseSettings::new(window, cx));
 
+                    let touchpad_settings = cx.new(|cx| TouchpadSettings::new(window, cx));
+                    let gesture_settings = cx.new(|cx| GestureSettings::new(window, cx));
+
+                    let tablet_settings =
+                        cx.new(|cx| TabletSettings::new(monitors.clone(), window, cx));
+
+                    let cursor_settings = cx.new(|cx| CursorSettings::new(window, cx));
+
+                    let appearance_settings = cx.new(|cx| AppearanceSettings::new(window, cx));
+
+                    let blur_shadow_settings = cx.new(|cx| BlurShadowSettings::new(window, cx));
+
+                    let animation_settings = cx.new(|cx| AnimationSettings::new(window, cx));
+
+                    let keybind_settings = cx.new(|cx| KeybindSettings::new(window, cx));
+
+                    let submap_settings = cx.new(|cx| SubmapSettings::new(window, cx));
+
+                    let window_rule_settings = cx.new(|cx| WindowRuleSettings::new(window, cx));
+
+                    let layer_rule_settings = cx.new(|cx| LayerRuleSettings::new(window, cx));
+
+                    let workspace_rule_settings =
+                        cx.new(|cx| WorkspaceRuleSettings::new(window, cx));
+
+                    let autostart_settings = cx.new(|cx| AutostartSettings::new(window, cx));
+
+                    let env_settings = cx.new(|cx| EnvSettings::new(window, cx));
+
+                    let layout_settings = cx.new(|cx| LayoutSettings::new(window, cx));
+
+                    let misc_settings = cx.new(|cx| MiscSettings::new(window, cx));
+
+                    let all_options = cx.new(|cx| AllOptions::new(window, cx));
+
+                    let pending_changes = cx.new(|cx| PendingChanges::new(window, cx));
+
+                    let command_palette = cx.new(|cx| CommandPalette::new(window, cx));
+                    cx.subscribe_in(
+                        &command_palette,
+                        window,
+                        |this: &mut Hyprsetting, _, event: &CommandPaletteEvent, window, cx| {
+                            match event {
+                                CommandPaletteEvent::Select(target) => {
+                                    this.select_search_target(*target, window, cx)
+                                }
+                                CommandPaletteEvent::Dismiss => {
+                                    this.toggle_command_palette(window, cx)
+                                }
+                            }
+                        },
+                    )
+                    .detach();
+
+                    // focused so Ctrl+K works before anything else was clicked
+                    let focus_handle = cx.focus_handle();
+                    window.focus(&focus_handle);
+
+                    let backup_settings = cx.new(|cx| BackupSettings::new(window, cx));
+
                     Hyprsetting {
                         monitor_visualizer,
                         keyboard_settings,
                         mouse_settings,
+                        touchpad_settings,
+                        gesture_settings,
+                        tablet_settings,
+                        cursor_settings,
+                        appearance_settings,
+                        blur_shadow_settings,
+                        animation_settings,
+                        keybind_settings,
+                        submap_settings,
+                        window_rule_settings,
+                        layer_rule_settings,
+                        workspace_rule_settings,
+                        autostart_settings,
+                        env_settings,
+                        layout_settings,
+                        misc_settings,
+                        all_options,
+                        pending_changes,
+                        command_palette,
+                        palette_open: false,
+                        focus_handle,
+                        backup_settings,
                         active_section: ActiveSection::Monitors,
                     }
                 });
@@ -188,7 +617,8 @@ fn create_overrides() -> anyhow::Result<()> {
     if !Path::new(&hypr_overrides_path).exists() {
         std::fs::write(&hypr_overrides_path, "# Hyprland setting overrides\n")?;
 
-        // append the file source line to main conf
+        // append the file source line to main conf, keeping a copy of it as it was
+        backup_once(&hypr_setting_path)?;
         let mut hypr_setting_file = std::fs::OpenOptions::new()
             .append(true)
             .open(&hypr_setting_path)?;
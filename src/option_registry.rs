@@ -0,0 +1,947 @@
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use regex::Regex;
+
+use crate::setting_writer::SettingWriter;
+use crate::util::descriptions::{Description, get_descriptions};
+use crate::util::layout::{FORCE_SPLIT_MODES, LAYOUTS, MASTER_NEW_STATUSES, MASTER_ORIENTATIONS};
+use crate::util::mouse::{FLOAT_SWITCH_MODES, FOLLOW_MOUSE_MODES};
+use crate::util::option::get_option;
+
+/// Type of a Hyprland option, which also decides how its value is checked and edited.
+/// Values outside of a range are rejected before they are written.
+#[derive(Debug, Clone)]
+pub enum OptionType {
+    Bool,
+    /// `min` and `max` only bound the slider, Hyprland accepts values outside them
+    Int {
+        min: i64,
+        max: i64,
+    },
+    Float {
+        min: f32,
+        max: f32,
+        step: f32,
+    },
+    /// A number without a known range
+    Number {
+        integer: bool,
+    },
+    /// One of a list of values, numbered modes are labelled like "1 - On"
+    Choice(&'static [&'static str]),
+    Text,
+    /// A regex Hyprland matches whole values against, e.g. window classes
+    Regex,
+}
+
+/// A Hyprland option such as `misc:vrr`, see the Hyprland wiki for all of them
+#[derive(Debug, Clone)]
+pub struct OptionSpec {
+    pub path: &'static str,
+    pub option_type: OptionType,
+    pub default: &'static str,
+    pub description: &'static str,
+    /// First Hyprland version knowing the option, older versions reject the config line
+    pub since: Option<&'static str>,
+}
+
+const fn spec(
+    path: &'static str,
+    option_type: OptionType,
+    default: &'static str,
+    description: &'static str,
+) -> OptionSpec {
+    OptionSpec {
+        path,
+        option_type,
+        default,
+        description,
+        since: None,
+    }
+}
+
+const fn spec_since(
+    path: &'static str,
+    option_type: OptionType,
+    default: &'static str,
+    description: &'static str,
+    since: &'static str,
+) -> OptionSpec {
+    OptionSpec {
+        path,
+        option_type,
+        default,
+        description,
+        since: Some(since),
+    }
+}
+
+/// Options with curated metadata. Adding an option here gives it a handler that replaces its
+/// line in the overrides file and lets `OptionForm` edit it, even on Hyprland versions that
+/// can't describe their options.
+pub static OPTIONS: [OptionSpec; 75] = [
+    spec(
+        "input:kb_layout",
+        OptionType::Text,
+        "us",
+        "Keyboard layouts, separated by commas.",
+    ),
+    spec(
+        "input:sensitivity",
+        OptionType::Float {
+            min: -1.0,
+            max: 1.0,
+            step: 0.05,
+        },
+        "0",
+        "Pointer speed from -1.0 (slow) to 1.0 (fast).",
+    ),
+    spec(
+        "input:force_no_accel",
+        OptionType::Bool,
+        "false",
+        "Turn off pointer acceleration for consistent 1:1 tracking.",
+    ),
+    spec(
+        "input:follow_mouse",
+        OptionType::Choice(&FOLLOW_MOUSE_MODES),
+        "1",
+        "How keyboard focus follows the cursor.",
+    ),
+    spec(
+        "input:mouse_refocus",
+        OptionType::Bool,
+        "true",
+        "Refocus the window under the cursor even if the cursor has not crossed a window border.",
+    ),
+    spec(
+        "input:float_switch_override_focus",
+        OptionType::Choice(&FLOAT_SWITCH_MODES),
+        "1",
+        "Whether moving the cursor between tiled and floating windows changes focus.",
+    ),
+    spec(
+        "cursor:inactive_timeout",
+        OptionType::Float {
+            min: 0.0,
+            max: 30.0,
+            step: 1.0,
+        },
+        "0",
+        "Hide the cursor after this many seconds without movement. 0 never hides it.",
+    ),
+    spec(
+        "cursor:no_warps",
+        OptionType::Bool,
+        "false",
+        "Never move the cursor automatically, for example when focus is changed with the keyboard.",
+    ),
+    spec(
+        "cursor:hide_on_key_press",
+        OptionType::Bool,
+        "false",
+        "Hide the cursor on key presses until the mouse is moved again.",
+    ),
+    spec(
+        "general:layout",
+        OptionType::Choice(&LAYOUTS),
+        "dwindle",
+        "Tiling layout used on all workspaces.",
+    ),
+    spec(
+        "dwindle:pseudotile",
+        OptionType::Bool,
+        "false",
+        "Let pseudotiled windows keep their floating size inside their tile.",
+    ),
+    spec(
+        "dwindle:preserve_split",
+        OptionType::Bool,
+        "false",
+        "Keep the split direction when the window is resized or moved.",
+    ),
+    spec(
+        "dwindle:smart_split",
+        OptionType::Bool,
+        "false",
+        "Split towards the edge of the window the cursor is closest to.",
+    ),
+    spec(
+        "dwindle:force_split",
+        OptionType::Choice(&FORCE_SPLIT_MODES),
+        "0",
+        "Which half of the split window a new window takes.",
+    ),
+    spec_since(
+        "master:new_status",
+        OptionType::Choice(&MASTER_NEW_STATUSES),
+        "slave",
+        "Whether new windows join the stack, take the master area or inherit the status of the focused window.",
+        "0.41.0",
+    ),
+    spec(
+        "master:mfact",
+        OptionType::Float {
+            min: 0.0,
+            max: 1.0,
+            step: 0.05,
+        },
+        "0.55",
+        "Share of the monitor taken by the master area.",
+    ),
+    spec(
+        "master:orientation",
+        OptionType::Choice(&MASTER_ORIENTATIONS),
+        "left",
+        "Where the master area sits.",
+    ),
+    spec(
+        "misc:disable_hyprland_logo",
+        OptionType::Bool,
+        "false",
+        "Hide the Hyprland logo and anime mascot drawn on the background.",
+    ),
+    spec(
+        "misc:disable_splash_rendering",
+        OptionType::Bool,
+        "false",
+        "Hide the random splash text drawn on the background.",
+    ),
+    spec(
+        "misc:vrr",
+        OptionType::Choice(&[
+            "0 - Off",
+            "1 - On",
+            "2 - Fullscreen only",
+            "3 - Fullscreen games and videos",
+        ]),
+        "0",
+        "Adaptive sync for all monitors. Fullscreen modes only turn it on while a window is fullscreen.",
+    ),
+    spec(
+        "misc:vfr",
+        OptionType::Bool,
+        "true",
+        "Only render frames when something changed, which saves power. Turning it off can help with stutter.",
+    ),
+    spec(
+        "misc:render_unfocused_fps",
+        OptionType::Int { min: 1, max: 60 },
+        "15",
+        "Frame rate hidden windows are told to render at, for apps that keep rendering in the background.",
+    ),
+    spec(
+        "misc:focus_on_activate",
+        OptionType::Bool,
+        "false",
+        "Focus windows that ask to be activated, e.g. a browser when a link is opened elsewhere.",
+    ),
+    spec(
+        "misc:new_window_takes_over_fullscreen",
+        OptionType::Choice(&[
+            "0 - New window stays behind",
+            "1 - New window takes over",
+            "2 - Leave fullscreen",
+        ]),
+        "0",
+        "What happens to a fullscreen window when a new window opens on its workspace.",
+    ),
+    spec(
+        "misc:middle_click_paste",
+        OptionType::Bool,
+        "true",
+        "Paste the primary selection with the middle mouse button.",
+    ),
+    spec(
+        "misc:enable_swallow",
+        OptionType::Bool,
+        "false",
+        "Hide a terminal while an app started from it is open, the app takes its place.",
+    ),
+    spec(
+        "misc:swallow_regex",
+        OptionType::Regex,
+        "",
+        "Class of the windows that get swallowed, usually your terminal, e.g. ^(kitty|foot)$.",
+    ),
+    spec(
+        "misc:swallow_exception_regex",
+        OptionType::Regex,
+        "",
+        "Title of the swallowed windows that are kept visible anyway, e.g. ^(.*wev.*)$.",
+    ),
+    spec_since(
+        "xwayland:enabled",
+        OptionType::Bool,
+        "true",
+        "Run X11 apps through XWayland. Without it only native Wayland apps start.",
+        "0.39.0",
+    ),
+    spec(
+        "xwayland:force_zero_scaling",
+        OptionType::Bool,
+        "false",
+        "Don't scale X11 apps, they render at native resolution and look sharp but small. Pair with GDK_SCALE.",
+    ),
+    spec(
+        "xwayland:use_nearest_neighbor",
+        OptionType::Bool,
+        "true",
+        "Scale X11 apps with nearest neighbour filtering, pixelated instead of blurry.",
+    ),
+    spec(
+        "gestures:workspace_swipe",
+        OptionType::Bool,
+        "false",
+        "Swipe horizontally with multiple fingers to switch workspaces.",
+    ),
+    spec(
+        "gestures:workspace_swipe_fingers",
+        OptionType::Int { min: 3, max: 5 },
+        "3",
+        "Number of fingers for the workspace swipe.",
+    ),
+    spec(
+        "gestures:workspace_swipe_distance",
+        OptionType::Int {
+            min: 100,
+            max: 1000,
+        },
+        "300",
+        "Distance of a full swipe in pixels.",
+    ),
+    spec(
+        "gestures:workspace_swipe_cancel_ratio",
+        OptionType::Float {
+            min: 0.0,
+            max: 1.0,
+            step: 0.05,
+        },
+        "0.5",
+        "How far a swipe has to go before letting go switches the workspace.",
+    ),
+    spec(
+        "gestures:workspace_swipe_invert",
+        OptionType::Bool,
+        "true",
+        "Swipe in the opposite direction to switch workspaces.",
+    ),
+    spec(
+        "gestures:workspace_swipe_create_new",
+        OptionType::Bool,
+        "true",
+        "Swiping right past the last workspace creates a new one.",
+    ),
+    spec(
+        "gestures:workspace_swipe_forever",
+        OptionType::Bool,
+        "false",
+        "Keep switching workspaces in a single swipe instead of stopping at the next one.",
+    ),
+    spec(
+        "input:touchpad:natural_scroll",
+        OptionType::Bool,
+        "false",
+        "Invert the scroll direction so content follows your fingers, like on a phone.",
+    ),
+    spec(
+        "input:touchpad:tap-to-click",
+        OptionType::Bool,
+        "true",
+        "Tapping the touchpad with 1, 2 or 3 fingers sends a left, right or middle click.",
+    ),
+    spec(
+        "input:touchpad:disable_while_typing",
+        OptionType::Bool,
+        "true",
+        "Ignore touchpad input while the keyboard is in use to avoid accidental palm clicks.",
+    ),
+    spec(
+        "input:touchpad:clickfinger_behavior",
+        OptionType::Bool,
+        "false",
+        "Pressing the touchpad with 1, 2 or 3 fingers sends a left, right or middle click instead of using button areas.",
+    ),
+    spec(
+        "input:touchpad:scroll_factor",
+        OptionType::Float {
+            min: 0.1,
+            max: 3.0,
+            step: 0.05,
+        },
+        "1.0",
+        "Multiplier applied to the amount of scroll movement.",
+    ),
+    spec(
+        "input:touchpad:drag_lock",
+        OptionType::Bool,
+        "false",
+        "Lifting a finger briefly while tap-dragging will not drop the dragged item.",
+    ),
+    spec(
+        "input:touchpad:middle_button_emulation",
+        OptionType::Bool,
+        "false",
+        "Pressing left and right buttons at the same time sends a middle click.",
+    ),
+    spec(
+        "input:tablet:output",
+        OptionType::Text,
+        "",
+        "Monitor the tablet is mapped to, empty maps it to all of them.",
+    ),
+    spec(
+        "input:tablet:region_position",
+        OptionType::Text,
+        "0 0",
+        "Top left corner of the area the tablet maps to, in monitor pixels.",
+    ),
+    spec(
+        "input:tablet:region_size",
+        OptionType::Text,
+        "0 0",
+        "Size of the area the tablet maps to, 0 0 maps it to the whole monitor.",
+    ),
+    spec(
+        "input:tablet:relative_input",
+        OptionType::Bool,
+        "false",
+        "Move the cursor relative to its position like a mouse instead of jumping to where the pen touches.",
+    ),
+    spec(
+        "input:touchdevice:output",
+        OptionType::Text,
+        "",
+        "Monitor touchscreens are mapped to.",
+    ),
+    spec(
+        "general:gaps_in",
+        OptionType::Text,
+        "5",
+        "Space between neighbouring windows in pixels, one value or top, right, bottom and left.",
+    ),
+    spec(
+        "general:gaps_out",
+        OptionType::Text,
+        "20",
+        "Space between windows and the monitor edges in pixels, one value or top, right, bottom and left.",
+    ),
+    spec(
+        "general:border_size",
+        OptionType::Int { min: 0, max: 20 },
+        "1",
+        "Width of the window border in pixels.",
+    ),
+    spec(
+        "general:col.active_border",
+        OptionType::Text,
+        "0xffffffff",
+        "Colour of the focused window's border. Add more colours to make it a gradient.",
+    ),
+    spec(
+        "general:col.inactive_border",
+        OptionType::Text,
+        "0xff444444",
+        "Colour of unfocused window borders. Add more colours to make it a gradient.",
+    ),
+    spec(
+        "decoration:rounding",
+        OptionType::Int { min: 0, max: 20 },
+        "0",
+        "Radius of rounded window corners in pixels.",
+    ),
+    spec(
+        "decoration:active_opacity",
+        OptionType::Float {
+            min: 0.0,
+            max: 1.0,
+            step: 0.05,
+        },
+        "1.0",
+        "Opacity of the focused window.",
+    ),
+    spec(
+        "decoration:inactive_opacity",
+        OptionType::Float {
+            min: 0.0,
+            max: 1.0,
+            step: 0.05,
+        },
+        "1.0",
+        "Opacity of unfocused windows.",
+    ),
+    spec(
+        "decoration:dim_inactive",
+        OptionType::Bool,
+        "false",
+        "Darken unfocused windows.",
+    ),
+    spec(
+        "decoration:blur:enabled",
+        OptionType::Bool,
+        "true",
+        "Blur what is behind transparent windows.",
+    ),
+    spec(
+        "decoration:blur:xray",
+        OptionType::Bool,
+        "false",
+        "Floating windows ignore tiled windows in their blur and only blur the wallpaper. Reduces overhead.",
+    ),
+    spec(
+        "decoration:blur:new_optimizations",
+        OptionType::Bool,
+        "true",
+        "Enable further blur optimizations. Keep this on unless you notice artifacts.",
+    ),
+    spec(
+        "decoration:blur:size",
+        OptionType::Int { min: 1, max: 20 },
+        "8",
+        "Blur radius. Higher values are blurrier but cost more.",
+    ),
+    spec(
+        "decoration:blur:passes",
+        OptionType::Int { min: 1, max: 4 },
+        "1",
+        "How many times the blur is applied. Each pass makes it smoother and more expensive.",
+    ),
+    spec(
+        "decoration:blur:noise",
+        OptionType::Float {
+            min: 0.0,
+            max: 1.0,
+            step: 0.01,
+        },
+        "0.0117",
+        "Amount of noise added to the blur to reduce banding.",
+    ),
+    spec(
+        "decoration:blur:contrast",
+        OptionType::Float {
+            min: 0.0,
+            max: 2.0,
+            step: 0.05,
+        },
+        "0.8916",
+        "Contrast modulation of the blurred background.",
+    ),
+    spec(
+        "decoration:blur:brightness",
+        OptionType::Float {
+            min: 0.0,
+            max: 2.0,
+            step: 0.05,
+        },
+        "0.8172",
+        "Brightness modulation of the blurred background.",
+    ),
+    spec(
+        "decoration:blur:vibrancy",
+        OptionType::Float {
+            min: 0.0,
+            max: 1.0,
+            step: 0.05,
+        },
+        "0.1696",
+        "Increase saturation of the blurred colours.",
+    ),
+    spec(
+        "decoration:shadow:enabled",
+        OptionType::Bool,
+        "true",
+        "Draw a drop shadow behind windows.",
+    ),
+    spec(
+        "decoration:shadow:sharp",
+        OptionType::Bool,
+        "false",
+        "Draw the shadow with a hard edge, like an infinitely high render power.",
+    ),
+    spec(
+        "decoration:shadow:ignore_window",
+        OptionType::Bool,
+        "true",
+        "Only draw the shadow around the window instead of also behind it.",
+    ),
+    spec(
+        "decoration:shadow:range",
+        OptionType::Int { min: 0, max: 100 },
+        "4",
+        "Size of the shadow in pixels.",
+    ),
+    spec(
+        "decoration:shadow:render_power",
+        OptionType::Int { min: 1, max: 4 },
+        "3",
+        "How quickly the shadow falls off. Higher values give a sharper falloff.",
+    ),
+    spec(
+        "decoration:shadow:scale",
+        OptionType::Float {
+            min: 0.0,
+            max: 1.0,
+            step: 0.05,
+        },
+        "1.0",
+        "Scale of the shadow relative to the window.",
+    ),
+    spec(
+        "decoration:shadow:color",
+        OptionType::Text,
+        "0xee1a1a1a",
+        "Colour of the shadow.",
+    ),
+];
+
+static REGISTRY: OnceLock<Vec<OptionSpec>> = OnceLock::new();
+
+/// Curated options followed by every other option `hyprctl descriptions` knows, loaded once
+pub fn registry() -> &'static [OptionSpec] {
+    REGISTRY.get_or_init(|| {
+        let descriptions = get_descriptions().unwrap_or_else(|e| {
+            eprintln!("Failed to get option descriptions: {}", e);
+            Vec::new()
+        });
+        merge_descriptions(&descriptions)
+    })
+}
+
+/// The registry lives for the whole run, so imported strings are leaked to fit `OptionSpec`
+fn leak(value: String) -> &'static str {
+    Box::leak(value.into_boxed_str())
+}
+
+/// Curated options keep their type and choice labels but take the official description
+fn merge_descriptions(descriptions: &[Description]) -> Vec<OptionSpec> {
+    let mut options: Vec<OptionSpec> = OPTIONS
+        .iter()
+        .map(|curated| {
+            let mut option = curated.clone();
+            if let Some(description) = descriptions
+                .iter()
+                .find(|d| d.value == curated.path && !d.description.is_empty())
+            {
+                option.description = leak(description.description.clone());
+            }
+            option
+        })
+        .collect();
+
+    options.extend(
+        descriptions
+            .iter()
+            .filter(|d| !OPTIONS.iter().any(|o| o.path == d.value))
+            .filter_map(imported_option),
+    );
+    options
+}
+
+/// An option from its description, `None` for types this version of hyprctl doesn't document
+fn imported_option(description: &Description) -> Option<OptionSpec> {
+    let range = description.min().zip(description.max());
+
+    let option_type = match (description.option_type, range) {
+        (0, _) => OptionType::Bool,
+        (1, Some((min, max))) => OptionType::Int {
+            min: min as i64,
+            max: max as i64,
+        },
+        (1, None) => OptionType::Number { integer: true },
+        (2, Some((min, max))) => OptionType::Float {
+            min: min as f32,
+            max: max as f32,
+            step: ((max - min) / 100.0) as f32,
+        },
+        (2, None) => OptionType::Number { integer: false },
+        (6, _) => {
+            let labels: Vec<&'static str> = description
+                .choices()
+                .into_iter()
+                .map(|(value, name)| leak(format!("{} - {}", value, name)))
+                .collect();
+            OptionType::Choice(Box::leak(labels.into_boxed_slice()))
+        }
+        // strings, colours, gradients and vectors are edited as text
+        (3 | 4 | 5 | 7 | 8, _) => OptionType::Text,
+        _ => return None,
+    };
+
+    Some(OptionSpec {
+        path: leak(description.value.clone()),
+        option_type,
+        default: leak(description.default_value()),
+        description: leak(description.description.clone()),
+        since: None,
+    })
+}
+
+pub fn find_option(path: &str) -> Option<&'static OptionSpec> {
+    registry().iter().find(|o| o.path == path)
+}
+
+/// Current value of a registered bool option, `false` for options that aren't registered
+pub fn read_bool(path: &str) -> bool {
+    find_option(path)
+        .and_then(|o| parse_bool(&o.read()).or_else(|| parse_bool(o.default)))
+        .unwrap_or_default()
+}
+
+/// Current value of a registered number option, its default when hyprctl reports something else
+pub fn read_number<T: FromStr + Default>(path: &str) -> T {
+    find_option(path)
+        .and_then(|o| o.read().parse().ok().or_else(|| o.default.parse().ok()))
+        .unwrap_or_default()
+}
+
+/// Current value of a registered option as text, empty for options that aren't registered
+pub fn read_text(path: &str) -> String {
+    find_option(path).map(OptionSpec::read).unwrap_or_default()
+}
+
+/// Stage a new value of a registered option, it is checked against the option before staging
+pub fn write_option(path: &str, value: impl Display) -> anyhow::Result<()> {
+    let option = find_option(path).ok_or_else(|| anyhow::anyhow!("Unknown option {}", path))?;
+    SettingWriter::build_single(&option.prefix(), value)?.write()
+}
+
+/// Curated options of a category such as `misc:`, in the order they are listed. Imported
+/// options are only edited on the all options page.
+pub fn options_in(category: &str) -> Vec<&'static OptionSpec> {
+    OPTIONS
+        .iter()
+        .filter(|o| o.path.starts_with(category))
+        .collect()
+}
+
+/// Value written for a choice, the mode number of labels like "1 - On"
+pub fn choice_value(label: &str) -> &str {
+    label
+        .split_once(" - ")
+        .map(|(value, _)| value)
+        .unwrap_or(label)
+}
+
+pub fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "1" | "true" | "yes" | "on" => Some(true),
+        "0" | "false" | "no" | "off" => Some(false),
+        _ => None,
+    }
+}
+
+/// Whether a regex matches the whole text, the way Hyprland matches window classes
+pub fn test_regex(pattern: &str, text: &str) -> anyhow::Result<bool> {
+    let regex = Regex::new(&format!("^(?:{})$", pattern))
+        .map_err(|e| anyhow::anyhow!("Invalid regex {}: {}", pattern, e))?;
+    Ok(regex.is_match(text))
+}
+
+/// Whether a version such as `0.45.2` is at least `required`. Missing parts count as 0, so
+/// `0.45` and `0.45.0` are the same version.
+pub fn version_at_least(version: &str, required: &str) -> bool {
+    let parse = |v: &str| -> (u32, u32, u32) {
+        let mut parts = v.trim_start_matches('v').split('.').map(|part| {
+            // tags of development builds look like 0.45.2-12-gabcdef
+            let digits: String = part.chars().take_while(|c| c.is_ascii_digit()).collect();
+            digits.parse().unwrap_or(0)
+        });
+        (
+            parts.next().unwrap_or(0),
+            parts.next().unwrap_or(0),
+            parts.next().unwrap_or(0),
+        )
+    };
+    parse(version) >= parse(required)
+}
+
+impl OptionSpec {
+    /// Prefix of the override line, e.g. `misc:vrr=`
+    pub fn prefix(&self) -> String {
+        format!("{}=", self.path)
+    }
+
+    /// Name shown next to the control, e.g. `Disable hyprland logo` for `misc:disable_hyprland_logo`
+    pub fn label(&self) -> String {
+        let name = self
+            .path
+            .rsplit(':')
+            .next()
+            .unwrap_or(self.path)
+            .replace('_', " ");
+        let mut chars = name.chars();
+        match chars.next() {
+            Some(first) => first.to_uppercase().chain(chars).collect(),
+            None => name,
+        }
+    }
+
+    /// `(min, max, step)` of a number option with a range, e.g. for a slider
+    pub fn range(&self) -> Option<(f32, f32, f32)> {
+        match self.option_type {
+            OptionType::Int { min, max } => Some((min as f32, max as f32, 1.0)),
+            OptionType::Float { min, max, step } => Some((min, max, step)),
+            _ => None,
+        }
+    }
+
+    /// Current value as reported by hyprctl, the default when hyprctl is not reachable
+    pub fn read(&self) -> String {
+        match get_option(self.path) {
+            Ok(value) => match self.option_type {
+                // hyprctl reports unset strings as [[EMPTY]]
+                OptionType::Text | OptionType::Regex if value == "[[EMPTY]]" => String::new(),
+                // floats come back as 0.500000
+                OptionType::Float { .. } | OptionType::Number { integer: false } => {
+                    value.parse::<f32>().map(|v| v.to_string()).unwrap_or(value)
+                }
+                _ => value,
+            },
+            Err(_) => self.default.to_string(),
+        }
+    }
+
+    /// Check a value parses as the option type before it is written, Hyprland rejects the whole
+    /// line otherwise. Ranges are left to the sliders.
+    pub fn validate(&self, value: &str) -> anyhow::Result<()> {
+        let invalid = || anyhow::anyhow!("Invalid value for {}: {}", self.path, value);
+
+        match self.option_type {
+            OptionType::Bool => parse_bool(value).map(|_| ()).ok_or_else(invalid),
+            OptionType::Int { .. } | OptionType::Number { integer: true } => {
+                value.parse::<i64>().map(|_| ()).map_err(|_| invalid())
+            }
+            OptionType::Float { .. } | OptionType::Number { integer: false } => {
+                value.parse::<f32>().map(|_| ()).map_err(|_| invalid())
+            }
+            OptionType::Choice(choices) => {
+                if choices.iter().any(|c| choice_value(c) == value) {
+                    Ok(())
+                } else {
+                    Err(invalid())
+                }
+            }
+            OptionType::Text => Ok(()),
+            OptionType::Regex => test_regex(value, "").map(|_| ()),
+        }
+    }
+
+    /// Whether the running Hyprland knows the option, assumed when the version is unknown
+    pub fn supported(&self, version: Option<&str>) -> bool {
+        match (self.since, version) {
+            (Some(since), Some(version)) => version_at_least(version, since),
+            _ => true,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::util::descriptions::parse_descriptions;
+
+    fn fixture() -> Vec<Description> {
+        parse_descriptions(include_str!("../fixtures/hyprctl_descriptions.json")).unwrap()
+    }
+
+    fn merged(path: &str) -> OptionSpec {
+        merge_descriptions(&fixture())
+            .into_iter()
+            .find(|o| o.path == path)
+            .unwrap()
+    }
+
+    #[test]
+    fn curated_options_keep_their_type() {
+        let vrr = merged("misc:vrr");
+        assert!(matches!(vrr.option_type, OptionType::Choice(c) if c[1] == "1 - On"));
+        assert!(vrr.description.starts_with("controls the VRR"));
+
+        let layout = merged("general:layout");
+        assert!(matches!(layout.option_type, OptionType::Choice(_)));
+    }
+
+    #[test]
+    fn curated_options_come_first_and_once() {
+        let options = merge_descriptions(&fixture());
+
+        assert_eq!(options[0].path, OPTIONS[0].path);
+        assert_eq!(
+            options
+                .iter()
+                .filter(|o| o.path == "input:sensitivity")
+                .count(),
+            1
+        );
+    }
+
+    #[test]
+    fn imported_types_and_defaults() {
+        let repeat_rate = merged("input:repeat_rate");
+        assert!(matches!(
+            repeat_rate.option_type,
+            OptionType::Int { min: 0, max: 200 }
+        ));
+        assert_eq!(repeat_rate.default, "25");
+
+        let scroll_factor = merged("input:scroll_factor");
+        assert!(matches!(
+            scroll_factor.option_type,
+            OptionType::Number { integer: false }
+        ));
+        assert_eq!(scroll_factor.default, "1.0");
+
+        let overlay = merged("debug:overlay");
+        assert!(matches!(overlay.option_type, OptionType::Bool));
+        assert_eq!(overlay.default, "false");
+
+        let scanout = merged("render:direct_scanout");
+        assert!(
+            matches!(scanout.option_type, OptionType::Choice(c) if c == ["0 - off", "1 - on", "2 - auto"])
+        );
+
+        let border = merged("general:col.active_border");
+        assert!(matches!(border.option_type, OptionType::Text));
+        assert_eq!(border.default, "0xffffffff");
+    }
+
+    #[test]
+    fn unknown_types_are_skipped() {
+        let mut descriptions = fixture();
+        for description in &mut descriptions {
+            if description.value == "debug:overlay" {
+                description.option_type = 42;
+            }
+        }
+
+        let options = merge_descriptions(&descriptions);
+        assert!(!options.iter().any(|o| o.path == "debug:overlay"));
+        assert!(options.iter().any(|o| o.path == "input:repeat_rate"));
+    }
+
+    #[test]
+    fn ranges_only_bound_the_sliders() {
+        let rounding = find_curated("decoration:rounding");
+        assert!(rounding.validate("30").is_ok());
+        assert!(rounding.validate("3.5").is_err());
+
+        let sensitivity = find_curated("input:sensitivity");
+        assert!(sensitivity.validate("1.5").is_ok());
+        assert!(sensitivity.validate("fast").is_err());
+    }
+
+    #[test]
+    fn versions_compare_by_part() {
+        assert!(version_at_least("0.45", "0.45.0"));
+        assert!(version_at_least("v0.45.0", "0.45"));
+        assert!(version_at_least("v0.45.2-12-gabcdef", "0.45.1"));
+        assert!(version_at_least("0.100.0", "0.45.0"));
+        assert!(!version_at_least("0.44.9", "0.45"));
+    }
+
+    fn find_curated(path: &str) -> &'static OptionSpec {
+        OPTIONS.iter().find(|o| o.path == path).unwrap()
+    }
+}
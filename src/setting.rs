@@ -1,16 +1,33 @@
+use std::sync::OnceLock;
 
+use crate::option_registry::find_option;
 use crate::setting_writer::SettingLine;
+use crate::util::keybinds::combo_key;
+use crate::util::window_rules::split_rule;
 
-const MONITOR_SETTING_PREFIX: &str = "monitor=";
-const KEYBOARD_LAYOUT_PREFIX: &str = "input:kb_layout=";
-const MOUSE_SENSITIVITY_PREFIX: &str = "input:sensitivity=";
-const MOUSE_FORCE_NO_ACCEL_PREFIX: &str = "input:force_no_accel=";
+const MONITOR_SETTING_PREFIX: &str = "monitor";
+const ENV_SETTING_PREFIX: &str = "env";
+const BEZIER_SETTING_PREFIX: &str = "bezier";
+const ANIMATION_SETTING_PREFIX: &str = "animation";
+const BIND_SETTING_PREFIX: &str = "bind";
+const UNBIND_SETTING_PREFIX: &str = "unbind";
+const WINDOW_RULE_SETTING_PREFIX: &str = "windowrule";
+const LAYER_RULE_SETTING_PREFIX: &str = "layerrule";
+const WORKSPACE_RULE_SETTING_PREFIX: &str = "workspace";
 
 struct MonitorSetting;
-struct KeyboardLayoutSetting;
-// I dont like this but i am too stupid and tired to think
-struct MouseSensitivitySetting;
-struct MouseForceNoAccelSetting;
+/// Options of the option registry such as `input:sensitivity = 0.5`, keyed by their path
+struct OptionSetting;
+/// Keyword lines such as `env = NAME,value` or `bezier = NAME, ...`
+struct KeywordSetting(&'static str);
+/// `bind` lines with any flags, and the `unbind` lines removing binds from the main config
+struct BindSetting;
+/// `windowrule` and `windowrulev2` lines, keyed by the rule name and its matchers
+struct WindowRuleSetting;
+/// `layerrule` lines, keyed by the rule name and namespace
+struct LayerRuleSetting;
+/// `workspace` rule lines, keyed by their workspace selector
+struct WorkspaceRuleSetting;
 
 impl SettingLine for MonitorSetting {
     fn prefix(&self) -> &str {
@@ -18,67 +35,184 @@ impl SettingLine for MonitorSetting {
     }
 
     fn extract_key(&self, line: &str) -> Option<String> {
-        let trimmed = line.trim();
-        if let Some(setting) = trimmed.strip_prefix(self.prefix())
-            && let Some(comma_pos) = setting.find(',')
-        {
-            return Some(setting[..comma_pos].to_string());
+        // keyed by the monitor name, whatever spacing the line uses
+        let (keyword, value) = line.trim().split_once('=')?;
+        if keyword.trim() != self.prefix() {
+            return None;
         }
-        None
+        let (name, _) = value.split_once(',')?;
+        Some(name.trim().to_string())
     }
 }
 
-impl SettingLine for KeyboardLayoutSetting {
+impl SettingLine for OptionSetting {
     fn prefix(&self) -> &str {
-        KEYBOARD_LAYOUT_PREFIX
+        // options have no common prefix, they are looked up in the registry instead
+        ""
     }
 
     fn extract_key(&self, line: &str) -> Option<String> {
-        if line.trim().starts_with(self.prefix()) {
-            // For keyboard layout, we use a constant key since there's only one
-            Some("kb_layout".to_string())
-        } else {
-            None
-        }
+        let (path, _) = line.trim().split_once('=')?;
+        find_option(path.trim()).map(|option| option.path.to_string())
+    }
+}
+
+impl SettingLine for KeywordSetting {
+    fn prefix(&self) -> &str {
+        self.0
+    }
+
+    fn extract_key(&self, line: &str) -> Option<String> {
+        // keyword lines are keyed by their first argument, e.g. the variable or curve name
+        let setting = line.trim().strip_prefix(self.prefix())?;
+        let setting = setting.trim_start().strip_prefix('=')?;
+        let (name, _) = setting.split_once(',')?;
+        Some(name.trim().to_string())
     }
 }
 
-impl SettingLine for MouseSensitivitySetting {
+impl SettingLine for BindSetting {
     fn prefix(&self) -> &str {
-        MOUSE_SENSITIVITY_PREFIX
+        BIND_SETTING_PREFIX
     }
 
     fn extract_key(&self, line: &str) -> Option<String> {
-        if line.trim().starts_with(self.prefix()) {
-            // For mouse sensitivity, we use a constant key since there's only one
-            Some("sensitivity".to_string())
+        let (keyword, value) = line.trim().split_once('=')?;
+        let keyword = keyword.trim();
+
+        // binde, bindl, ... of the same combo are different binds, unbind lines are kept apart
+        // so a bind replacing one from the main config can follow its unbind line
+        let kind = if keyword == UNBIND_SETTING_PREFIX {
+            keyword.to_string()
         } else {
-            None
+            // flags may come in any order, `bindel` and `bindle` are the same bind
+            let mut flags: Vec<char> = keyword.strip_prefix(self.prefix())?.chars().collect();
+            if !flags.iter().all(|c| c.is_ascii_lowercase()) {
+                return None;
+            }
+            flags.sort_unstable();
+            format!("{}{}", self.prefix(), flags.into_iter().collect::<String>())
+        };
+
+        let mut args = value.split(',');
+        let mods = args.next()?;
+        let key = args.next()?;
+        Some(format!("{} {}", kind, combo_key(mods, key)))
+    }
+}
+
+impl SettingLine for WindowRuleSetting {
+    fn prefix(&self) -> &str {
+        WINDOW_RULE_SETTING_PREFIX
+    }
+
+    fn extract_key(&self, line: &str) -> Option<String> {
+        let (keyword, value) = line.trim().split_once('=')?;
+        if !matches!(keyword.trim().strip_prefix(self.prefix())?, "" | "v2") {
+            return None;
         }
+
+        // rules add up, so `size 800 600` only replaces another size rule for the same windows
+        let (rule, mut matchers) = split_rule(value)?;
+        let rule = rule.split_whitespace().next()?;
+        matchers.sort_unstable();
+
+        Some(format!("{} {}", rule, matchers.join(", ")))
     }
 }
 
-impl SettingLine for MouseForceNoAccelSetting {
+impl SettingLine for LayerRuleSetting {
     fn prefix(&self) -> &str {
-        MOUSE_FORCE_NO_ACCEL_PREFIX
+        LAYER_RULE_SETTING_PREFIX
     }
 
     fn extract_key(&self, line: &str) -> Option<String> {
-        if line.trim().starts_with(self.prefix()) {
-            // For mouse force_no_accel, we use a constant key since there's only one
-            Some("force_no_accel".to_string())
-        } else {
-            None
+        let setting = line.trim().strip_prefix(self.prefix())?;
+        let setting = setting.trim_start().strip_prefix('=')?;
+
+        // `ignorealpha 0.5` replaces `ignorealpha 0.3` for the same namespace
+        let (rule, namespace) = setting.split_once(',')?;
+        let rule = rule.split_whitespace().next()?;
+        Some(format!("{} {}", rule, namespace.trim()))
+    }
+}
+
+impl SettingLine for WorkspaceRuleSetting {
+    fn prefix(&self) -> &str {
+        WORKSPACE_RULE_SETTING_PREFIX
+    }
+
+    fn extract_key(&self, line: &str) -> Option<String> {
+        let (keyword, value) = line.trim().split_once('=')?;
+        if keyword.trim() != self.prefix() {
+            return None;
         }
+
+        // all options of a workspace live on one line
+        let selector = value.split(',').next()?.trim();
+        Some(selector.to_string())
     }
 }
 
-/// Registry of all known setting line types
-pub fn get_setting_handlers() -> Vec<Box<dyn SettingLine>> {
-    vec![
-        Box::new(MonitorSetting),
-        Box::new(KeyboardLayoutSetting),
-        Box::new(MouseSensitivitySetting),
-        Box::new(MouseForceNoAccelSetting),
-    ]
+type Handler = Box<dyn SettingLine + Send + Sync>;
+
+static HANDLERS: OnceLock<Vec<Handler>> = OnceLock::new();
+
+/// Registry of all known setting line types, built once
+pub fn get_setting_handlers() -> &'static [Handler] {
+    HANDLERS.get_or_init(|| {
+        vec![
+            Box::new(MonitorSetting),
+            Box::new(OptionSetting),
+            Box::new(KeywordSetting(ENV_SETTING_PREFIX)),
+            Box::new(KeywordSetting(BEZIER_SETTING_PREFIX)),
+            Box::new(KeywordSetting(ANIMATION_SETTING_PREFIX)),
+            Box::new(BindSetting),
+            Box::new(WindowRuleSetting),
+            Box::new(LayerRuleSetting),
+            Box::new(WorkspaceRuleSetting),
+        ]
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bind_key(line: &str) -> Option<String> {
+        BindSetting.extract_key(line)
+    }
+
+    #[test]
+    fn bind_flags_are_part_of_the_key() {
+        assert_eq!(
+            bind_key("bindel = , XF86AudioRaiseVolume, exec, wpctl set-volume @ 5%+").as_deref(),
+            Some("bindel XF86AUDIORAISEVOLUME")
+        );
+        assert_eq!(
+            bind_key("bindle=,XF86AudioRaiseVolume,exec,true"),
+            bind_key("bindel = , XF86AudioRaiseVolume, exec, true")
+        );
+        assert_eq!(
+            bind_key("bindm = SUPER, mouse:272, movewindow").as_deref(),
+            Some("bindm SUPER+MOUSE:272")
+        );
+        assert_ne!(
+            bind_key("bind = SUPER, Q, killactive"),
+            bind_key("binde = SUPER, Q, killactive")
+        );
+        assert_eq!(bind_key("bind_alt = SUPER, Q, killactive"), None);
+    }
+
+    #[test]
+    fn unbind_lines_are_kept_apart() {
+        assert_eq!(
+            bind_key("unbind = SUPER_SHIFT, q").as_deref(),
+            Some("unbind SUPER+SHIFT+Q")
+        );
+        assert_ne!(
+            bind_key("unbind = SUPER, Q"),
+            bind_key("bind = SUPER, Q, killactive")
+        );
+    }
 }
@@ -1,12 +1,22 @@
 use std::fs;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
 
 use dirs::home_dir;
 
+use crate::change_set::{self, Edit};
+use crate::option_registry::find_option;
 use crate::setting::get_setting_handlers;
+use crate::util::autostart::{AUTOSTART_KEYWORDS, DISABLED_AUTOSTART_PREFIX};
+use crate::util::backup::backup_once;
+use crate::util::option::set_keyword;
 
 pub const HYPR_SETTING_PATH: &str = ".config/hypr/hyprland.conf";
 pub const HYPR_OVERRIDES_PATH: &str = ".config/hypr/conf-overrides.conf";
 
+const SUBMAP_PREFIX: &str = "submap";
+const SUBMAP_RESET: &str = "reset";
+
 pub struct SettingWriter {
     setting_line: (SettingObjectKey, String),
 }
@@ -34,35 +44,152 @@ impl SettingWriter {
         ))
     }
 
+    /// Setting builder for a submap, written as one `submap = name` ... `submap = reset` region
+    pub fn build_submap(submap: SubmapSetting) -> anyhow::Result<Self> {
+        let name = submap.name.trim();
+        if name.is_empty() || name == SUBMAP_RESET {
+            return Err(anyhow::anyhow!("Invalid submap name: {}", submap.name));
+        }
+
+        let mut lines = vec![format!("{} = {}", SUBMAP_PREFIX, name)];
+        lines.extend(
+            submap
+                .binds
+                .iter()
+                .map(|(keyword, value)| format!("{} = {}", keyword, value)),
+        );
+        lines.push(format!("{} = {}", SUBMAP_PREFIX, SUBMAP_RESET));
+
+        Ok(SettingWriter {
+            setting_line: (SettingObjectKey::Submap, lines.join("\n")),
+        })
+    }
+
+    /// Setting builder for the autostart list, every `exec-once`, `exec` and `exec-shutdown`
+    /// line of the overrides file is replaced by `lines`, in order
+    pub fn build_autostart(lines: Vec<String>) -> anyhow::Result<Self> {
+        if let Some(line) = lines.iter().find(|line| !is_autostart_line(line)) {
+            return Err(anyhow::anyhow!("Not an autostart line: {}", line));
+        }
+
+        Ok(SettingWriter {
+            setting_line: (SettingObjectKey::Autostart, lines.join("\n")),
+        })
+    }
+
+    /// Setting builder for a `prefix value` line, a `#` in the value is escaped so it is not read
+    /// as a comment
     pub fn build_single<T: std::fmt::Display>(prefix: &str, value: T) -> anyhow::Result<Self> {
+        let value = value.to_string().replace('#', "##");
         Ok(SettingWriter {
             setting_line: (SettingObjectKey::SingleLine, format!("{}{}", prefix, value)),
         })
     }
 
-    /// Write the setting override file if it doesn't exist, and append the source line to the
-    /// main hyprland setting file.
+    /// Stage this setting in the pending change set, it is written to the overrides file with
+    /// every other pending change once they are applied
     pub fn write(self) -> anyhow::Result<()> {
-        let home_dir = home_dir().ok_or_else(|| {
-            anyhow::anyhow!("Could not determine home directory for the current user")
-        })?;
+        change_set::stage(Edit::Write(self))
+    }
+
+    /// Stage this setting like `write`, once saved the line is also applied to the running
+    /// compositor, e.g. a bind that should work without reloading
+    pub fn write_live(self) -> anyhow::Result<()> {
+        change_set::stage_live(Edit::Write(self))
+    }
+
+    /// Apply this single line setting to the running compositor through `hyprctl keyword`
+    pub fn apply_live(&self) -> anyhow::Result<()> {
+        let line = &self.setting_line.1;
+        let (keyword, value) = line
+            .split_once('=')
+            .ok_or_else(|| anyhow::anyhow!("Not a keyword line: {}", line))?;
+        set_keyword(keyword.trim(), value.trim())
+    }
+
+    /// Stage removing every line of the override file with the same key as this setting, e.g. a
+    /// bind that was deleted. Submaps remove their whole region.
+    pub fn remove(self) -> anyhow::Result<()> {
+        change_set::stage(Edit::Remove(self))
+    }
+
+    /// Short description of the line this writer changes, e.g. for the list of pending changes
+    pub fn summary(&self) -> String {
+        let mut lines = self.setting_line.1.lines();
+        let first = lines.next().unwrap_or_default().trim().to_string();
+
+        match lines.count() {
+            0 => first,
+            more => format!("{} (+{} lines)", first, more),
+        }
+    }
 
-        let hypr_overrides_path = home_dir.join(HYPR_OVERRIDES_PATH);
+    /// Replace or append this setting in the lines of the overrides file
+    pub fn write_lines(&self, lines: &mut Vec<String>) -> anyhow::Result<()> {
+        let regions = submap_regions(lines);
 
-        // Read existing content
-        let content = std::fs::read_to_string(&hypr_overrides_path)?;
-        let mut lines: Vec<String> = content.lines().map(|l| l.to_string()).collect();
+        // Submaps replace their whole region
+        if self.setting_line.0 == SettingObjectKey::Submap {
+            let block: Vec<String> = self.setting_line.1.lines().map(|l| l.to_string()).collect();
+            let name = submap_name(&block[0]);
+
+            match regions.into_iter().find(|(n, _)| Some(n.as_str()) == name) {
+                Some((_, range)) => {
+                    lines.splice(range, block);
+                }
+                None => lines.extend(block),
+            }
+            return Ok(());
+        }
+
+        // Autostart entries are kept in order as one list, disabled ones are commented out
+        if self.setting_line.0 == SettingObjectKey::Autostart {
+            let block: Vec<String> = self.setting_line.1.lines().map(|l| l.to_string()).collect();
+            let position = lines
+                .iter()
+                .enumerate()
+                .position(|(idx, line)| !in_region(&regions, idx) && is_autostart_line(line));
+
+            let mut idx = 0;
+            lines.retain(|line| {
+                let keep = in_region(&regions, idx) || !is_autostart_line(line);
+                idx += 1;
+                keep
+            });
+
+            match position {
+                Some(position) => {
+                    lines.splice(position..position, block);
+                }
+                None => {
+                    close_trailing_submap(lines, &regions);
+                    lines.extend(block);
+                }
+            }
+            return Ok(());
+        }
+
+        // registered options are checked first, Hyprland rejects the whole line otherwise
+        if let Some((path, value)) = self.setting_line.1.split_once('=')
+            && let Some(option) = find_option(path.trim())
+        {
+            option.validate(value.trim())?;
+        }
 
         let handlers = get_setting_handlers();
         let mut replaced = false;
 
         // Try to find a handler that matches this line
-        for handler in &handlers {
+        for handler in handlers {
             if let Some(new_key) = handler.extract_key(self.setting_line.1.as_str())
                 && handler.should_replace()
             {
-                // Find and replace existing line with the same key
-                for existing_line in lines.iter_mut() {
+                // Find and replace existing line with the same key, lines inside a submap
+                // belong to that submap and are left alone
+                for (idx, existing_line) in lines.iter_mut().enumerate() {
+                    if in_region(&regions, idx) {
+                        continue;
+                    }
                     if let Some(existing_key) = handler.extract_key(existing_line)
                         && existing_key == new_key
                     {
@@ -77,15 +204,128 @@ impl SettingWriter {
 
         // If not replaced, append the new line
         if !replaced {
+            close_trailing_submap(lines, &regions);
             lines.push(self.setting_line.1.to_string());
         }
 
-        // Write back to file
-        let updated_content = lines.join("\n") + "\n";
-        fs::write(&hypr_overrides_path, updated_content)?;
-
         Ok(())
     }
+
+    /// Remove this setting from the lines of the overrides file
+    pub fn remove_lines(&self, lines: &mut Vec<String>) {
+        let regions = submap_regions(lines);
+
+        if self.setting_line.0 == SettingObjectKey::Submap {
+            let name = self.setting_line.1.lines().next().and_then(submap_name);
+            if let Some((_, range)) = regions.into_iter().find(|(n, _)| Some(n.as_str()) == name) {
+                lines.drain(range);
+            }
+            return;
+        }
+
+        let handlers = get_setting_handlers();
+        let Some((handler, key)) = handlers.iter().find_map(|handler| {
+            handler
+                .extract_key(self.setting_line.1.as_str())
+                .filter(|_| handler.should_replace())
+                .map(|key| (handler, key))
+        }) else {
+            return;
+        };
+
+        let mut idx = 0;
+        lines.retain(|line| {
+            let keep = in_region(&regions, idx) || handler.extract_key(line).as_ref() != Some(&key);
+            idx += 1;
+            keep
+        });
+    }
+}
+
+/// Absolute path of a config file given relative to the home directory, e.g. `HYPR_SETTING_PATH`
+pub fn config_path(path: &str) -> anyhow::Result<PathBuf> {
+    let home_dir = home_dir().ok_or_else(|| {
+        anyhow::anyhow!("Could not determine home directory for the current user")
+    })?;
+
+    Ok(home_dir.join(path))
+}
+
+/// Replace a config file through a temporary file next to it, a failed write leaves it untouched.
+/// The file is backed up before its first change in a session.
+pub fn write_config_file(path: &Path, content: &str) -> anyhow::Result<()> {
+    backup_once(path)?;
+
+    let temp_path = path.with_extension("conf.tmp");
+
+    fs::write(&temp_path, content)?;
+    fs::rename(&temp_path, path)?;
+    Ok(())
+}
+
+pub fn overrides_path() -> anyhow::Result<PathBuf> {
+    config_path(HYPR_OVERRIDES_PATH)
+}
+
+/// Name of the submap a `submap = name` line starts, `reset` for the line ending one
+fn submap_name(line: &str) -> Option<&str> {
+    let (keyword, name) = line.trim().split_once('=')?;
+    (keyword.trim() == SUBMAP_PREFIX).then_some(name.trim())
+}
+
+/// Line ranges of every `submap = name` ... `submap = reset` region, including both lines.
+/// A region that is never reset runs until the next submap or the end of the file.
+fn submap_regions<T: AsRef<str>>(lines: &[T]) -> Vec<(String, Range<usize>)> {
+    let mut regions = Vec::new();
+    let mut open: Option<(String, usize)> = None;
+
+    for (idx, line) in lines.iter().enumerate() {
+        let Some(name) = submap_name(line.as_ref()) else {
+            continue;
+        };
+
+        if let Some((open_name, start)) = open.take() {
+            if name == SUBMAP_RESET {
+                regions.push((open_name, start..idx + 1));
+                continue;
+            }
+            regions.push((open_name, start..idx));
+        }
+        if name != SUBMAP_RESET {
+            open = Some((name.to_string(), idx));
+        }
+    }
+
+    if let Some((name, start)) = open {
+        regions.push((name, start..lines.len()));
+    }
+
+    regions
+}
+
+/// Whether a line is an autostart entry, including entries disabled by the page. Lines the user
+/// commented out are not entries and are left alone.
+fn is_autostart_line(line: &str) -> bool {
+    let line = line.trim();
+    let line = line.strip_prefix(DISABLED_AUTOSTART_PREFIX).unwrap_or(line);
+    line.split_once('=')
+        .is_some_and(|(keyword, _)| AUTOSTART_KEYWORDS.contains(&keyword.trim()))
+}
+
+/// A submap left open at the end of the file would swallow appended lines, so close it first
+fn close_trailing_submap(lines: &mut Vec<String>, regions: &[(String, Range<usize>)]) {
+    if let Some((_, range)) = regions.last()
+        && range.end == lines.len()
+        && lines
+            .last()
+            .is_none_or(|l| submap_name(l) != Some(SUBMAP_RESET))
+    {
+        lines.push(format!("{} = {}", SUBMAP_PREFIX, SUBMAP_RESET));
+    }
+}
+
+fn in_region(regions: &[(String, Range<usize>)], idx: usize) -> bool {
+    regions.iter().any(|(_, range)| range.contains(&idx))
 }
 
 /// Setting objects for specific things like devices in hyprland
@@ -93,6 +333,8 @@ impl SettingWriter {
 pub enum SettingObjectKey {
     Device,
     SingleLine,
+    Submap,
+    Autostart,
 }
 
 /// Struct representing a device setting for hyprland setting
@@ -103,6 +345,12 @@ pub struct DeviceSetting {
     pub kb_layout: String,
 }
 
+/// A submap and the binds inside it as `(keyword, value)` pairs, e.g. `("binde", ", l, resizeactive, 10 0")`
+pub struct SubmapSetting {
+    pub name: String,
+    pub binds: Vec<(String, String)>,
+}
+
 /// Trait for setting lines that can be overridden in the setting file
 pub trait SettingLine {
     /// Get the prefix that identifies this type of setting line
@@ -117,3 +365,106 @@ pub trait SettingLine {
         true
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn lines(lines: &[&str]) -> Vec<String> {
+        lines.iter().map(|line| line.to_string()).collect()
+    }
+
+    fn submap(name: &str, binds: &[(&str, &str)]) -> SettingWriter {
+        SettingWriter::build_submap(SubmapSetting {
+            name: name.to_string(),
+            binds: binds
+                .iter()
+                .map(|(keyword, value)| (keyword.to_string(), value.to_string()))
+                .collect(),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn submaps_replace_their_region() {
+        let mut config = lines(&[
+            "bind = SUPER, R, submap, resize",
+            "submap = resize",
+            "binde = , l, resizeactive, 10 0",
+            "submap = reset",
+            "bind = SUPER, Q, killactive",
+        ]);
+        submap("resize", &[("binde", ", h, resizeactive, -10 0")])
+            .write_lines(&mut config)
+            .unwrap();
+
+        assert_eq!(
+            config,
+            lines(&[
+                "bind = SUPER, R, submap, resize",
+                "submap = resize",
+                "binde = , h, resizeactive, -10 0",
+                "submap = reset",
+                "bind = SUPER, Q, killactive",
+            ])
+        );
+    }
+
+    #[test]
+    fn new_submaps_are_appended() {
+        let mut config = lines(&["bind = SUPER, Q, killactive"]);
+        submap("move", &[("bind", ", h, movewindow, l")])
+            .write_lines(&mut config)
+            .unwrap();
+
+        assert_eq!(
+            config,
+            lines(&[
+                "bind = SUPER, Q, killactive",
+                "submap = move",
+                "bind = , h, movewindow, l",
+                "submap = reset",
+            ])
+        );
+    }
+
+    #[test]
+    fn trailing_submaps_are_closed_before_appending() {
+        let mut config = lines(&["submap = resize", "binde = , l, resizeactive, 10 0"]);
+        SettingWriter::build_single("bind = ", "SUPER, Q, killactive")
+            .unwrap()
+            .write_lines(&mut config)
+            .unwrap();
+
+        assert_eq!(
+            config,
+            lines(&[
+                "submap = resize",
+                "binde = , l, resizeactive, 10 0",
+                "submap = reset",
+                "bind = SUPER, Q, killactive",
+            ])
+        );
+    }
+
+    #[test]
+    fn binds_inside_submaps_are_left_alone() {
+        let config = lines(&[
+            "submap = resize",
+            "bind = , escape, submap, reset",
+            "submap = reset",
+            "bind = , escape, exec, notify-send hi",
+        ]);
+        let writer =
+            SettingWriter::build_single("bind = ", ", escape, exec, notify-send bye").unwrap();
+
+        let mut written = config.clone();
+        writer.write_lines(&mut written).unwrap();
+        assert_eq!(written[1], "bind = , escape, submap, reset");
+        assert_eq!(written[3], "bind = , escape, exec, notify-send bye");
+
+        let mut removed = config.clone();
+        writer.remove_lines(&mut removed);
+        assert_eq!(removed, config[..3]);
+    }
+}
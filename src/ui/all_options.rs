@@ -0,0 +1,154 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::input::{InputEvent, InputState, TextInput};
+
+use crate::option_registry::{OptionSpec, registry};
+use crate::ui::item_pill::item_pill;
+use crate::ui::option_form::OptionForm;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+
+/// Results shown at once, the registry holds several hundred options
+const MAX_RESULTS: usize = 50;
+
+pub struct AllOptions {
+    search_input: Entity<InputState>,
+    editor: Option<Entity<OptionForm>>,
+}
+
+fn matches_search(option: &OptionSpec, query: &str) -> bool {
+    query.is_empty()
+        || option.path.to_lowercase().contains(query)
+        || option.description.to_lowercase().contains(query)
+}
+
+impl AllOptions {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let search_input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("Search options, e.g. border or decoration:")
+        });
+
+        cx.subscribe(&search_input, |_, _, event: &InputEvent, cx| {
+            if let InputEvent::Change = event {
+                cx.notify();
+            }
+        })
+        .detach();
+
+        Self {
+            search_input,
+            editor: None,
+        }
+    }
+
+    /// Open a form for a single option, it reads the current value from hyprctl
+    fn edit(&mut self, option: &'static OptionSpec, window: &mut Window, cx: &mut Context<Self>) {
+        self.editor =
+            Some(cx.new(|cx| {
+                OptionForm::new(option.path, option.description, vec![option], window, cx)
+            }));
+        cx.notify();
+    }
+
+    /// Search for an option and open its form, used by the command palette
+    pub fn show_option(
+        &mut self,
+        option: &'static OptionSpec,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) {
+        self.search_input.update(cx, |state, cx| {
+            state.set_value(option.path, window, cx);
+        });
+        self.edit(option, window, cx);
+    }
+
+    fn render_option(&self, option: &'static OptionSpec, cx: &mut Context<Self>) -> Div {
+        div()
+            .h_flex()
+            .gap_2()
+            .items_center()
+            .py_1()
+            .child(
+                div()
+                    .v_flex()
+                    .flex_1()
+                    .child(div().text_color(cx.theme().foreground).child(option.path))
+                    .child(
+                        div()
+                            .text_size(px(12.0))
+                            .text_color(cx.theme().muted_foreground)
+                            .child(option.description),
+                    ),
+            )
+            .when(!option.default.is_empty(), |this| {
+                this.child(with_tooltip(
+                    "Default value",
+                    item_pill(cx).text_size(px(10.0)).child(option.default),
+                    cx,
+                ))
+            })
+            .child(
+                Button::new(SharedString::from(format!("edit-option-{}", option.path)))
+                    .label("Edit")
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.edit(option, window, cx);
+                    })),
+            )
+    }
+}
+
+impl Render for AllOptions {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let query = self.search_input.read(cx).value().trim().to_lowercase();
+        let results: Vec<&'static OptionSpec> = registry()
+            .iter()
+            .filter(|option| matches_search(option, &query))
+            .collect();
+        let total = results.len();
+        let rows: Vec<Div> = results
+            .into_iter()
+            .take(MAX_RESULTS)
+            .map(|option| self.render_option(option, cx))
+            .collect();
+
+        div()
+            .v_flex()
+            .gap_4()
+            .when_some(self.editor.clone(), |this, editor| this.child(editor))
+            .child(
+                section_container(cx)
+                    .child(with_tooltip(
+                        "Every option the running Hyprland describes, including ones without a page of their own. Values are checked against the option type before they are written.",
+                        div()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(cx.theme().foreground)
+                            .child("All options"),
+                        cx,
+                    ))
+                    .child(TextInput::new(&self.search_input))
+                    .when(total == 0, |this| {
+                        this.child(
+                            div()
+                                .text_size(px(14.0))
+                                .text_color(cx.theme().muted_foreground)
+                                .child("No options found"),
+                        )
+                    })
+                    .children(rows)
+                    .when(total > MAX_RESULTS, |this| {
+                        this.child(
+                            div()
+                                .text_size(px(12.0))
+                                .text_color(cx.theme().muted_foreground)
+                                .child(format!(
+                                    "Showing {} of {} options, refine the search to see more",
+                                    MAX_RESULTS, total
+                                )),
+                        )
+                    }),
+            )
+    }
+}
@@ -0,0 +1,638 @@
+use std::time::Duration;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::IndexPath;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::dropdown::*;
+use gpui_component::input::{InputState, TextInput};
+use gpui_component::slider::{Slider, SliderEvent, SliderState};
+use gpui_component::switch::Switch;
+
+use crate::setting_writer::SettingWriter;
+use crate::ui::item_pill::item_pill;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::animations::{AnimationNode, Bezier, get_animations};
+use crate::util::option::set_keyword;
+
+const CURVE_CANVAS_SIZE: f32 = 240.0;
+/// Curves may overshoot, so the canvas shows a bit below 0 and above 1
+const CURVE_MIN_Y: f32 = -0.25;
+const CURVE_MAX_Y: f32 = 1.25;
+const HANDLE_SIZE: f32 = 12.0;
+const PREVIEW_BOX_SIZE: f32 = 16.0;
+
+pub struct AnimationSettings {
+    nodes: Vec<AnimationNode>,
+    beziers: Vec<Bezier>,
+    selected_node: usize,
+    selected_bezier: usize,
+    speed_slider: Entity<SliderState>,
+    curve_dropdown: Entity<DropdownState<Vec<String>>>,
+    style_dropdown: Entity<DropdownState<Vec<String>>>,
+    edit_curve_dropdown: Entity<DropdownState<Vec<String>>>,
+    new_curve_input: Entity<InputState>,
+    /// Names of nodes and curves edited since the page was opened, only these are saved
+    changed_nodes: Vec<String>,
+    changed_beziers: Vec<String>,
+    dragging_handle: Option<usize>,
+    canvas_bounds: Bounds<Pixels>,
+}
+
+/// Apply an animation keyword to the running compositor so changes can be seen before saving
+fn preview(keyword: &str, value: &str) {
+    if let Err(e) = set_keyword(keyword, value) {
+        eprintln!("{}", e);
+    }
+}
+
+fn style_label(style: &str) -> String {
+    if style.is_empty() {
+        "default".to_string()
+    } else {
+        style.to_string()
+    }
+}
+
+/// Canvas position of a point in curve space
+fn to_canvas(x: f32, y: f32) -> (f32, f32) {
+    (
+        x * CURVE_CANVAS_SIZE,
+        (CURVE_MAX_Y - y) / (CURVE_MAX_Y - CURVE_MIN_Y) * CURVE_CANVAS_SIZE,
+    )
+}
+
+impl AnimationSettings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let (nodes, beziers) = get_animations().unwrap_or_else(|e| {
+            eprintln!("Failed to get animations: {}", e);
+            (Vec::new(), Vec::new())
+        });
+
+        let curve_names: Vec<String> = beziers.iter().map(|b| b.name.clone()).collect();
+
+        let speed_slider = cx.new(|_cx| SliderState::new().min(0.1).max(20.0).step(0.1));
+        let curve_dropdown = cx.new(|cx| DropdownState::new(curve_names.clone(), None, window, cx));
+        let style_dropdown = cx.new(|cx| DropdownState::new(Vec::new(), None, window, cx));
+        let edit_curve_dropdown = cx.new(|cx| {
+            DropdownState::new(
+                curve_names,
+                (!beziers.is_empty()).then(|| IndexPath::new(0)),
+                window,
+                cx,
+            )
+        });
+        let new_curve_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("New curve name"));
+
+        cx.subscribe(
+            &speed_slider,
+            |this, _, event: &SliderEvent, cx| match event {
+                SliderEvent::Change(value) => {
+                    let speed = (value.start() * 10.0).round() / 10.0;
+                    this.update_node(cx, |node| node.speed = speed);
+                }
+            },
+        )
+        .detach();
+
+        cx.subscribe(
+            &curve_dropdown,
+            |this, _, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(curve)) = event {
+                    let curve = curve.clone();
+                    this.update_node(cx, |node| node.bezier = curve);
+                }
+            },
+        )
+        .detach();
+
+        cx.subscribe(
+            &style_dropdown,
+            |this, _, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(style)) = event {
+                    let style = if style == "default" {
+                        String::new()
+                    } else {
+                        style.clone()
+                    };
+                    this.update_node(cx, |node| node.style = style);
+                }
+            },
+        )
+        .detach();
+
+        cx.subscribe(
+            &edit_curve_dropdown,
+            |this, _, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(curve)) = event
+                    && let Some(idx) = this.beziers.iter().position(|b| &b.name == curve)
+                {
+                    this.selected_bezier = idx;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        let mut settings = Self {
+            nodes,
+            beziers,
+            selected_node: 0,
+            selected_bezier: 0,
+            speed_slider,
+            curve_dropdown,
+            style_dropdown,
+            edit_curve_dropdown,
+            new_curve_input,
+            changed_nodes: Vec::new(),
+            changed_beziers: Vec::new(),
+            dragging_handle: None,
+            canvas_bounds: Bounds::default(),
+        };
+        settings.select_node(0, window, cx);
+        settings
+    }
+
+    /// Load the values of a node into the editor controls
+    fn select_node(&mut self, idx: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(node) = self.nodes.get(idx) else {
+            return;
+        };
+        self.selected_node = idx;
+
+        let speed = node.speed;
+        let bezier = node.bezier.clone();
+        let style = style_label(&node.style);
+
+        let mut styles: Vec<String> = node.styles().iter().map(|s| style_label(s)).collect();
+        // keep styles with parameters such as `popin 80%` selectable
+        if !styles.contains(&style) {
+            styles.push(style.clone());
+        }
+
+        self.speed_slider.update(cx, |state, cx| {
+            state.set_value(speed, window, cx);
+        });
+        self.curve_dropdown.update(cx, |state, cx| {
+            state.set_selected_value(&bezier, window, cx);
+        });
+        self.style_dropdown.update(cx, |state, cx| {
+            state.set_items(styles, window, cx);
+            state.set_selected_value(&style, window, cx);
+        });
+
+        cx.notify();
+    }
+
+    /// Edit the selected node, which then no longer inherits from its parent
+    fn update_node(&mut self, cx: &mut Context<Self>, update: impl FnOnce(&mut AnimationNode)) {
+        let Some(node) = self.nodes.get_mut(self.selected_node) else {
+            return;
+        };
+
+        update(node);
+        node.overridden = true;
+        preview("animation", &node.line_value());
+
+        if !self.changed_nodes.contains(&node.name) {
+            self.changed_nodes.push(node.name.clone());
+        }
+        cx.notify();
+    }
+
+    fn add_curve(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let name = self.new_curve_input.read(cx).value().trim().to_string();
+
+        if name.is_empty() || name.contains(',') || name.contains(char::is_whitespace) {
+            eprintln!("Curve names can not be empty or contain commas or spaces");
+            return;
+        }
+        if self.beziers.iter().any(|b| b.name == name) {
+            eprintln!("A curve named {} already exists", name);
+            return;
+        }
+
+        let bezier = Bezier::new(&name);
+        preview("bezier", &bezier.line_value());
+        self.beziers.push(bezier);
+        self.selected_bezier = self.beziers.len() - 1;
+        self.changed_beziers.push(name.clone());
+
+        let curve_names: Vec<String> = self.beziers.iter().map(|b| b.name.clone()).collect();
+        self.curve_dropdown.update(cx, |state, cx| {
+            state.set_items(curve_names.clone(), window, cx);
+        });
+        self.edit_curve_dropdown.update(cx, |state, cx| {
+            state.set_items(curve_names, window, cx);
+            state.set_selected_value(&name, window, cx);
+        });
+        self.new_curve_input.update(cx, |state, cx| {
+            state.set_value("", window, cx);
+        });
+
+        cx.notify();
+    }
+
+    /// Convert a window position into curve space, clamped to the visible canvas
+    fn curve_position(&self, position: Point<Pixels>) -> (f32, f32) {
+        let x: f32 = (position.x - self.canvas_bounds.left()).into();
+        let y: f32 = (position.y - self.canvas_bounds.top()).into();
+
+        let x = (x / CURVE_CANVAS_SIZE).clamp(0.0, 1.0);
+        let y = (CURVE_MAX_Y - y / CURVE_CANVAS_SIZE * (CURVE_MAX_Y - CURVE_MIN_Y))
+            .clamp(CURVE_MIN_Y, CURVE_MAX_Y);
+
+        ((x * 100.0).round() / 100.0, (y * 100.0).round() / 100.0)
+    }
+
+    /// Write the changed curves and animations, the ones that fail to write are kept to retry
+    fn save(&mut self) {
+        let write = |prefix: &str, value: String| {
+            SettingWriter::build_single(prefix, value)
+                .and_then(|w| w.write())
+                .inspect_err(|e| eprintln!("Failed to write override: {}", e))
+                .is_ok()
+        };
+
+        // curves first, animations can only use curves defined before them
+        for bezier in &self.beziers {
+            if self.changed_beziers.contains(&bezier.name)
+                && write("bezier = ", bezier.line_value())
+            {
+                self.changed_beziers.retain(|name| *name != bezier.name);
+            }
+        }
+        for node in &self.nodes {
+            if self.changed_nodes.contains(&node.name) && write("animation = ", node.line_value()) {
+                self.changed_nodes.retain(|name| *name != node.name);
+            }
+        }
+    }
+
+    fn render_tree(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .v_flex()
+            .gap_1()
+            .children(self.nodes.iter().enumerate().map(|(idx, node)| {
+                let is_selected = self.selected_node == idx;
+                let summary = if node.enabled {
+                    format!("{:.1}ds, {}", node.speed, node.bezier)
+                } else {
+                    "off".to_string()
+                };
+
+                div()
+                    .id(("animation-node", idx))
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .pl(px(8.0 + node.depth() as f32 * 16.0))
+                    .pr_2()
+                    .py_1()
+                    .rounded_md()
+                    .cursor_pointer()
+                    .when(is_selected, |this| this.bg(cx.theme().accent))
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.select_node(idx, window, cx);
+                    }))
+                    .child(
+                        div()
+                            .min_w(px(160.0))
+                            .text_color(cx.theme().foreground)
+                            .child(node.name.clone()),
+                    )
+                    .child(
+                        div()
+                            .text_size(px(12.0))
+                            .text_color(cx.theme().muted_foreground)
+                            .child(summary),
+                    )
+                    .when(!node.overridden, |this| {
+                        this.child(item_pill(cx).text_size(px(10.0)).child("inherited"))
+                    })
+            }))
+    }
+
+    fn render_node_editor(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let node = self.nodes.get(self.selected_node);
+        let enabled = node.map(|n| n.enabled).unwrap_or(false);
+        let speed = node.map(|n| n.speed).unwrap_or(0.0);
+
+        div()
+            .v_flex()
+            .gap_3()
+            .child(
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child(node.map(|n| n.name.clone()).unwrap_or_default()),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(with_tooltip(
+                        "Disabled animations happen instantly.",
+                        div().min_w(px(120.0)).child("Enabled"),
+                        cx,
+                    ))
+                    .child(
+                        Switch::new("animation-enabled-switch")
+                            .checked(enabled)
+                            .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                let checked = *checked;
+                                this.update_node(cx, |node| node.enabled = checked);
+                            })),
+                    ),
+            )
+            .child(
+                div()
+                    .v_flex()
+                    .gap_1()
+                    .child(
+                        div()
+                            .h_flex()
+                            .gap_4()
+                            .items_center()
+                            .child(with_tooltip(
+                                "Duration of the animation in deciseconds, 1ds is 100ms.",
+                                div().min_w(px(120.0)).child("Speed:"),
+                                cx,
+                            ))
+                            .child(
+                                div()
+                                    .text_size(px(14.0))
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(format!("{:.1}ds", speed)),
+                            ),
+                    )
+                    .child(
+                        Slider::new(&self.speed_slider)
+                            .w_full()
+                            .text_color(cx.theme().foreground),
+                    ),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(120.0)).child("Curve:"))
+                    .child(Dropdown::new(&self.curve_dropdown).min_w(px(160.0))),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(with_tooltip(
+                        "How the animation moves, only some animations support styles.",
+                        div().min_w(px(120.0)).child("Style:"),
+                        cx,
+                    ))
+                    .child(Dropdown::new(&self.style_dropdown).min_w(px(160.0))),
+            )
+    }
+
+    fn render_curve_canvas(&self, bezier: &Bezier, cx: &mut Context<Self>) -> impl IntoElement {
+        let view = cx.entity();
+        let curve = bezier.clone();
+        let handles = [
+            to_canvas(bezier.x0, bezier.y0),
+            to_canvas(bezier.x1, bezier.y1),
+        ];
+
+        div()
+            .relative()
+            .size(px(CURVE_CANVAS_SIZE))
+            .bg(rgb(0x3b4252))
+            .border_1()
+            .border_color(rgb(0x4c566a))
+            .cursor_crosshair()
+            .child(
+                canvas(
+                    move |bounds, _, cx| view.update(cx, |this, _| this.canvas_bounds = bounds),
+                    move |bounds, _, window, _| {
+                        let at = |(x, y): (f32, f32)| {
+                            let (x, y) = to_canvas(x, y);
+                            point(bounds.left() + px(x), bounds.top() + px(y))
+                        };
+
+                        // lines at 0 and 1 show where the curve starts and ends
+                        for y in [0.0, 1.0] {
+                            let mut line = PathBuilder::stroke(px(1.0));
+                            line.move_to(at((0.0, y)));
+                            line.line_to(at((1.0, y)));
+                            if let Ok(path) = line.build() {
+                                window.paint_path(path, rgb(0x4c566a));
+                            }
+                        }
+
+                        let mut handle_lines = PathBuilder::stroke(px(1.0));
+                        handle_lines.move_to(at((0.0, 0.0)));
+                        handle_lines.line_to(at((curve.x0, curve.y0)));
+                        handle_lines.move_to(at((1.0, 1.0)));
+                        handle_lines.line_to(at((curve.x1, curve.y1)));
+                        if let Ok(path) = handle_lines.build() {
+                            window.paint_path(path, rgb(0xd8dee9));
+                        }
+
+                        let mut path = PathBuilder::stroke(px(2.0));
+                        path.move_to(at((0.0, 0.0)));
+                        path.cubic_bezier_to(
+                            at((1.0, 1.0)),
+                            at((curve.x0, curve.y0)),
+                            at((curve.x1, curve.y1)),
+                        );
+                        if let Ok(path) = path.build() {
+                            window.paint_path(path, rgb(0x88c0d0));
+                        }
+                    },
+                )
+                .absolute()
+                .size_full(),
+            )
+            .children(handles.iter().map(|(x, y)| {
+                div()
+                    .absolute()
+                    .left(px(x - HANDLE_SIZE / 2.0))
+                    .top(px(y - HANDLE_SIZE / 2.0))
+                    .size(px(HANDLE_SIZE))
+                    .rounded_full()
+                    .bg(rgb(0xebcb8b))
+            }))
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(move |this, event: &MouseDownEvent, _window, cx| {
+                    let x: f32 = (event.position.x - this.canvas_bounds.left()).into();
+                    let y: f32 = (event.position.y - this.canvas_bounds.top()).into();
+
+                    // grab the closest handle if the press is close enough to it
+                    this.dragging_handle = handles
+                        .iter()
+                        .enumerate()
+                        .map(|(idx, (hx, hy))| (idx, (hx - x).hypot(hy - y)))
+                        .filter(|(_, distance)| *distance <= HANDLE_SIZE)
+                        .min_by(|a, b| a.1.total_cmp(&b.1))
+                        .map(|(idx, _)| idx);
+                    cx.notify();
+                }),
+            )
+            .on_mouse_move(cx.listener(|this, event: &MouseMoveEvent, _window, cx| {
+                let Some(handle) = this.dragging_handle else {
+                    return;
+                };
+                let (x, y) = this.curve_position(event.position);
+                if let Some(bezier) = this.beziers.get_mut(this.selected_bezier) {
+                    if handle == 0 {
+                        (bezier.x0, bezier.y0) = (x, y);
+                    } else {
+                        (bezier.x1, bezier.y1) = (x, y);
+                    }
+                    cx.notify();
+                }
+            }))
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|this, _event: &MouseUpEvent, _window, cx| {
+                    if this.dragging_handle.take().is_none() {
+                        return;
+                    }
+                    if let Some(bezier) = this.beziers.get(this.selected_bezier) {
+                        preview("bezier", &bezier.line_value());
+                        if !this.changed_beziers.contains(&bezier.name) {
+                            this.changed_beziers.push(bezier.name.clone());
+                        }
+                    }
+                    cx.notify();
+                }),
+            )
+    }
+
+    /// A box moving across a track with the easing of the curve
+    fn render_easing_preview(&self, bezier: &Bezier, cx: &mut Context<Self>) -> impl IntoElement {
+        let curve = bezier.clone();
+        let travel = CURVE_CANVAS_SIZE - PREVIEW_BOX_SIZE;
+
+        div()
+            .relative()
+            .w(px(CURVE_CANVAS_SIZE))
+            .h(px(PREVIEW_BOX_SIZE))
+            .rounded_md()
+            .bg(cx.theme().muted)
+            .child(
+                div()
+                    .absolute()
+                    .top_0()
+                    .size(px(PREVIEW_BOX_SIZE))
+                    .rounded_md()
+                    .bg(rgb(0x88c0d0))
+                    .with_animation(
+                        "bezier-easing-preview",
+                        Animation::new(Duration::from_secs(1))
+                            .repeat()
+                            .with_easing(move |time| curve.ease(time)),
+                        move |this, progress| this.left(px(progress * travel)),
+                    ),
+            )
+    }
+
+    fn render_curve_editor(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let bezier = self.beziers.get(self.selected_bezier).cloned();
+
+        section_container(cx)
+            .child(with_tooltip(
+                "Drag the yellow points to shape the curve. Changes to a curve apply to every animation using it.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Curves".to_string()),
+                cx,
+            ))
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(120.0)).child("Curve:"))
+                    .child(Dropdown::new(&self.edit_curve_dropdown).min_w(px(160.0))),
+            )
+            .when_some(bezier, |this, bezier| {
+                this.child(self.render_curve_canvas(&bezier, cx))
+                    .child(self.render_easing_preview(&bezier, cx))
+                    .child(
+                        div()
+                            .text_size(px(12.0))
+                            .text_color(cx.theme().muted_foreground)
+                            .child(format!("bezier = {}", bezier.line_value())),
+                    )
+            })
+            .child(
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(div().w(px(200.0)).child(TextInput::new(&self.new_curve_input)))
+                    .child(
+                        Button::new("add-bezier-curve")
+                            .label("New curve")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.add_curve(window, cx);
+                            })),
+                    ),
+            )
+    }
+}
+
+impl Render for AnimationSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .v_flex()
+            .gap_4()
+            .child(
+                section_container(cx)
+                    .child(with_tooltip(
+                        "Animations inherit from their parent until they are changed. Changes are previewed live.",
+                        div()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(cx.theme().foreground)
+                            .child("Animation tree".to_string()),
+                        cx,
+                    ))
+                    .when(self.nodes.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_size(px(14.0))
+                                .text_color(cx.theme().muted_foreground)
+                                .child("Could not read animations from Hyprland"),
+                        )
+                    })
+                    .child(
+                        div()
+                            .h_flex()
+                            .gap_6()
+                            .items_start()
+                            .child(self.render_tree(cx))
+                            .child(div().flex_1().child(self.render_node_editor(cx))),
+                    ),
+            )
+            .child(self.render_curve_editor(cx))
+            .child(
+                div().h_flex().gap_4().items_center().child(
+                    with_tooltip(
+                        "Writes the curves and animations changed on this page to the overrides file.",
+                        Button::new("save-animation-settings")
+                            .label("Save animations")
+                            .on_click(cx.listener(|this, _, _, _cx| {
+                                this.save();
+                            })),
+                        cx,
+                    ),
+                ),
+            )
+    }
+}
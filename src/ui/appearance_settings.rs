@@ -0,0 +1,386 @@
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::slider::{Slider, SliderEvent, SliderState};
+use gpui_component::switch::Switch;
+
+use crate::option_registry::{find_option, write_option};
+use crate::ui::colour_picker::{ColourPicker, ColourPickerEvent};
+use crate::ui::highlight::highlight_row;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::appearance::{
+    ACTIVE_BORDER_OPTION, ACTIVE_OPACITY_OPTION, AppearanceOptions, BORDER_SIZE_OPTION,
+    DIM_INACTIVE_OPTION, GAPS_IN_OPTION, GAPS_OUT_OPTION, Gaps, INACTIVE_BORDER_OPTION,
+    INACTIVE_OPACITY_OPTION, ROUNDING_OPTION, get_appearance_options,
+};
+use crate::util::colour::ColourValue;
+use crate::util::option::set_keyword;
+
+pub struct AppearanceSettings {
+    options: AppearanceOptions,
+    gaps_in_slider: Entity<SliderState>,
+    gaps_out_slider: Entity<SliderState>,
+    border_size_slider: Entity<SliderState>,
+    rounding_slider: Entity<SliderState>,
+    active_opacity_slider: Entity<SliderState>,
+    inactive_opacity_slider: Entity<SliderState>,
+    active_border_picker: Entity<ColourPicker>,
+    inactive_border_picker: Entity<ColourPicker>,
+    /// Last value of every option changed since the page was applied, untouched options are not
+    /// written so their exact value is kept
+    changed: Vec<(&'static str, String)>,
+    /// Option the command palette jumped to
+    highlighted: Option<&'static str>,
+}
+
+/// Apply an option to the running compositor so changes can be seen before saving
+fn preview(option: &str, value: &str) {
+    if let Err(e) = set_keyword(option, value) {
+        eprintln!("{}", e);
+    }
+}
+
+/// Size of uniform gaps, or every side from the top clockwise
+fn gaps_label(gaps: Gaps) -> String {
+    if gaps.is_uniform() {
+        format!("{}px", gaps.top)
+    } else {
+        format!(
+            "{}px {}px {}px {}px",
+            gaps.top, gaps.right, gaps.bottom, gaps.left
+        )
+    }
+}
+
+impl AppearanceSettings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let options = get_appearance_options();
+
+        // gaps take one value per side, so the registry has no range for them
+        let gaps_in_slider = Self::slider(0.0, 50.0, 1.0, options.gaps_in.top as f32, window, cx);
+        let gaps_out_slider =
+            Self::slider(0.0, 100.0, 1.0, options.gaps_out.top as f32, window, cx);
+        let border_size_slider =
+            Self::option_slider(BORDER_SIZE_OPTION, options.border_size as f32, window, cx);
+        let rounding_slider =
+            Self::option_slider(ROUNDING_OPTION, options.rounding as f32, window, cx);
+        let active_opacity_slider =
+            Self::option_slider(ACTIVE_OPACITY_OPTION, options.active_opacity, window, cx);
+        let inactive_opacity_slider = Self::option_slider(
+            INACTIVE_OPACITY_OPTION,
+            options.inactive_opacity,
+            window,
+            cx,
+        );
+
+        Self::bind_slider(&gaps_in_slider, GAPS_IN_OPTION, cx, |o, v| {
+            o.gaps_in = Gaps::uniform(v.round() as i64);
+            o.gaps_in.to_string()
+        });
+        Self::bind_slider(&gaps_out_slider, GAPS_OUT_OPTION, cx, |o, v| {
+            o.gaps_out = Gaps::uniform(v.round() as i64);
+            o.gaps_out.to_string()
+        });
+        Self::bind_slider(&border_size_slider, BORDER_SIZE_OPTION, cx, |o, v| {
+            o.border_size = v.round() as i64;
+            o.border_size.to_string()
+        });
+        Self::bind_slider(&rounding_slider, ROUNDING_OPTION, cx, |o, v| {
+            o.rounding = v.round() as i64;
+            o.rounding.to_string()
+        });
+        Self::bind_slider(&active_opacity_slider, ACTIVE_OPACITY_OPTION, cx, |o, v| {
+            o.active_opacity = v;
+            format!("{:.2}", v)
+        });
+        Self::bind_slider(
+            &inactive_opacity_slider,
+            INACTIVE_OPACITY_OPTION,
+            cx,
+            |o, v| {
+                o.inactive_opacity = v;
+                format!("{:.2}", v)
+            },
+        );
+
+        let active_border_picker =
+            cx.new(|cx| ColourPicker::new(options.active_border.clone(), true, window, cx));
+        let inactive_border_picker =
+            cx.new(|cx| ColourPicker::new(options.inactive_border.clone(), true, window, cx));
+
+        Self::bind_colour_picker(&active_border_picker, ACTIVE_BORDER_OPTION, cx, |o| {
+            &mut o.active_border
+        });
+        Self::bind_colour_picker(&inactive_border_picker, INACTIVE_BORDER_OPTION, cx, |o| {
+            &mut o.inactive_border
+        });
+
+        Self {
+            options,
+            gaps_in_slider,
+            gaps_out_slider,
+            border_size_slider,
+            rounding_slider,
+            active_opacity_slider,
+            inactive_opacity_slider,
+            active_border_picker,
+            inactive_border_picker,
+            changed: Vec::new(),
+            highlighted: None,
+        }
+    }
+
+    /// Outline the row of an option, `None` clears it
+    pub fn highlight(&mut self, path: Option<&'static str>, cx: &mut Context<Self>) {
+        self.highlighted = path;
+        cx.notify();
+    }
+
+    fn option_row(&self, option: &str, row: Div, cx: &mut Context<Self>) -> Div {
+        highlight_row(row, self.highlighted == Some(option), cx)
+    }
+
+    /// Preview a changed option and remember it for the next apply
+    fn change(&mut self, option: &'static str, value: String) {
+        preview(option, &value);
+        self.changed.retain(|(name, _)| *name != option);
+        self.changed.push((option, value));
+    }
+
+    fn slider(
+        min: f32,
+        max: f32,
+        step: f32,
+        value: f32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<SliderState> {
+        let slider = cx.new(|_cx| SliderState::new().min(min).max(max).step(step));
+        slider.update(cx, |state, cx| {
+            state.set_value(value, window, cx);
+        });
+        slider
+    }
+
+    /// Slider over the registered range of an option
+    fn option_slider(
+        option: &str,
+        value: f32,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<SliderState> {
+        let (min, max, step) = find_option(option)
+            .and_then(|option| option.range())
+            .unwrap_or((0.0, 1.0, 0.05));
+        Self::slider(min, max, step, value, window, cx)
+    }
+
+    /// Keep the option in sync with the slider and preview every change live
+    fn bind_slider(
+        slider: &Entity<SliderState>,
+        option: &'static str,
+        cx: &mut Context<Self>,
+        set: fn(&mut AppearanceOptions, f32) -> String,
+    ) {
+        cx.subscribe(
+            slider,
+            move |this, _, event: &SliderEvent, cx| match event {
+                SliderEvent::Change(value) => {
+                    let value = set(&mut this.options, value.start());
+                    this.change(option, value);
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+    }
+
+    fn bind_colour_picker(
+        picker: &Entity<ColourPicker>,
+        option: &'static str,
+        cx: &mut Context<Self>,
+        field: fn(&mut AppearanceOptions) -> &mut ColourValue,
+    ) {
+        cx.subscribe(picker, move |this, _, event: &ColourPickerEvent, cx| {
+            let ColourPickerEvent::Change(value) = event;
+            this.change(option, value.to_string());
+            *field(&mut this.options) = value.clone();
+            cx.notify();
+        })
+        .detach();
+    }
+
+    fn slider_row(
+        &self,
+        option: &'static str,
+        label: &'static str,
+        description: &'static str,
+        value: String,
+        slider: &Entity<SliderState>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        self.option_row(option, div().v_flex().gap_1(), cx)
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(with_tooltip(
+                        description,
+                        div().min_w(px(200.0)).child(label),
+                        cx,
+                    ))
+                    .child(
+                        div()
+                            .text_size(px(14.0))
+                            .text_color(cx.theme().muted_foreground)
+                            .child(value),
+                    ),
+            )
+            .child(
+                Slider::new(slider)
+                    .w_full()
+                    .text_color(cx.theme().foreground),
+            )
+    }
+
+    fn colour_row(
+        &self,
+        option: &'static str,
+        label: &'static str,
+        description: &'static str,
+        picker: &Entity<ColourPicker>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        self.option_row(option, div().h_flex().gap_4().items_center(), cx)
+            .child(with_tooltip(
+                description,
+                div().min_w(px(200.0)).child(label),
+                cx,
+            ))
+            .child(div().flex_1().child(picker.clone()))
+    }
+
+    /// Write the changed options, the ones that fail to write are kept to retry
+    fn apply_appearance_setting(&mut self) {
+        self.changed
+            .retain(|(option, value)| match write_option(option, value) {
+                Ok(()) => false,
+                Err(e) => {
+                    eprintln!("Failed to write override: {}", e);
+                    true
+                }
+            });
+    }
+}
+
+impl Render for AppearanceSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let options = self.options.clone();
+
+        section_container(cx)
+            .min_h(px(200.0))
+            .child(with_tooltip(
+                "Changes are previewed live on your windows. Use the apply button to keep the changed values.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Windows".to_string()),
+                cx,
+            ))
+            .child(self.slider_row(
+                GAPS_IN_OPTION,
+                "Inner gaps:",
+                "Space between neighbouring windows in pixels. Sizes per side are kept until the slider is moved, which sets every side.",
+                gaps_label(options.gaps_in),
+                &self.gaps_in_slider,
+                cx,
+            ))
+            .child(self.slider_row(
+                GAPS_OUT_OPTION,
+                "Outer gaps:",
+                "Space between windows and the monitor edges in pixels. Sizes per side are kept until the slider is moved, which sets every side.",
+                gaps_label(options.gaps_out),
+                &self.gaps_out_slider,
+                cx,
+            ))
+            .child(self.slider_row(
+                BORDER_SIZE_OPTION,
+                "Border size:",
+                "Width of the window border in pixels.",
+                format!("{}px", options.border_size),
+                &self.border_size_slider,
+                cx,
+            ))
+            .child(self.colour_row(
+                ACTIVE_BORDER_OPTION,
+                "Active border colour:",
+                "Colour of the focused window's border. Add more colours to make it a gradient.",
+                &self.active_border_picker,
+                cx,
+            ))
+            .child(self.colour_row(
+                INACTIVE_BORDER_OPTION,
+                "Inactive border colour:",
+                "Colour of unfocused window borders. Add more colours to make it a gradient.",
+                &self.inactive_border_picker,
+                cx,
+            ))
+            .child(self.slider_row(
+                ROUNDING_OPTION,
+                "Rounding:",
+                "Radius of rounded window corners in pixels.",
+                format!("{}px", options.rounding),
+                &self.rounding_slider,
+                cx,
+            ))
+            .child(self.slider_row(
+                ACTIVE_OPACITY_OPTION,
+                "Active opacity:",
+                "Opacity of the focused window.",
+                format!("{:.2}", options.active_opacity),
+                &self.active_opacity_slider,
+                cx,
+            ))
+            .child(self.slider_row(
+                INACTIVE_OPACITY_OPTION,
+                "Inactive opacity:",
+                "Opacity of unfocused windows.",
+                format!("{:.2}", options.inactive_opacity),
+                &self.inactive_opacity_slider,
+                cx,
+            ))
+            .child(
+                self.option_row(DIM_INACTIVE_OPTION, div().h_flex().gap_4().items_center(), cx)
+                    .child(with_tooltip(
+                        "Darken unfocused windows.",
+                        div().min_w(px(200.0)).child("Dim inactive"),
+                        cx,
+                    ))
+                    .child(
+                        Switch::new("dim-inactive-switch")
+                            .checked(options.dim_inactive)
+                            .on_click(cx.listener(|view, checked, _, cx| {
+                                view.options.dim_inactive = *checked;
+                                view.change(DIM_INACTIVE_OPTION, checked.to_string());
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(200.0)))
+                    .child(
+                        Button::new("apply-appearance-settings")
+                            .label("Apply appearance setting")
+                            .on_click(cx.listener(|this, _, _, _cx| {
+                                this.apply_appearance_setting();
+                            })),
+                    ),
+            )
+    }
+}
@@ -0,0 +1,276 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::dropdown::*;
+use gpui_component::input::{InputState, TextInput};
+use gpui_component::switch::Switch;
+use gpui_component::{Disableable, IndexPath};
+
+use crate::ui::item_pill::item_pill;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::autostart::{AUTOSTART_KEYWORDS, AutostartEntry, get_autostart, write_autostart};
+use crate::util::config_tree::display_path;
+use crate::util::dispatchers::dispatch;
+
+pub struct AutostartSettings {
+    entries: Vec<AutostartEntry>,
+    keyword_dropdown: Entity<DropdownState<Vec<String>>>,
+    keyword: String,
+    command_input: Entity<InputState>,
+    error: Option<String>,
+}
+
+fn load_entries() -> Vec<AutostartEntry> {
+    get_autostart().unwrap_or_else(|e| {
+        eprintln!("Failed to get autostart entries: {}", e);
+        Vec::new()
+    })
+}
+
+impl AutostartSettings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let keyword_dropdown = cx.new(|cx| {
+            DropdownState::new(
+                AUTOSTART_KEYWORDS.iter().map(|k| k.to_string()).collect(),
+                Some(IndexPath::default()),
+                window,
+                cx,
+            )
+        });
+        let command_input =
+            cx.new(|cx| InputState::new(window, cx).placeholder("Command, e.g. waybar"));
+
+        cx.subscribe(
+            &keyword_dropdown,
+            |this, _, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(keyword)) = event {
+                    this.keyword = keyword.clone();
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        Self {
+            entries: load_entries(),
+            keyword_dropdown,
+            keyword: AUTOSTART_KEYWORDS[0].to_string(),
+            command_input,
+            error: None,
+        }
+    }
+
+    /// Write the overridden entries and read the tree again
+    fn save(&mut self, cx: &mut Context<Self>) {
+        if let Err(e) = write_autostart(&self.entries) {
+            eprintln!("Failed to write override: {}", e);
+        }
+        self.entries = load_entries();
+        cx.notify();
+    }
+
+    fn add_entry(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let command = self.command_input.read(cx).value().trim().to_string();
+        if command.is_empty() {
+            self.error = Some("Enter a command".to_string());
+            cx.notify();
+            return;
+        }
+
+        self.entries
+            .push(AutostartEntry::new(&self.keyword, &command));
+        self.command_input.update(cx, |state, cx| {
+            state.set_value("", window, cx);
+        });
+        self.error = None;
+        self.save(cx);
+    }
+
+    fn set_enabled(&mut self, idx: usize, enabled: bool, cx: &mut Context<Self>) {
+        if let Some(entry) = self.entries.get_mut(idx) {
+            entry.enabled = enabled;
+        }
+        self.save(cx);
+    }
+
+    /// Swap an entry with the closest overridden entry above or below it
+    fn move_entry(&mut self, idx: usize, up: bool, cx: &mut Context<Self>) {
+        let other = if up {
+            self.entries[..idx].iter().rposition(|e| e.overridden)
+        } else {
+            self.entries
+                .iter()
+                .skip(idx + 1)
+                .position(|e| e.overridden)
+                .map(|pos| idx + 1 + pos)
+        };
+
+        if let Some(other) = other {
+            self.entries.swap(idx, other);
+            self.save(cx);
+        }
+    }
+
+    fn remove_entry(&mut self, idx: usize, cx: &mut Context<Self>) {
+        if idx < self.entries.len() {
+            self.entries.remove(idx);
+        }
+        self.save(cx);
+    }
+
+    /// Run a command once through Hyprland so it gets the same environment as at startup
+    fn run_now(&mut self, idx: usize, cx: &mut Context<Self>) {
+        let Some(entry) = self.entries.get(idx) else {
+            return;
+        };
+        self.error = dispatch("exec", &entry.command)
+            .err()
+            .map(|e| e.to_string());
+        cx.notify();
+    }
+
+    fn render_entry_row(&self, idx: usize, entry: &AutostartEntry, cx: &mut Context<Self>) -> Div {
+        let first = !self.entries[..idx].iter().any(|e| e.overridden);
+        let last = !self.entries[idx + 1..].iter().any(|e| e.overridden);
+
+        div()
+            .h_flex()
+            .gap_2()
+            .items_center()
+            .py_1()
+            .child(
+                div()
+                    .flex_1()
+                    .text_color(if entry.enabled {
+                        cx.theme().foreground
+                    } else {
+                        cx.theme().muted_foreground
+                    })
+                    .child(entry.command.clone()),
+            )
+            .child(
+                item_pill(cx)
+                    .text_size(px(10.0))
+                    .child(entry.keyword.clone()),
+            )
+            .when(!entry.overridden, |this| {
+                this.child(
+                    item_pill(cx)
+                        .text_size(px(10.0))
+                        .child(display_path(&entry.path)),
+                )
+            })
+            .when(entry.overridden, |this| {
+                this.child(with_tooltip(
+                    "Disabled entries are kept in the overrides file behind a `#disabled` comment.",
+                    Switch::new(("autostart-enabled", idx))
+                        .checked(entry.enabled)
+                        .on_click(cx.listener(move |this, checked: &bool, _, cx| {
+                            this.set_enabled(idx, *checked, cx);
+                        })),
+                    cx,
+                ))
+                .child(
+                    Button::new(("autostart-up", idx))
+                        .label("Up")
+                        .disabled(first)
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.move_entry(idx, true, cx);
+                        })),
+                )
+                .child(
+                    Button::new(("autostart-down", idx))
+                        .label("Down")
+                        .disabled(last)
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.move_entry(idx, false, cx);
+                        })),
+                )
+            })
+            .child(
+                Button::new(("autostart-run", idx))
+                    .label("Run now")
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.run_now(idx, cx);
+                    })),
+            )
+            .when(entry.overridden, |this| {
+                this.child(
+                    Button::new(("autostart-remove", idx))
+                        .label("Remove")
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.remove_entry(idx, cx);
+                        })),
+                )
+            })
+    }
+}
+
+impl Render for AutostartSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .v_flex()
+            .gap_4()
+            .child(
+                section_container(cx)
+                    .child(with_tooltip(
+                        "exec-once runs when Hyprland starts, exec on every config reload and exec-shutdown when Hyprland exits.",
+                        div()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(cx.theme().foreground)
+                            .child("Add entry"),
+                        cx,
+                    ))
+                    .child(
+                        div()
+                            .h_flex()
+                            .gap_2()
+                            .items_center()
+                            .child(Dropdown::new(&self.keyword_dropdown).w(px(160.0)))
+                            .child(div().flex_1().child(TextInput::new(&self.command_input)))
+                            .child(
+                                Button::new("add-autostart")
+                                    .label("Add")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.add_entry(window, cx);
+                                    })),
+                            ),
+                    )
+                    .when_some(self.error.clone(), |this, error| {
+                        this.child(
+                            div()
+                                .text_size(px(12.0))
+                                .text_color(cx.theme().danger)
+                                .child(error),
+                        )
+                    }),
+            )
+            .child(
+                section_container(cx)
+                    .child(with_tooltip(
+                        "Entries from every file sourced by hyprland.conf, in the order Hyprland runs them. Only entries in the overrides file can be changed.",
+                        div()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(cx.theme().foreground)
+                            .child("Autostart"),
+                        cx,
+                    ))
+                    .when(self.entries.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_size(px(14.0))
+                                .text_color(cx.theme().muted_foreground)
+                                .child("No autostart entries found"),
+                        )
+                    })
+                    .children(
+                        self.entries
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, entry)| self.render_entry_row(idx, entry, cx)),
+                    ),
+            )
+    }
+}
@@ -0,0 +1,246 @@
+use std::fs;
+use std::path::PathBuf;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+
+use crate::change_set;
+use crate::setting_writer::config_path;
+use crate::ui::item_pill::item_pill;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::backup::{
+    BACKED_UP_FILES, Backup, MAX_BACKUPS, create_backup, get_all_backups, restore_backup,
+};
+use crate::util::config_tree::display_path;
+use crate::util::diff::{DiffLine, unified_diff};
+
+/// Unchanged lines shown around each change of a backup diff
+const DIFF_CONTEXT: usize = 3;
+
+pub struct BackupSettings {
+    backups: Vec<Backup>,
+    /// Backup whose diff against the current file is shown, with that diff
+    expanded: Option<(PathBuf, Vec<DiffLine>)>,
+    /// Change set revision the list was read at, saving on any page can add a backup
+    revision: u64,
+    status: Option<String>,
+    error: Option<String>,
+}
+
+fn load_backups() -> Vec<Backup> {
+    get_all_backups().unwrap_or_else(|e| {
+        eprintln!("Failed to get backups: {}", e);
+        Vec::new()
+    })
+}
+
+/// Diff of the current file against a backup of it
+fn backup_diff(backup: &Backup) -> Vec<DiffLine> {
+    let current = fs::read_to_string(&backup.source).unwrap_or_default();
+    fs::read_to_string(&backup.path)
+        .map(|content| unified_diff(&current, &content, DIFF_CONTEXT))
+        .unwrap_or_else(|e| {
+            eprintln!("Failed to read backup {}: {}", backup.path.display(), e);
+            Vec::new()
+        })
+}
+
+impl BackupSettings {
+    pub fn new(_window: &mut Window, _cx: &mut Context<Self>) -> Self {
+        Self {
+            backups: load_backups(),
+            expanded: None,
+            revision: change_set::revision(),
+            status: None,
+            error: None,
+        }
+    }
+
+    /// Read the backups again, the shown diff is closed as the files may have changed
+    fn refresh(&mut self) {
+        self.backups = load_backups();
+        self.expanded = None;
+        self.revision = change_set::revision();
+    }
+
+    fn report(&mut self, result: anyhow::Result<()>, done: String, cx: &mut Context<Self>) {
+        self.refresh();
+        match result {
+            Ok(()) => {
+                self.status = Some(done);
+                self.error = None;
+            }
+            Err(e) => {
+                eprintln!("{}", e);
+                self.error = Some(e.to_string());
+            }
+        }
+        cx.notify();
+    }
+
+    fn backup_now(&mut self, cx: &mut Context<Self>) {
+        let result = BACKED_UP_FILES
+            .iter()
+            .try_for_each(|file| create_backup(&config_path(file)?));
+        self.report(
+            result,
+            "Backed up hyprland.conf and the overrides file".to_string(),
+            cx,
+        );
+    }
+
+    fn restore(&mut self, backup: &Backup, cx: &mut Context<Self>) {
+        let done = format!(
+            "Restored {} from {}",
+            display_path(&backup.source),
+            backup.created()
+        );
+        self.report(restore_backup(backup), done, cx);
+    }
+
+    fn toggle_diff(&mut self, backup: &Backup, cx: &mut Context<Self>) {
+        self.expanded = match &self.expanded {
+            Some((path, _)) if *path == backup.path => None,
+            _ => Some((backup.path.clone(), backup_diff(backup))),
+        };
+        cx.notify();
+    }
+
+    fn render_diff(&self, diff: &[DiffLine], cx: &mut Context<Self>) -> Div {
+        div()
+            .v_flex()
+            .pl_4()
+            .font_family("monospace")
+            .text_size(px(12.0))
+            .when(diff.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Same as the current file"),
+                )
+            })
+            .children(diff.iter().map(|line| {
+                let (text, color) = match line {
+                    DiffLine::Hunk(text) => (text.clone(), cx.theme().muted_foreground),
+                    DiffLine::Context(text) => (format!("  {}", text), cx.theme().foreground),
+                    DiffLine::Added(text) => (format!("+ {}", text), cx.theme().success),
+                    DiffLine::Removed(text) => (format!("- {}", text), cx.theme().danger),
+                };
+                div().text_color(color).child(text)
+            }))
+    }
+
+    fn render_backup(&self, idx: usize, backup: &Backup, cx: &mut Context<Self>) -> Div {
+        let diff = self
+            .expanded
+            .as_ref()
+            .filter(|(path, _)| *path == backup.path)
+            .map(|(_, diff)| diff);
+        let expanded = diff.is_some();
+        let diff_backup = backup.clone();
+
+        div()
+            .v_flex()
+            .gap_1()
+            .py_1()
+            .child(
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_color(cx.theme().foreground)
+                            .child(backup.created()),
+                    )
+                    .child(
+                        item_pill(cx)
+                            .text_size(px(10.0))
+                            .child(display_path(&backup.source)),
+                    )
+                    .child(
+                        Button::new(("backup-diff", idx))
+                            .label(if expanded { "Hide diff" } else { "Diff" })
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.toggle_diff(&diff_backup, cx);
+                            })),
+                    )
+                    .child(
+                        Button::new(("backup-restore", idx))
+                            .label("Restore")
+                            .on_click(cx.listener({
+                                let backup = backup.clone();
+                                move |this, _, _, cx| {
+                                    this.restore(&backup, cx);
+                                }
+                            })),
+                    ),
+            )
+            .when_some(diff, |this, diff| this.child(self.render_diff(diff, cx)))
+    }
+}
+
+impl Render for BackupSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        if self.revision != change_set::revision() {
+            self.refresh();
+        }
+        let rows: Vec<Div> = self
+            .backups
+            .iter()
+            .enumerate()
+            .map(|(idx, backup)| self.render_backup(idx, backup, cx))
+            .collect();
+
+        section_container(cx)
+            .child(with_tooltip(
+                format!(
+                    "hyprland.conf and the overrides file are copied before their first change each time the app runs. The last {} copies of each are kept. Restoring backs up the current file first.",
+                    MAX_BACKUPS
+                ),
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Backups"),
+                cx,
+            ))
+            .child(
+                div().h_flex().child(
+                    Button::new("backup-now")
+                        .label("Back up now")
+                        .on_click(cx.listener(|this, _, _, cx| {
+                            this.backup_now(cx);
+                        })),
+                ),
+            )
+            .when(rows.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_size(px(14.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child("No backups yet"),
+                )
+            })
+            .children(rows)
+            .when_some(self.status.clone(), |this, status| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child(status),
+                )
+            })
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(cx.theme().danger)
+                        .child(error),
+                )
+            })
+    }
+}
@@ -0,0 +1,362 @@
+use std::time::Duration;
+
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::slider::{Slider, SliderEvent, SliderState};
+use gpui_component::switch::Switch;
+
+use crate::option_registry::{
+    OptionSpec, find_option, read_bool, read_number, read_text, write_option,
+};
+use crate::ui::colour_picker::{ColourPicker, ColourPickerEvent};
+use crate::ui::highlight::highlight_row;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::colour::{Colour, ColourValue};
+use crate::util::option::set_keyword;
+
+/// Wait this long after the last change before applying it, dragging a slider fires many events
+const PREVIEW_DEBOUNCE: Duration = Duration::from_millis(150);
+
+/// A slider of the page, its range, default and description come from the option registry
+struct SliderOption {
+    option: &'static str,
+    label: &'static str,
+}
+
+/// A switch of the page, its default and description come from the option registry
+struct SwitchOption {
+    option: &'static str,
+    label: &'static str,
+}
+
+static BLUR_SWITCHES: [SwitchOption; 3] = [
+    SwitchOption {
+        option: "decoration:blur:enabled",
+        label: "Blur",
+    },
+    SwitchOption {
+        option: "decoration:blur:xray",
+        label: "X-ray",
+    },
+    SwitchOption {
+        option: "decoration:blur:new_optimizations",
+        label: "New optimizations",
+    },
+];
+
+static BLUR_SLIDERS: [SliderOption; 6] = [
+    SliderOption {
+        option: "decoration:blur:size",
+        label: "Size",
+    },
+    SliderOption {
+        option: "decoration:blur:passes",
+        label: "Passes",
+    },
+    SliderOption {
+        option: "decoration:blur:noise",
+        label: "Noise",
+    },
+    SliderOption {
+        option: "decoration:blur:contrast",
+        label: "Contrast",
+    },
+    SliderOption {
+        option: "decoration:blur:brightness",
+        label: "Brightness",
+    },
+    SliderOption {
+        option: "decoration:blur:vibrancy",
+        label: "Vibrancy",
+    },
+];
+
+static SHADOW_SWITCHES: [SwitchOption; 3] = [
+    SwitchOption {
+        option: "decoration:shadow:enabled",
+        label: "Shadow",
+    },
+    SwitchOption {
+        option: "decoration:shadow:sharp",
+        label: "Sharp",
+    },
+    SwitchOption {
+        option: "decoration:shadow:ignore_window",
+        label: "Ignore window",
+    },
+];
+
+static SHADOW_SLIDERS: [SliderOption; 3] = [
+    SliderOption {
+        option: "decoration:shadow:range",
+        label: "Range",
+    },
+    SliderOption {
+        option: "decoration:shadow:render_power",
+        label: "Render power",
+    },
+    SliderOption {
+        option: "decoration:shadow:scale",
+        label: "Scale",
+    },
+];
+
+const SHADOW_COLOR_OPTION: &str = "decoration:shadow:color";
+
+/// Registered `(min, max, step)` of a slider
+fn slider_range(option: &SliderOption) -> (f32, f32, f32) {
+    find_option(option.option)
+        .and_then(OptionSpec::range)
+        .unwrap_or((0.0, 1.0, 0.05))
+}
+
+fn description(option: &str) -> &'static str {
+    find_option(option)
+        .map(|option| option.description)
+        .unwrap_or_default()
+}
+
+fn format_value(option: &SliderOption, value: f32) -> String {
+    let (_, _, step) = slider_range(option);
+    if step >= 1.0 {
+        format!("{}", value.round() as i64)
+    } else {
+        format!("{:.2}", value)
+    }
+}
+
+pub struct BlurShadowSettings {
+    sliders: Vec<(&'static SliderOption, Entity<SliderState>, f32)>,
+    switches: Vec<(&'static SwitchOption, bool)>,
+    shadow_color_picker: Entity<ColourPicker>,
+    pending: Vec<(&'static str, String)>,
+    /// Last value of every option changed since the page was saved, untouched options are not
+    /// written so their exact value is kept
+    changed: Vec<(&'static str, String)>,
+    preview_task: Option<Task<()>>,
+    /// Option the command palette jumped to
+    highlighted: Option<&'static str>,
+}
+
+impl BlurShadowSettings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let mut sliders = Vec::new();
+        for (idx, option) in BLUR_SLIDERS.iter().chain(SHADOW_SLIDERS.iter()).enumerate() {
+            let value = read_number(option.option);
+            let (min, max, step) = slider_range(option);
+            let slider = cx.new(|_cx| SliderState::new().min(min).max(max).step(step));
+            slider.update(cx, |state, cx| {
+                state.set_value(value, window, cx);
+            });
+
+            cx.subscribe(
+                &slider,
+                move |this, _, event: &SliderEvent, cx| match event {
+                    SliderEvent::Change(value) => {
+                        let (option, _, current) = &mut this.sliders[idx];
+                        let option: &'static SliderOption = option;
+                        *current = value.start();
+                        this.queue_preview(option.option, format_value(option, value.start()), cx);
+                    }
+                },
+            )
+            .detach();
+
+            sliders.push((option, slider, value));
+        }
+
+        let switches = BLUR_SWITCHES
+            .iter()
+            .chain(SHADOW_SWITCHES.iter())
+            .map(|option| (option, read_bool(option.option)))
+            .collect();
+
+        // colours are reported as their integer value, the registered default is written in hex
+        let shadow_color = read_text(SHADOW_COLOR_OPTION);
+        let shadow_color = match shadow_color.parse::<i64>() {
+            Ok(argb) => ColourValue::single(Colour::from_argb(argb as u32)),
+            Err(_) => shadow_color
+                .parse()
+                .unwrap_or_else(|_| ColourValue::single(Colour::from_argb(0xee1a1a1a))),
+        };
+
+        let shadow_color_picker = cx.new(|cx| ColourPicker::new(shadow_color, false, window, cx));
+
+        cx.subscribe(
+            &shadow_color_picker,
+            |this, _, event: &ColourPickerEvent, cx| {
+                let ColourPickerEvent::Change(value) = event;
+                this.queue_preview(SHADOW_COLOR_OPTION, value.to_string(), cx);
+            },
+        )
+        .detach();
+
+        Self {
+            sliders,
+            switches,
+            shadow_color_picker,
+            pending: Vec::new(),
+            changed: Vec::new(),
+            preview_task: None,
+            highlighted: None,
+        }
+    }
+
+    /// Outline the row of an option, `None` clears it
+    pub fn highlight(&mut self, path: Option<&'static str>, cx: &mut Context<Self>) {
+        self.highlighted = path;
+        cx.notify();
+    }
+
+    fn option_row(&self, option: &str, row: Div, cx: &mut Context<Self>) -> Div {
+        highlight_row(row, self.highlighted == Some(option), cx)
+    }
+
+    /// Remember the change and apply all pending changes once the user stops editing
+    fn queue_preview(&mut self, option: &'static str, value: String, cx: &mut Context<Self>) {
+        self.pending.retain(|(name, _)| *name != option);
+        self.pending.push((option, value.clone()));
+        self.changed.retain(|(name, _)| *name != option);
+        self.changed.push((option, value));
+
+        // replacing the task drops the previous timer, which cancels it
+        self.preview_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(PREVIEW_DEBOUNCE).await;
+            let _ = this.update(cx, |this, _cx| {
+                for (option, value) in this.pending.drain(..) {
+                    if let Err(e) = set_keyword(option, &value) {
+                        eprintln!("{}", e);
+                    }
+                }
+            });
+        }));
+
+        cx.notify();
+    }
+
+    /// Write the changed options, the ones that fail to write are kept to retry
+    fn save(&mut self) {
+        self.changed
+            .retain(|(option, value)| match write_option(option, value) {
+                Ok(()) => false,
+                Err(e) => {
+                    eprintln!("Failed to write override: {}", e);
+                    true
+                }
+            });
+    }
+
+    fn render_group(
+        &self,
+        title: &'static str,
+        switches: &'static [SwitchOption],
+        sliders: &'static [SliderOption],
+        cx: &mut Context<Self>,
+    ) -> Div {
+        let switch_rows = self
+            .switches
+            .iter()
+            .enumerate()
+            .filter(|(_, (option, _))| switches.iter().any(|s| s.option == option.option))
+            .map(|(idx, (option, checked))| {
+                self.option_row(option.option, div().h_flex().gap_4().items_center(), cx)
+                    .child(with_tooltip(
+                        description(option.option),
+                        div().min_w(px(200.0)).child(option.label),
+                        cx,
+                    ))
+                    .child(
+                        Switch::new(SharedString::from(format!("{}-switch", option.option)))
+                            .checked(*checked)
+                            .on_click(cx.listener(move |this, checked: &bool, _, cx| {
+                                let (option, current) = &mut this.switches[idx];
+                                *current = *checked;
+                                let name = option.option;
+                                this.queue_preview(name, checked.to_string(), cx);
+                            })),
+                    )
+            })
+            .collect::<Vec<_>>();
+
+        let slider_rows = self
+            .sliders
+            .iter()
+            .filter(|(option, _, _)| sliders.iter().any(|s| s.option == option.option))
+            .map(|(option, slider, value)| {
+                self.option_row(option.option, div().v_flex().gap_1(), cx)
+                    .child(
+                        div()
+                            .h_flex()
+                            .gap_4()
+                            .items_center()
+                            .child(with_tooltip(
+                                description(option.option),
+                                div().min_w(px(200.0)).child(format!("{}:", option.label)),
+                                cx,
+                            ))
+                            .child(
+                                div()
+                                    .text_size(px(14.0))
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(format_value(option, *value)),
+                            ),
+                    )
+                    .child(
+                        Slider::new(slider)
+                            .w_full()
+                            .text_color(cx.theme().foreground),
+                    )
+            })
+            .collect::<Vec<_>>();
+
+        section_container(cx)
+            .child(
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child(title),
+            )
+            .children(switch_rows)
+            .children(slider_rows)
+    }
+}
+
+impl Render for BlurShadowSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .v_flex()
+            .gap_4()
+            .child(self.render_group("Blur", &BLUR_SWITCHES, &BLUR_SLIDERS, cx))
+            .child(
+                self.render_group("Shadow", &SHADOW_SWITCHES, &SHADOW_SLIDERS, cx)
+                    .child(
+                        self.option_row(
+                            SHADOW_COLOR_OPTION,
+                            div().h_flex().gap_4().items_center(),
+                            cx,
+                        )
+                            .child(with_tooltip(
+                                "Colour of the shadow, including its transparency.",
+                                div().min_w(px(200.0)).child("Colour:"),
+                                cx,
+                            ))
+                            .child(div().flex_1().child(self.shadow_color_picker.clone())),
+                    ),
+            )
+            .child(
+                div().h_flex().gap_4().items_center().child(
+                    with_tooltip(
+                        "Changes are previewed live. Saving writes the changed blur and shadow values to the overrides file.",
+                        Button::new("save-blur-shadow-settings")
+                            .label("Save blur & shadow")
+                            .on_click(cx.listener(|this, _, _, _cx| {
+                                this.save();
+                            })),
+                        cx,
+                    ),
+                ),
+            )
+    }
+}
@@ -0,0 +1,238 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::StyledExt;
+use gpui_component::button::{Button, ButtonVariants as _};
+use gpui_component::color_picker::{ColorPicker, ColorPickerEvent, ColorPickerState};
+use gpui_component::slider::{Slider, SliderEvent, SliderState};
+
+use crate::util::colour::{Colour, ColourValue};
+
+/// Emitted whenever the user edits a colour, a stop or the gradient angle
+pub enum ColourPickerEvent {
+    Change(ColourValue),
+}
+
+/// Colour editor for Hyprland colour options. Edits a single colour, or a gradient with an angle
+/// when `gradient` is enabled, and keeps every colour in the notation it was read in.
+pub struct ColourPicker {
+    value: ColourValue,
+    gradient: bool,
+    stops: Vec<Entity<ColorPickerState>>,
+    angle_slider: Entity<SliderState>,
+    _subscriptions: Vec<Subscription>,
+}
+
+impl EventEmitter<ColourPickerEvent> for ColourPicker {}
+
+fn to_hsla(colour: &Colour) -> Hsla {
+    Rgba {
+        r: colour.red as f32 / 255.0,
+        g: colour.green as f32 / 255.0,
+        b: colour.blue as f32 / 255.0,
+        a: colour.alpha,
+    }
+    .into()
+}
+
+fn from_hsla(colour: Colour, hsla: Hsla) -> Colour {
+    let rgba = hsla.to_rgb();
+    let channel = |c: f32| (c.clamp(0.0, 1.0) * 255.0).round() as u8;
+
+    colour.with_channels(
+        channel(rgba.r),
+        channel(rgba.g),
+        channel(rgba.b),
+        // keep the exact alpha when it was not changed, the picker works in floats
+        if channel(rgba.a) == colour.alpha_u8() {
+            colour.alpha
+        } else {
+            rgba.a
+        },
+    )
+}
+
+impl ColourPicker {
+    pub fn new(
+        value: ColourValue,
+        gradient: bool,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let angle_slider = cx.new(|_cx| SliderState::new().min(0.0).max(360.0).step(1.0));
+        angle_slider.update(cx, |state, cx| {
+            state.set_value(value.angle.unwrap_or(0.0), window, cx);
+        });
+
+        let angle_subscription =
+            cx.subscribe(
+                &angle_slider,
+                |this, _, event: &SliderEvent, cx| match event {
+                    SliderEvent::Change(angle) => {
+                        this.value.angle = Some(angle.start().round());
+                        this.emit_change(cx);
+                    }
+                },
+            );
+
+        let mut picker = Self {
+            value,
+            gradient,
+            stops: Vec::new(),
+            angle_slider,
+            _subscriptions: vec![angle_subscription],
+        };
+        picker.rebuild_stops(window, cx);
+        picker
+    }
+
+    fn emit_change(&mut self, cx: &mut Context<Self>) {
+        cx.emit(ColourPickerEvent::Change(self.value.clone()));
+        cx.notify();
+    }
+
+    /// Create one picker state per colour stop
+    fn rebuild_stops(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self._subscriptions.truncate(1);
+        self.stops = self
+            .value
+            .colours
+            .iter()
+            .enumerate()
+            .map(|(idx, colour)| {
+                let hsla = to_hsla(colour);
+                let state = cx.new(|cx| ColorPickerState::new(window, cx).default_value(hsla));
+
+                self._subscriptions.push(cx.subscribe(
+                    &state,
+                    move |this, _, event: &ColorPickerEvent, cx| {
+                        if let ColorPickerEvent::Change(Some(hsla)) = event
+                            && let Some(colour) = this.value.colours.get_mut(idx)
+                        {
+                            *colour = from_hsla(*colour, *hsla);
+                            this.emit_change(cx);
+                        }
+                    },
+                ));
+
+                state
+            })
+            .collect();
+    }
+
+    fn add_stop(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let last = self
+            .value
+            .colours
+            .last()
+            .copied()
+            .unwrap_or(Colour::new(255, 255, 255, 1.0));
+        self.value.colours.push(last);
+        if self.value.angle.is_none() {
+            self.value.angle = Some(0.0);
+        }
+        self.rebuild_stops(window, cx);
+        self.emit_change(cx);
+    }
+
+    fn remove_stop(&mut self, idx: usize, window: &mut Window, cx: &mut Context<Self>) {
+        if self.value.colours.len() <= 1 {
+            return;
+        }
+        self.value.colours.remove(idx);
+        if !self.value.is_gradient() {
+            self.value.angle = None;
+        }
+        self.rebuild_stops(window, cx);
+        self.emit_change(cx);
+    }
+
+    fn render_preview(&self) -> Div {
+        let colours = &self.value.colours;
+        let first = colours.first().map(to_hsla).unwrap_or_default();
+        let last = colours.last().map(to_hsla).unwrap_or_default();
+        // Hyprland's 0deg runs left to right, which is 90deg for a CSS style gradient
+        let angle = self.value.angle.unwrap_or(0.0) + 90.0;
+
+        div()
+            .w(px(120.0))
+            .h(px(24.0))
+            .rounded_md()
+            .border_1()
+            .map(|this| {
+                if self.value.is_gradient() {
+                    this.bg(linear_gradient(
+                        angle,
+                        linear_color_stop(first, 0.0),
+                        linear_color_stop(last, 1.0),
+                    ))
+                } else {
+                    this.bg(first)
+                }
+            })
+    }
+}
+
+impl Render for ColourPicker {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let can_remove = self.value.colours.len() > 1;
+
+        div()
+            .v_flex()
+            .gap_2()
+            .child(
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .flex_wrap()
+                    .child(self.render_preview().border_color(cx.theme().border))
+                    .children(self.stops.iter().enumerate().map(|(idx, state)| {
+                        div()
+                            .h_flex()
+                            .gap_1()
+                            .items_center()
+                            .child(ColorPicker::new(state))
+                            .when(can_remove, |this| {
+                                this.child(
+                                    Button::new(("remove-colour-stop", idx))
+                                        .label("×")
+                                        .ghost()
+                                        .on_click(cx.listener(move |this, _, window, cx| {
+                                            this.remove_stop(idx, window, cx);
+                                        })),
+                                )
+                            })
+                    }))
+                    .when(self.gradient, |this| {
+                        this.child(Button::new("add-colour-stop").label("+").ghost().on_click(
+                            cx.listener(|this, _, window, cx| {
+                                this.add_stop(window, cx);
+                            }),
+                        ))
+                    }),
+            )
+            .when(self.value.is_gradient(), |this| {
+                this.child(
+                    div()
+                        .h_flex()
+                        .gap_2()
+                        .items_center()
+                        .child(
+                            div()
+                                .text_size(px(12.0))
+                                .text_color(cx.theme().muted_foreground)
+                                .min_w(px(80.0))
+                                .child(format!("Angle: {}°", self.value.angle.unwrap_or(0.0))),
+                        )
+                        .child(Slider::new(&self.angle_slider).w(px(200.0))),
+                )
+            })
+            .child(
+                div()
+                    .text_size(px(12.0))
+                    .text_color(cx.theme().muted_foreground)
+                    .child(self.value.to_string()),
+            )
+    }
+}
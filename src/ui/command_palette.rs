@@ -0,0 +1,158 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::StyledExt;
+use gpui_component::input::{InputEvent, InputState, TextInput};
+
+use crate::ui::item_pill::item_pill;
+use crate::ui::section_container::section_container;
+use crate::util::search::{SearchTarget, search};
+
+actions!(command_palette, [ToggleCommandPalette, CloseCommandPalette]);
+
+const CONTEXT: &str = "CommandPalette";
+
+/// Results shown at once, Enter picks the first one
+const MAX_RESULTS: usize = 12;
+
+pub fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("ctrl-k", ToggleCommandPalette, None),
+        KeyBinding::new("escape", CloseCommandPalette, Some(CONTEXT)),
+    ]);
+}
+
+pub enum CommandPaletteEvent {
+    Select(SearchTarget),
+    Dismiss,
+}
+
+/// Fuzzy search over pages, options and actions
+pub struct CommandPalette {
+    search_input: Entity<InputState>,
+    results: Vec<SearchTarget>,
+}
+
+impl EventEmitter<CommandPaletteEvent> for CommandPalette {}
+
+impl CommandPalette {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let search_input = cx
+            .new(|cx| InputState::new(window, cx).placeholder("Search pages, options and actions"));
+
+        cx.subscribe(
+            &search_input,
+            |this, input, event: &InputEvent, cx| match event {
+                InputEvent::Change => {
+                    this.results = search(input.read(cx).value().as_ref(), MAX_RESULTS);
+                    cx.notify();
+                }
+                InputEvent::PressEnter { .. } => {
+                    if let Some(target) = this.results.first().copied() {
+                        cx.emit(CommandPaletteEvent::Select(target));
+                    }
+                }
+                _ => {}
+            },
+        )
+        .detach();
+
+        Self {
+            search_input,
+            results: search("", MAX_RESULTS),
+        }
+    }
+
+    /// Start a new search with the input focused
+    pub fn open(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        self.search_input.update(cx, |state, cx| {
+            state.set_value("", window, cx);
+            state.focus(window, cx);
+        });
+        self.results = search("", MAX_RESULTS);
+        cx.notify();
+    }
+
+    fn render_result(
+        &self,
+        idx: usize,
+        target: SearchTarget,
+        cx: &mut Context<Self>,
+    ) -> Stateful<Div> {
+        let hover_bg = cx.theme().accent;
+
+        div()
+            .id(("palette-result", idx))
+            .h_flex()
+            .gap_2()
+            .items_center()
+            .px_2()
+            .py_1()
+            .rounded_sm()
+            .cursor_pointer()
+            .when(idx == 0, |this| this.bg(cx.theme().secondary))
+            .hover(move |style| style.bg(hover_bg))
+            .child(
+                item_pill(cx)
+                    .text_size(px(10.0))
+                    .min_w(px(56.0))
+                    .child(target.kind()),
+            )
+            .child(
+                div()
+                    .v_flex()
+                    .flex_1()
+                    .overflow_hidden()
+                    .child(
+                        div()
+                            .text_color(cx.theme().foreground)
+                            .child(target.title()),
+                    )
+                    .child(
+                        div()
+                            .text_size(px(12.0))
+                            .text_color(cx.theme().muted_foreground)
+                            .truncate()
+                            .child(target.detail()),
+                    ),
+            )
+            .on_click(cx.listener(move |_, _, _, cx| {
+                cx.emit(CommandPaletteEvent::Select(target));
+            }))
+    }
+}
+
+impl Render for CommandPalette {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let rows: Vec<_> = self
+            .results
+            .iter()
+            .enumerate()
+            .map(|(idx, target)| self.render_result(idx, *target, cx))
+            .collect();
+
+        section_container(cx)
+            .key_context(CONTEXT)
+            .w(px(640.0))
+            .shadow_lg()
+            .on_action(cx.listener(|_, _: &CloseCommandPalette, _, cx| {
+                cx.emit(CommandPaletteEvent::Dismiss);
+            }))
+            .child(TextInput::new(&self.search_input))
+            .when(self.results.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_size(px(14.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Nothing found"),
+                )
+            })
+            .children(rows)
+            .child(
+                div()
+                    .text_size(px(12.0))
+                    .text_color(cx.theme().muted_foreground)
+                    .child("Enter opens the first result, Esc closes"),
+            )
+    }
+}
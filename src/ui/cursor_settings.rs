@@ -0,0 +1,219 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::IndexPath;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::dropdown::*;
+use std::sync::Arc;
+
+use crate::setting_writer::SettingWriter;
+use crate::ui::item_pill::item_pill;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::cursor::{
+    CursorTheme, current_cursor, cursor_preview, get_cursor_themes, set_cursor,
+};
+
+const CURSOR_SIZES: [u32; 6] = [16, 24, 32, 48, 64, 96];
+
+pub struct CursorSettings {
+    themes: Vec<(CursorTheme, Option<Arc<Image>>)>,
+    selected_theme: Option<usize>,
+    size: u32,
+    size_dropdown: Entity<DropdownState<Vec<String>>>,
+}
+
+impl CursorSettings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let themes: Vec<(CursorTheme, Option<Arc<Image>>)> = get_cursor_themes()
+            .into_iter()
+            .map(|theme| {
+                let preview = cursor_preview(&theme)
+                    .map(|bytes| Arc::new(Image::from_bytes(ImageFormat::Bmp, bytes)));
+                (theme, preview)
+            })
+            .collect();
+
+        let (current_theme, size) = current_cursor();
+        let selected_theme =
+            current_theme.and_then(|name| themes.iter().position(|(t, _)| t.name == name));
+
+        let size_labels: Vec<String> = CURSOR_SIZES.iter().map(|s| s.to_string()).collect();
+        let current_size_idx = CURSOR_SIZES.iter().position(|s| *s == size);
+
+        let size_dropdown = cx.new(|cx| {
+            DropdownState::new(
+                size_labels,
+                current_size_idx.map(IndexPath::new),
+                window,
+                cx,
+            )
+        });
+
+        cx.subscribe(
+            &size_dropdown,
+            |this, _dropdown, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(size)) = event
+                    && let Ok(size) = size.parse()
+                {
+                    this.size = size;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        Self {
+            themes,
+            selected_theme,
+            size,
+            size_dropdown,
+        }
+    }
+
+    fn apply_cursor_setting(theme: &CursorTheme, size: u32) {
+        let mut variables = vec![
+            ("XCURSOR_THEME", theme.name.clone()),
+            ("XCURSOR_SIZE", size.to_string()),
+        ];
+
+        // hyprcursor falls back to XCursor on its own, only point it at themes it can read
+        if theme.hyprcursor {
+            variables.push(("HYPRCURSOR_THEME", theme.name.clone()));
+            variables.push(("HYPRCURSOR_SIZE", size.to_string()));
+        } else {
+            // an earlier hyprcursor theme would take priority over the XCursor one at next start
+            for variable in ["HYPRCURSOR_THEME", "HYPRCURSOR_SIZE"] {
+                if let Err(e) = SettingWriter::build_single("env = ", format!("{},", variable))
+                    .and_then(|w| w.remove())
+                {
+                    eprintln!("Failed to remove override: {}", e);
+                }
+            }
+        }
+
+        for (variable, value) in variables {
+            if let Err(e) = SettingWriter::build_single("env = ", format!("{},{}", variable, value))
+                .and_then(|w| w.write())
+            {
+                eprintln!("Failed to write override: {}", e);
+            }
+        }
+
+        if let Err(e) = set_cursor(&theme.name, size) {
+            eprintln!("{}", e);
+        }
+    }
+}
+
+impl Render for CursorSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let selected = self
+            .selected_theme
+            .and_then(|idx| self.themes.get(idx))
+            .map(|(theme, _)| theme.clone());
+        let size = self.size;
+
+        section_container(cx)
+            .min_h(px(200.0))
+            .child(with_tooltip(
+                "Cursor themes found in ~/.local/share/icons, ~/.icons and the system icon directories. Themes marked hyprcursor are used natively by Hyprland, others through XCursor.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Cursor theme".to_string()),
+                cx,
+            ))
+            .when(self.themes.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_size(px(14.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child("No cursor themes found"),
+                )
+            })
+            .child(
+                div()
+                    .h_flex()
+                    .flex_wrap()
+                    .gap_2()
+                    .children(self.themes.iter().enumerate().map(|(idx, (theme, preview))| {
+                        let is_selected = self.selected_theme == Some(idx);
+
+                        div()
+                            .id(("cursor-theme", idx))
+                            .v_flex()
+                            .items_center()
+                            .gap_2()
+                            .p_3()
+                            .w(px(140.0))
+                            .border_1()
+                            .rounded_md()
+                            .cursor_pointer()
+                            .border_color(if is_selected {
+                                cx.theme().primary
+                            } else {
+                                cx.theme().border
+                            })
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.selected_theme = Some(idx);
+                                cx.notify();
+                            }))
+                            .child(
+                                div()
+                                    .size(px(32.0))
+                                    .flex()
+                                    .items_center()
+                                    .justify_center()
+                                    .when_some(preview.clone(), |this, preview| {
+                                        this.child(img(preview).size(px(32.0)))
+                                    }),
+                            )
+                            .child(
+                                div()
+                                    .text_size(px(12.0))
+                                    .text_color(cx.theme().foreground)
+                                    .child(theme.name.clone()),
+                            )
+                            .child(
+                                div()
+                                    .h_flex()
+                                    .gap_1()
+                                    .text_size(px(10.0))
+                                    .when(theme.xcursor, |this| {
+                                        this.child(item_pill(cx).child("xcursor"))
+                                    })
+                                    .when(theme.hyprcursor, |this| {
+                                        this.child(item_pill(cx).child("hyprcursor"))
+                                    }),
+                            )
+                    })),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(120.0)).child("Cursor size:"))
+                    .child(Dropdown::new(&self.size_dropdown).min_w(px(120.0))),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(120.0)))
+                    .child(
+                        Button::new("apply-cursor-settings")
+                            .label("Apply cursor setting")
+                            .on_click(move |_, _, _cx| {
+                                if let Some(theme) = &selected {
+                                    Self::apply_cursor_setting(theme, size);
+                                } else {
+                                    eprintln!("No cursor theme selected");
+                                }
+                            }),
+                    ),
+            )
+    }
+}
@@ -0,0 +1,277 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::input::{InputState, TextInput};
+
+use crate::ui::item_pill::item_pill;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::config_tree::display_path;
+use crate::util::env::{
+    EnvVar, KNOWN_ENV_VARS, find_known_env_var, get_env_vars, remove_env_var, set_env_var,
+    validate_env_name,
+};
+
+pub struct EnvSettings {
+    vars: Vec<EnvVar>,
+    name_input: Entity<InputState>,
+    value_input: Entity<InputState>,
+    error: Option<String>,
+}
+
+fn load_vars() -> Vec<EnvVar> {
+    get_env_vars().unwrap_or_else(|e| {
+        eprintln!("Failed to get environment variables: {}", e);
+        Vec::new()
+    })
+}
+
+impl EnvSettings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let name_input = cx.new(|cx| InputState::new(window, cx).placeholder("e.g. GDK_SCALE"));
+        let value_input = cx.new(|cx| InputState::new(window, cx).placeholder("Value"));
+
+        Self {
+            vars: load_vars(),
+            name_input,
+            value_input,
+            error: None,
+        }
+    }
+
+    /// How often a variable is set across the config tree
+    fn count(&self, name: &str) -> usize {
+        self.vars.iter().filter(|var| var.name == name).count()
+    }
+
+    /// Value the variable ends up with, the last line setting it wins
+    fn effective_value(&self, name: &str) -> Option<&str> {
+        self.vars
+            .iter()
+            .rev()
+            .find(|var| var.name == name)
+            .map(|var| var.value.as_str())
+    }
+
+    /// Fill the editor, e.g. with a suggested value
+    fn edit(&mut self, name: &str, value: &str, window: &mut Window, cx: &mut Context<Self>) {
+        self.name_input.update(cx, |state, cx| {
+            state.set_value(name.to_string(), window, cx);
+        });
+        self.value_input.update(cx, |state, cx| {
+            state.set_value(value.to_string(), window, cx);
+        });
+        self.error = None;
+        cx.notify();
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        let name = self.name_input.read(cx).value().trim().to_string();
+        let value = self.value_input.read(cx).value().trim().to_string();
+
+        self.error = if let Err(e) = validate_env_name(&name) {
+            Some(e.to_string())
+        } else if value.is_empty() {
+            Some(format!("Enter a value for {}", name))
+        } else {
+            None
+        };
+        if self.error.is_none() {
+            set_env_var(&name, &value);
+            self.vars = load_vars();
+        }
+        cx.notify();
+    }
+
+    fn remove(&mut self, idx: usize, cx: &mut Context<Self>) {
+        if let Some(var) = self.vars.get(idx) {
+            remove_env_var(var);
+        }
+        self.vars = load_vars();
+        cx.notify();
+    }
+
+    fn render_editor(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let name = self.name_input.read(cx).value().trim().to_string();
+        let known = find_known_env_var(&name);
+
+        section_container(cx)
+            .child(with_tooltip(
+                "Variables are written to the overrides file and passed to apps started from now on. Some only take effect after restarting Hyprland.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Set variable"),
+                cx,
+            ))
+            .child(
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(div().w(px(280.0)).child(TextInput::new(&self.name_input)))
+                    .child(div().flex_1().child(TextInput::new(&self.value_input)))
+                    .child(
+                        Button::new("save-env")
+                            .label("Save")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.save(cx);
+                            })),
+                    ),
+            )
+            .when_some(known, |this, known| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child(known.description),
+                )
+            })
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(cx.theme().danger)
+                        .child(error),
+                )
+            })
+    }
+
+    fn render_vars(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        section_container(cx)
+            .child(with_tooltip(
+                "env lines from every file sourced by hyprland.conf, in the order Hyprland reads them.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Environment variables"),
+                cx,
+            ))
+            .when(self.vars.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_size(px(14.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child("No environment variables found"),
+                )
+            })
+            .children(self.vars.iter().enumerate().map(|(idx, var)| {
+                let count = self.count(&var.name);
+                let name = var.name.clone();
+                let value = var.value.clone();
+
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .py_1()
+                    .child(
+                        div()
+                            .min_w(px(280.0))
+                            .text_color(cx.theme().foreground)
+                            .child(var.name.clone()),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_size(px(12.0))
+                            .text_color(cx.theme().muted_foreground)
+                            .child(var.value.clone()),
+                    )
+                    .when(count > 1, |this| {
+                        this.child(with_tooltip(
+                            format!("Set {} times, the last one wins.", count),
+                            item_pill(cx)
+                                .text_size(px(10.0))
+                                .text_color(cx.theme().danger)
+                                .child("duplicate"),
+                            cx,
+                        ))
+                    })
+                    .when(!var.overridden, |this| {
+                        this.child(
+                            item_pill(cx)
+                                .text_size(px(10.0))
+                                .child(display_path(&var.path)),
+                        )
+                    })
+                    .child(
+                        Button::new(("edit-env", idx))
+                            .label("Edit")
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.edit(&name, &value, window, cx);
+                            })),
+                    )
+                    .when(var.overridden, |this| {
+                        this.child(
+                            Button::new(("remove-env", idx))
+                                .label("Remove")
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.remove(idx, cx);
+                                })),
+                        )
+                    })
+            }))
+    }
+
+    fn render_known(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        section_container(cx)
+            .child(with_tooltip(
+                "Variables commonly set for Wayland sessions, scaling and NVIDIA cards.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Well-known variables"),
+                cx,
+            ))
+            .children(KNOWN_ENV_VARS.iter().enumerate().map(|(idx, known)| {
+                let current = self.effective_value(known.name);
+
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .py_1()
+                    .child(
+                        div()
+                            .v_flex()
+                            .min_w(px(280.0))
+                            .child(div().text_color(cx.theme().foreground).child(known.name))
+                            .child(
+                                div()
+                                    .text_size(px(12.0))
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(known.description),
+                            ),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_size(px(12.0))
+                            .text_color(cx.theme().muted_foreground)
+                            .child(current.unwrap_or("not set").to_string()),
+                    )
+                    .children(known.suggestions.iter().enumerate().map(
+                        |(suggestion_idx, suggestion)| {
+                            Button::new(("suggest-env", idx * 10 + suggestion_idx))
+                                .label(*suggestion)
+                                .on_click(cx.listener(move |this, _, window, cx| {
+                                    this.edit(known.name, suggestion, window, cx);
+                                }))
+                        },
+                    ))
+            }))
+    }
+}
+
+impl Render for EnvSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .v_flex()
+            .gap_4()
+            .child(self.render_editor(cx))
+            .child(self.render_vars(cx))
+            .child(self.render_known(cx))
+    }
+}
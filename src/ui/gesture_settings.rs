@@ -0,0 +1,291 @@
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::slider::{Slider, SliderEvent, SliderState};
+use gpui_component::switch::Switch;
+use std::time::Duration;
+
+use crate::option_registry::write_option;
+use crate::ui::highlight::highlight_row;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::gestures::{GestureOptions, gesture_option, get_gesture_options};
+use crate::util::option::set_keyword;
+
+pub struct GestureSettings {
+    options: GestureOptions,
+    fingers_slider: Entity<SliderState>,
+    distance_slider: Entity<SliderState>,
+    cancel_ratio_slider: Entity<SliderState>,
+    /// Changes not yet applied to the running compositor
+    pending: Vec<(&'static str, String)>,
+    preview_task: Option<Task<()>>,
+    /// Option the command palette jumped to
+    highlighted: Option<&'static str>,
+}
+
+/// Wait for the slider to settle before previewing, every preview spawns hyprctl
+const PREVIEW_DEBOUNCE: Duration = Duration::from_millis(150);
+
+impl GestureSettings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let options = get_gesture_options();
+
+        let fingers_slider = cx.new(|_cx| SliderState::new().min(3.0).max(5.0).step(1.0));
+        let distance_slider = cx.new(|_cx| SliderState::new().min(100.0).max(1000.0).step(10.0));
+        let cancel_ratio_slider = cx.new(|_cx| SliderState::new().min(0.0).max(1.0).step(0.05));
+
+        fingers_slider.update(cx, |state, cx| {
+            state.set_value(options.fingers as f32, window, cx);
+        });
+        distance_slider.update(cx, |state, cx| {
+            state.set_value(options.distance as f32, window, cx);
+        });
+        cancel_ratio_slider.update(cx, |state, cx| {
+            state.set_value(options.cancel_ratio, window, cx);
+        });
+
+        cx.subscribe(
+            &fingers_slider,
+            |this, _, event: &SliderEvent, cx| match event {
+                SliderEvent::Change(value) => {
+                    let fingers = value.start().round() as i64;
+                    if fingers != this.options.fingers {
+                        this.options.fingers = fingers;
+                        this.queue_preview("workspace_swipe_fingers", fingers.to_string(), cx);
+                    }
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        cx.subscribe(
+            &distance_slider,
+            |this, _, event: &SliderEvent, cx| match event {
+                SliderEvent::Change(value) => {
+                    let distance = value.start().round() as i64;
+                    if distance != this.options.distance {
+                        this.options.distance = distance;
+                        this.queue_preview("workspace_swipe_distance", distance.to_string(), cx);
+                    }
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        cx.subscribe(
+            &cancel_ratio_slider,
+            |this, _, event: &SliderEvent, cx| match event {
+                SliderEvent::Change(value) => {
+                    this.options.cancel_ratio = value.start();
+                    this.queue_preview(
+                        "workspace_swipe_cancel_ratio",
+                        format!("{:.2}", value.start()),
+                        cx,
+                    );
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        Self {
+            options,
+            fingers_slider,
+            distance_slider,
+            cancel_ratio_slider,
+            pending: Vec::new(),
+            preview_task: None,
+            highlighted: None,
+        }
+    }
+
+    /// Outline the row of an option, `None` clears it
+    pub fn highlight(&mut self, path: Option<&'static str>, cx: &mut Context<Self>) {
+        self.highlighted = path;
+        cx.notify();
+    }
+
+    /// Apply gesture options to the running compositor once the user stops editing, so changes
+    /// can be tried before saving
+    fn queue_preview(&mut self, option: &'static str, value: String, cx: &mut Context<Self>) {
+        self.pending.retain(|(name, _)| *name != option);
+        self.pending.push((option, value));
+
+        // replacing the task drops the previous timer, which cancels it
+        self.preview_task = Some(cx.spawn(async move |this, cx| {
+            cx.background_executor().timer(PREVIEW_DEBOUNCE).await;
+            let _ = this.update(cx, |this, _cx| {
+                for (option, value) in this.pending.drain(..) {
+                    if let Err(e) = set_keyword(&gesture_option(option), &value) {
+                        eprintln!("{}", e);
+                    }
+                }
+            });
+        }));
+    }
+
+    fn is_highlighted(&self, option: &str) -> bool {
+        self.highlighted
+            .is_some_and(|path| path == gesture_option(option))
+    }
+
+    fn switch_row(
+        &self,
+        option: &'static str,
+        label: &'static str,
+        description: &'static str,
+        checked: bool,
+        set: fn(&mut GestureOptions, bool),
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let row = div().h_flex().gap_4().items_center();
+
+        highlight_row(row, self.is_highlighted(option), cx)
+            .child(with_tooltip(
+                description,
+                div().min_w(px(200.0)).child(label),
+                cx,
+            ))
+            .child(
+                Switch::new(SharedString::from(format!("gesture-{}-switch", option)))
+                    .checked(checked)
+                    .on_click(cx.listener(move |view, checked, _, cx| {
+                        set(&mut view.options, *checked);
+                        view.queue_preview(option, checked.to_string(), cx);
+                        cx.notify();
+                    })),
+            )
+    }
+
+    fn slider_row(
+        &self,
+        option: &'static str,
+        label: &'static str,
+        value: String,
+        slider: &Entity<SliderState>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let row = div().v_flex().gap_1();
+
+        highlight_row(row, self.is_highlighted(option), cx)
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(200.0)).child(label))
+                    .child(
+                        div()
+                            .text_size(px(14.0))
+                            .text_color(cx.theme().muted_foreground)
+                            .child(value),
+                    ),
+            )
+            .child(
+                Slider::new(slider)
+                    .w_full()
+                    .text_color(cx.theme().foreground),
+            )
+    }
+}
+
+impl Render for GestureSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let options = self.options.clone();
+
+        section_container(cx)
+            .min_h(px(200.0))
+            .child(with_tooltip(
+                "Changes are previewed live so you can try a swipe right away. Use the apply button to keep them.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Gesture settings".to_string()),
+                cx,
+            ))
+            .child(self.switch_row(
+                "workspace_swipe",
+                "Workspace swipe",
+                "Swipe horizontally with multiple fingers to switch workspaces.",
+                options.workspace_swipe,
+                |o, v| o.workspace_swipe = v,
+                cx,
+            ))
+            .child(self.slider_row(
+                "workspace_swipe_fingers",
+                "Fingers:",
+                options.fingers.to_string(),
+                &self.fingers_slider,
+                cx,
+            ))
+            .child(self.slider_row(
+                "workspace_swipe_distance",
+                "Swipe distance:",
+                format!("{}px", options.distance),
+                &self.distance_slider,
+                cx,
+            ))
+            .child(self.slider_row(
+                "workspace_swipe_cancel_ratio",
+                "Cancel ratio:",
+                format!("{:.2}", options.cancel_ratio),
+                &self.cancel_ratio_slider,
+                cx,
+            ))
+            .child(self.switch_row(
+                "workspace_swipe_invert",
+                "Invert direction",
+                "Swipe in the opposite direction to switch workspaces.",
+                options.invert,
+                |o, v| o.invert = v,
+                cx,
+            ))
+            .child(self.switch_row(
+                "workspace_swipe_create_new",
+                "Create new workspace",
+                "Swiping right past the last workspace creates a new one.",
+                options.create_new,
+                |o, v| o.create_new = v,
+                cx,
+            ))
+            .child(self.switch_row(
+                "workspace_swipe_forever",
+                "Swipe forever",
+                "Keep switching workspaces in a single swipe instead of stopping at the next one.",
+                options.forever,
+                |o, v| o.forever = v,
+                cx,
+            ))
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(200.0)))
+                    .child(
+                        Button::new("apply-gesture-settings")
+                            .label("Apply gesture setting")
+                            .on_click(move |_, _, _cx| {
+                                let values = [
+                                    ("workspace_swipe", options.workspace_swipe.to_string()),
+                                    ("workspace_swipe_fingers", options.fingers.to_string()),
+                                    ("workspace_swipe_distance", options.distance.to_string()),
+                                    ("workspace_swipe_invert", options.invert.to_string()),
+                                    ("workspace_swipe_cancel_ratio", format!("{:.2}", options.cancel_ratio)),
+                                    ("workspace_swipe_create_new", options.create_new.to_string()),
+                                    ("workspace_swipe_forever", options.forever.to_string()),
+                                ];
+
+                                for (name, value) in values {
+                                    if let Err(e) = write_option(&gesture_option(name), value) {
+                                        eprintln!("Failed to write override: {}", e);
+                                    }
+                                }
+                            }),
+                    ),
+            )
+    }
+}
@@ -0,0 +1,13 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme;
+
+/// Outline the row of an option the command palette jumped to
+pub fn highlight_row<T>(row: Div, highlighted: bool, cx: &mut Context<T>) -> Div {
+    row.when(highlighted, |this| {
+        this.p_1()
+            .border_1()
+            .rounded_sm()
+            .border_color(cx.theme().ring)
+    })
+}
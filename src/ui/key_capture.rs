@@ -0,0 +1,102 @@
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+
+use crate::util::keybinds::{combo_key, modmask_from_mods, mods_from_modmask, xkb_key_name};
+
+/// Emitted with the modifier mask and key name whenever a combination is pressed
+pub enum KeyCaptureEvent {
+    Change { modmask: u32, key: String },
+}
+
+/// Field that records the next key combination pressed while it is focused
+pub struct KeyCapture {
+    focus_handle: FocusHandle,
+    modmask: u32,
+    key: String,
+    placeholder: &'static str,
+}
+
+impl EventEmitter<KeyCaptureEvent> for KeyCapture {}
+
+impl KeyCapture {
+    pub fn new(placeholder: &'static str, cx: &mut Context<Self>) -> Self {
+        Self {
+            focus_handle: cx.focus_handle(),
+            modmask: 0,
+            key: String::new(),
+            placeholder,
+        }
+    }
+
+    /// Show an existing combination, e.g. when editing a bind
+    pub fn set_combo(&mut self, modmask: u32, key: String, cx: &mut Context<Self>) {
+        self.modmask = modmask;
+        self.key = key;
+        cx.notify();
+    }
+
+    fn capture(&mut self, event: &KeyDownEvent, cx: &mut Context<Self>) {
+        let keystroke = &event.keystroke;
+        let modifiers = [
+            (keystroke.modifiers.platform, "SUPER"),
+            (keystroke.modifiers.control, "CTRL"),
+            (keystroke.modifiers.alt, "ALT"),
+            (keystroke.modifiers.shift, "SHIFT"),
+        ];
+        let mods = modifiers
+            .iter()
+            .filter(|(pressed, _)| *pressed)
+            .map(|(_, name)| *name)
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        self.modmask = modmask_from_mods(&mods);
+        self.key = xkb_key_name(&keystroke.key);
+
+        cx.emit(KeyCaptureEvent::Change {
+            modmask: self.modmask,
+            key: self.key.clone(),
+        });
+        cx.stop_propagation();
+        cx.notify();
+    }
+}
+
+impl Render for KeyCapture {
+    fn render(&mut self, window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let focused = self.focus_handle.is_focused(window);
+        let label = if self.key.is_empty() {
+            self.placeholder.to_string()
+        } else {
+            combo_key(&mods_from_modmask(self.modmask), &self.key).replace('+', " + ")
+        };
+
+        div()
+            .id("key-capture")
+            .track_focus(&self.focus_handle)
+            .min_w(px(240.0))
+            .px_3()
+            .py_1()
+            .border_1()
+            .rounded_md()
+            .cursor_pointer()
+            .border_color(if focused {
+                cx.theme().primary
+            } else {
+                cx.theme().border
+            })
+            .text_color(if self.key.is_empty() {
+                cx.theme().muted_foreground
+            } else {
+                cx.theme().foreground
+            })
+            .on_click(cx.listener(|this, _, window, cx| {
+                window.focus(&this.focus_handle);
+                cx.notify();
+            }))
+            .on_key_down(cx.listener(|this, event: &KeyDownEvent, _window, cx| {
+                this.capture(event, cx);
+            }))
+            .child(label)
+    }
+}
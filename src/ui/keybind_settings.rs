@@ -0,0 +1,581 @@
+use std::collections::BTreeMap;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::dropdown::*;
+use gpui_component::input::{InputState, TextInput};
+
+use crate::ui::item_pill::item_pill;
+use crate::ui::key_capture::{KeyCapture, KeyCaptureEvent};
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::dispatchers::{
+    ArgKind, DISPATCHERS, MOUSE_DISPATCHERS, dispatch, find_dispatcher, validate_args,
+};
+use crate::util::keybinds::{BIND_KEYWORDS, Keybind, get_keybinds, replace_bind};
+
+pub struct KeybindSettings {
+    binds: Vec<Keybind>,
+    /// Bind being edited, `None` while adding a new one
+    editing: Option<usize>,
+    editor_open: bool,
+    draft: Keybind,
+    key_capture: Entity<KeyCapture>,
+    keyword_dropdown: Entity<DropdownState<Vec<String>>>,
+    dispatcher_dropdown: Entity<DropdownState<SearchableVec<String>>>,
+    /// Used for dispatchers taking one of a fixed set of values
+    arg_dropdown: Entity<DropdownState<Vec<String>>>,
+    arg_input: Entity<InputState>,
+    error: Option<String>,
+}
+
+fn dispatcher_names() -> Vec<String> {
+    DISPATCHERS.iter().map(|d| d.name.to_string()).collect()
+}
+
+/// Placeholder describing the argument a dispatcher expects
+fn arg_hint(kind: ArgKind) -> &'static str {
+    match kind {
+        ArgKind::None => "No arguments",
+        ArgKind::Command => "Command, e.g. kitty",
+        ArgKind::Workspace => "Workspace, e.g. 1, +1, name:web or special:term",
+        ArgKind::Direction => "Direction: l, r, u or d",
+        ArgKind::Window => "Window, e.g. class:firefox",
+        ArgKind::Monitor => "Monitor, e.g. DP-1 or l",
+        ArgKind::Resize => "Size, e.g. 10 -10 or exact 50% 50%",
+        ArgKind::Number => "Number, e.g. +0.1 or exact 0.5",
+        ArgKind::Choice(_) | ArgKind::Text => "Arguments",
+    }
+}
+
+fn display_combo(bind: &Keybind) -> String {
+    bind.combo().replace('+', " + ")
+}
+
+impl KeybindSettings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let binds = get_keybinds().unwrap_or_else(|e| {
+            eprintln!("Failed to get binds: {}", e);
+            Vec::new()
+        });
+
+        let keyword_dropdown = cx.new(|cx| {
+            DropdownState::new(
+                BIND_KEYWORDS.iter().map(|k| k.to_string()).collect(),
+                None,
+                window,
+                cx,
+            )
+        });
+        let dispatcher_dropdown = cx
+            .new(|cx| DropdownState::new(SearchableVec::new(dispatcher_names()), None, window, cx));
+        let arg_dropdown = cx.new(|cx| DropdownState::new(Vec::new(), None, window, cx));
+        let arg_input = cx.new(|cx| InputState::new(window, cx).placeholder("Arguments"));
+
+        cx.subscribe(
+            &keyword_dropdown,
+            |this, _, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(keyword)) = event {
+                    this.draft.set_keyword(keyword);
+                    this.error = None;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        cx.subscribe_in(
+            &dispatcher_dropdown,
+            window,
+            |this, _, event: &DropdownEvent<SearchableVec<String>>, window, cx| {
+                if let DropdownEvent::Confirm(Some(dispatcher)) = event {
+                    this.draft.dispatcher = dispatcher.clone();
+                    this.draft.arg.clear();
+                    this.sync_arg_controls(window, cx);
+                }
+            },
+        )
+        .detach();
+
+        cx.subscribe(
+            &arg_dropdown,
+            |this, _, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(arg)) = event {
+                    this.draft.arg = arg.clone();
+                    this.error = None;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        let key_capture = cx.new(|cx| KeyCapture::new("Click and press a key combination", cx));
+
+        cx.subscribe(&key_capture, |this, _, event: &KeyCaptureEvent, cx| {
+            let KeyCaptureEvent::Change { modmask, key } = event;
+            this.draft.modmask = *modmask;
+            this.draft.key = key.clone();
+            this.draft.keycode = 0;
+            this.error = None;
+            cx.notify();
+        })
+        .detach();
+
+        Self {
+            binds,
+            editing: None,
+            editor_open: false,
+            draft: Keybind::default(),
+            key_capture,
+            keyword_dropdown,
+            dispatcher_dropdown,
+            arg_dropdown,
+            arg_input,
+            error: None,
+        }
+    }
+
+    /// Show the argument controls matching the dispatcher of the draft
+    fn sync_arg_controls(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let kind = find_dispatcher(&self.draft.dispatcher)
+            .map(|d| d.arg)
+            .unwrap_or(ArgKind::Text);
+        let arg = self.draft.arg.clone();
+
+        if let ArgKind::Choice(choices) = kind {
+            let choices: Vec<String> = choices.iter().map(|c| c.to_string()).collect();
+            self.arg_dropdown.update(cx, |state, cx| {
+                state.set_items(choices, window, cx);
+                state.set_selected_value(&arg, window, cx);
+            });
+        }
+        self.arg_input.update(cx, |state, cx| {
+            state.set_placeholder(arg_hint(kind), window, cx);
+            state.set_value(arg, window, cx);
+        });
+
+        self.error = None;
+        cx.notify();
+    }
+
+    /// Read the argument from whichever control is shown and check it against the catalogue
+    fn validate_draft(&mut self, cx: &mut Context<Self>) -> bool {
+        let kind = find_dispatcher(&self.draft.dispatcher).map(|d| d.arg);
+        if !matches!(kind, Some(ArgKind::Choice(_))) {
+            self.draft.arg = self.arg_input.read(cx).value().trim().to_string();
+        }
+
+        self.error = if self.draft.key_name().is_empty() {
+            Some("Press the key combination for this bind".to_string())
+        } else if self.draft.dispatcher.is_empty() {
+            Some("Pick a dispatcher".to_string())
+        } else {
+            validate_args(&self.draft.dispatcher, &self.draft.arg, self.draft.mouse)
+                .err()
+                .map(|e| e.to_string())
+        };
+
+        cx.notify();
+        self.error.is_none()
+    }
+
+    /// Run the dispatcher of the draft once so the user can see what the bind will do
+    fn test_dispatcher(&mut self, cx: &mut Context<Self>) {
+        // only the argument matters for a test, the keys may still be missing
+        let has_keys = !self.draft.key_name().is_empty();
+        if !has_keys {
+            self.draft.key = "test".to_string();
+        }
+        let valid = self.validate_draft(cx);
+        if !has_keys {
+            self.draft.key.clear();
+        }
+
+        if valid && let Err(e) = dispatch(&self.draft.dispatcher, &self.draft.arg) {
+            self.error = Some(e.to_string());
+            cx.notify();
+        }
+    }
+
+    /// Open the editor for an existing bind, or for a new bind when `idx` is `None`
+    fn open_editor(&mut self, idx: Option<usize>, window: &mut Window, cx: &mut Context<Self>) {
+        let draft = idx
+            .and_then(|idx| self.binds.get(idx))
+            .cloned()
+            .unwrap_or_else(|| Keybind {
+                dispatcher: "exec".to_string(),
+                ..Default::default()
+            });
+
+        let keyword = draft.keyword();
+        let mut keywords: Vec<String> = BIND_KEYWORDS.iter().map(|k| k.to_string()).collect();
+        if !keywords.contains(&keyword) {
+            keywords.push(keyword.clone());
+        }
+
+        // dispatchers from plugins are not in the catalogue, keep them selectable
+        let mut dispatchers = dispatcher_names();
+        if !dispatchers.contains(&draft.dispatcher) {
+            dispatchers.push(draft.dispatcher.clone());
+        }
+
+        self.keyword_dropdown.update(cx, |state, cx| {
+            state.set_items(keywords, window, cx);
+            state.set_selected_value(&keyword, window, cx);
+        });
+        self.dispatcher_dropdown.update(cx, |state, cx| {
+            state.set_items(SearchableVec::new(dispatchers), window, cx);
+            state.set_selected_value(&draft.dispatcher, window, cx);
+        });
+
+        let (modmask, key) = (draft.modmask, draft.key_name());
+        self.key_capture.update(cx, |capture, cx| {
+            capture.set_combo(modmask, key, cx);
+        });
+
+        self.draft = draft;
+        self.editing = idx;
+        self.editor_open = true;
+        self.sync_arg_controls(window, cx);
+    }
+
+    fn close_editor(&mut self, cx: &mut Context<Self>) {
+        self.editor_open = false;
+        self.editing = None;
+        cx.notify();
+    }
+
+    /// Other binds in the same submap using the combo of `bind`
+    fn conflicts(&self, bind: &Keybind, skip: Option<usize>) -> usize {
+        let combo = bind.combo();
+        self.binds
+            .iter()
+            .enumerate()
+            .filter(|(idx, other)| {
+                Some(*idx) != skip && other.submap == bind.submap && other.combo() == combo
+            })
+            .count()
+    }
+
+    fn save_bind(&mut self, cx: &mut Context<Self>) {
+        if !self.validate_draft(cx) {
+            return;
+        }
+
+        let bind = self.draft.clone();
+        replace_bind(
+            self.editing.and_then(|idx| self.binds.get(idx)),
+            Some(&bind),
+        );
+
+        match self.editing {
+            Some(idx) => self.binds[idx] = bind,
+            None => self.binds.push(bind),
+        }
+        self.close_editor(cx);
+    }
+
+    fn remove_bind(&mut self, idx: usize, cx: &mut Context<Self>) {
+        if idx >= self.binds.len() {
+            return;
+        }
+        let bind = self.binds.remove(idx);
+        replace_bind(Some(&bind), None);
+
+        // keep the editor pointing at the same bind
+        match self.editing {
+            Some(editing) if editing == idx => self.close_editor(cx),
+            Some(editing) if editing > idx => self.editing = Some(editing - 1),
+            _ => {}
+        }
+        cx.notify();
+    }
+
+    fn render_bind_row(&self, idx: usize, bind: &Keybind, cx: &mut Context<Self>) -> Div {
+        let keyword = bind.keyword();
+        let duplicate = self.conflicts(bind, Some(idx)) > 0;
+        // binds inside a submap need their submap block, they are edited on the submap page
+        let editable = bind.submap.is_empty();
+
+        div()
+            .h_flex()
+            .gap_2()
+            .items_center()
+            .py_1()
+            .child(
+                div()
+                    .min_w(px(200.0))
+                    .text_color(cx.theme().foreground)
+                    .child(display_combo(bind)),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .text_size(px(12.0))
+                    .text_color(cx.theme().muted_foreground)
+                    .child(if bind.has_description {
+                        format!("{} ({})", bind.arg, bind.description)
+                    } else {
+                        bind.arg.clone()
+                    }),
+            )
+            .when(keyword != "bind", |this| {
+                this.child(item_pill(cx).text_size(px(10.0)).child(keyword))
+            })
+            .when(!bind.submap.is_empty(), |this| {
+                this.child(
+                    item_pill(cx)
+                        .text_size(px(10.0))
+                        .child(format!("submap {}", bind.submap)),
+                )
+            })
+            .when(duplicate, |this| {
+                this.child(with_tooltip(
+                    "Another bind uses the same key combination.",
+                    item_pill(cx)
+                        .text_size(px(10.0))
+                        .text_color(cx.theme().danger)
+                        .child("duplicate"),
+                    cx,
+                ))
+            })
+            .when(editable, |this| {
+                this.child(
+                    Button::new(("edit-bind", idx))
+                        .label("Edit")
+                        .on_click(cx.listener(move |this, _, window, cx| {
+                            this.open_editor(Some(idx), window, cx);
+                        })),
+                )
+                .child(
+                    Button::new(("remove-bind", idx))
+                        .label("Remove")
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.remove_bind(idx, cx);
+                        })),
+                )
+            })
+    }
+
+    fn render_editor(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let conflicts = if self.draft.key_name().is_empty() {
+            0
+        } else {
+            self.conflicts(&self.draft, self.editing)
+        };
+        let dispatcher = find_dispatcher(&self.draft.dispatcher);
+        let kind = dispatcher.map(|d| d.arg).unwrap_or(ArgKind::Text);
+
+        section_container(cx)
+            .child(
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child(if self.editing.is_some() {
+                        "Edit bind"
+                    } else {
+                        "New bind"
+                    }),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(120.0)).child("Keys:"))
+                    .child(self.key_capture.clone()),
+            )
+            .when(conflicts > 0, |this| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(cx.theme().danger)
+                        .child(format!(
+                            "{} is already used by {} other bind(s)",
+                            display_combo(&self.draft),
+                            conflicts
+                        )),
+                )
+            })
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(with_tooltip(
+                        "binde repeats while held, bindl works on the lock screen, bindr fires on release, bindm is for mouse binds and bindn does not consume the key.",
+                        div().min_w(px(120.0)).child("Type:"),
+                        cx,
+                    ))
+                    .child(Dropdown::new(&self.keyword_dropdown).min_w(px(160.0))),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(120.0)).child("Dispatcher:"))
+                    .child(Dropdown::new(&self.dispatcher_dropdown).w(px(240.0)))
+                    .when_some(dispatcher, |this, dispatcher| {
+                        this.child(
+                            div()
+                                .text_size(px(12.0))
+                                .text_color(cx.theme().muted_foreground)
+                                .child(dispatcher.description),
+                        )
+                    }),
+            )
+            .when(kind != ArgKind::None && !self.draft.mouse, |this| {
+                this.child(
+                    div()
+                        .h_flex()
+                        .gap_4()
+                        .items_center()
+                        .child(div().min_w(px(120.0)).child("Arguments:"))
+                        .map(|this| {
+                            if let ArgKind::Choice(_) = kind {
+                                this.child(Dropdown::new(&self.arg_dropdown).w(px(240.0)))
+                            } else {
+                                this.child(div().flex_1().child(TextInput::new(&self.arg_input)))
+                            }
+                        }),
+                )
+            })
+            .when(self.draft.mouse, |this| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child(format!(
+                            "Mouse binds use {} with a mouse button such as mouse:272.",
+                            MOUSE_DISPATCHERS.join(" or ")
+                        )),
+                )
+            })
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(cx.theme().danger)
+                        .child(error),
+                )
+            })
+            .child(
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(div().min_w(px(120.0)))
+                    .child(with_tooltip(
+                        "Stages the bind, it is written to the overrides file and applied once the pending changes are saved.",
+                        Button::new("save-bind")
+                            .label("Save bind")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.save_bind(cx);
+                            })),
+                        cx,
+                    ))
+                    .child(with_tooltip(
+                        "Runs the dispatcher once with these arguments.",
+                        Button::new("test-bind")
+                            .label("Test")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.test_dispatcher(cx);
+                            })),
+                        cx,
+                    ))
+                    .child(
+                        Button::new("cancel-bind")
+                            .label("Cancel")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.close_editor(cx);
+                            })),
+                    ),
+            )
+    }
+}
+
+impl Render for KeybindSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let mut groups: BTreeMap<&str, Vec<(usize, &Keybind)>> = BTreeMap::new();
+        for (idx, bind) in self.binds.iter().enumerate() {
+            groups
+                .entry(bind.dispatcher.as_str())
+                .or_default()
+                .push((idx, bind));
+        }
+
+        let group_elements = groups
+            .iter()
+            .map(|(dispatcher, binds)| {
+                div()
+                    .v_flex()
+                    .gap_1()
+                    .child(
+                        div()
+                            .h_flex()
+                            .gap_2()
+                            .items_center()
+                            .child(
+                                div()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(cx.theme().foreground)
+                                    .child(dispatcher.to_string()),
+                            )
+                            .child(
+                                item_pill(cx)
+                                    .text_size(px(10.0))
+                                    .child(binds.len().to_string()),
+                            ),
+                    )
+                    .children(
+                        binds
+                            .iter()
+                            .map(|(idx, bind)| self.render_bind_row(*idx, bind, cx)),
+                    )
+            })
+            .collect::<Vec<_>>();
+
+        div()
+            .v_flex()
+            .gap_4()
+            .when(self.editor_open, |this| {
+                this.child(self.render_editor(cx))
+            })
+            .child(
+                section_container(cx)
+                    .child(
+                        div()
+                            .h_flex()
+                            .gap_4()
+                            .items_center()
+                            .child(with_tooltip(
+                                "All binds known to Hyprland, grouped by dispatcher. Binds inside a submap are shown but edited with their submap.",
+                                div()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(cx.theme().foreground)
+                                    .child("Keybinds".to_string()),
+                                cx,
+                            ))
+                            .child(
+                                Button::new("add-bind")
+                                    .label("Add bind")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.open_editor(None, window, cx);
+                                    })),
+                            ),
+                    )
+                    .when(self.binds.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_size(px(14.0))
+                                .text_color(cx.theme().muted_foreground)
+                                .child("No binds found"),
+                        )
+                    })
+                    .children(group_elements),
+            )
+    }
+}
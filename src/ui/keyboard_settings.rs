@@ -6,8 +6,8 @@ use std::collections::HashSet;
 use gpui_component::{ActiveTheme as _, StyledExt};
 
 use crate::{
-    setting_writer::{self, SettingObjectKey, DeviceSetting},
-    ui::{section_container::section_container, tooltip::with_tooltip},
+    setting_writer::{self, DeviceSetting, SettingObjectKey},
+    ui::{highlight::highlight_row, section_container::section_container, tooltip::with_tooltip},
     util::keyboard::{LocaleInfo, current_device_locales, get_all_keyboards, sys_locales},
 };
 
@@ -16,8 +16,12 @@ pub struct KeyboardSettings {
     available_locales: Vec<LocaleInfo>,
     devices: Vec<crate::util::keyboard::Keyboard>,
     device_dropdowns: Vec<Entity<DropdownState<Vec<String>>>>,
+    /// Option the command palette jumped to
+    highlighted: Option<&'static str>,
 }
 
+const KB_LAYOUT_OPTION: &str = "input:kb_layout";
+
 impl KeyboardSettings {
     pub fn new(window: &mut gpui::Window, cx: &mut gpui::Context<Self>) -> Self {
         let keyboards = get_all_keyboards().unwrap_or_else(|e| {
@@ -100,9 +104,16 @@ impl KeyboardSettings {
             available_locales,
             devices: keyboards,
             device_dropdowns,
+            highlighted: None,
         }
     }
 
+    /// Outline the layout dropdowns when the command palette jumped to the layout option
+    pub fn highlight(&mut self, path: Option<&'static str>, cx: &mut Context<Self>) {
+        self.highlighted = path;
+        cx.notify();
+    }
+
     fn extract_code_from_label(&self, label: &str) -> Option<String> {
         // Extract code from "Label (code)" format
         label
@@ -115,6 +126,7 @@ impl KeyboardSettings {
 impl Render for KeyboardSettings {
     fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
         let devices = &self.devices;
+        let highlighted = self.highlighted == Some(KB_LAYOUT_OPTION);
 
         section_container(cx)
             .flex_col()
@@ -139,7 +151,7 @@ impl Render for KeyboardSettings {
                             .border_color(cx.theme().border)
                             .child(div().font_weight(FontWeight::BOLD).child(d.name.clone()))
                             .child(div().text_sm().child(format!("Current layout: {}", d.layout)))
-                            .child(div().h_flex().child(Dropdown::new(dropdown).min_w(px(200.0))))
+                            .child(highlight_row(div().h_flex(), highlighted, cx).child(Dropdown::new(dropdown).min_w(px(200.0))))
                             .child(
                                 Button::new(("apply-keyboard-settings", idx))
                                     .label("Apply keyboard setting")
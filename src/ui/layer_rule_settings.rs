@@ -0,0 +1,458 @@
+use std::collections::BTreeMap;
+
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::dropdown::*;
+use gpui_component::input::{InputState, TextInput};
+use gpui_component::switch::Switch;
+
+use crate::ui::item_pill::item_pill;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::layer_rules::{
+    LAYER_ANIMATIONS, LAYER_FLAG_RULES, LAYER_LEVELS, Layer, LayerRule, get_layer_rules,
+    get_layers, replace_layer_rules, validate_alpha,
+};
+
+const NO_ANIMATION: &str = "default";
+
+pub struct LayerRuleSettings {
+    layers: BTreeMap<String, Vec<Layer>>,
+    rules: Vec<LayerRule>,
+    /// Namespace shown in the editor
+    selected: Option<String>,
+    /// Flag rules enabled for the selected namespace
+    flags: Vec<&'static str>,
+    ignore_alpha: bool,
+    alpha_input: Entity<InputState>,
+    animation: Option<String>,
+    animation_dropdown: Entity<DropdownState<Vec<String>>>,
+    namespace_input: Entity<InputState>,
+    error: Option<String>,
+}
+
+fn load_layers() -> BTreeMap<String, Vec<Layer>> {
+    get_layers().unwrap_or_else(|e| {
+        eprintln!("Failed to get layers: {}", e);
+        BTreeMap::new()
+    })
+}
+
+fn load_rules() -> Vec<LayerRule> {
+    get_layer_rules().unwrap_or_else(|e| {
+        eprintln!("Failed to get layer rules: {}", e);
+        Vec::new()
+    })
+}
+
+impl LayerRuleSettings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let alpha_input = cx.new(|cx| InputState::new(window, cx).placeholder("e.g. 0.5"));
+        let namespace_input = cx.new(|cx| InputState::new(window, cx).placeholder("Namespace"));
+        let animation_dropdown = cx.new(|cx| {
+            DropdownState::new(
+                std::iter::once(NO_ANIMATION)
+                    .chain(LAYER_ANIMATIONS)
+                    .map(|a| a.to_string())
+                    .collect(),
+                None,
+                window,
+                cx,
+            )
+        });
+
+        cx.subscribe(
+            &animation_dropdown,
+            |this, _, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(animation)) = event {
+                    this.animation = (animation != NO_ANIMATION).then(|| animation.clone());
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        Self {
+            layers: load_layers(),
+            rules: load_rules(),
+            selected: None,
+            flags: Vec::new(),
+            ignore_alpha: false,
+            alpha_input,
+            animation: None,
+            animation_dropdown,
+            namespace_input,
+            error: None,
+        }
+    }
+
+    fn overridden_rules(&self, namespace: &str) -> Vec<LayerRule> {
+        self.rules
+            .iter()
+            .filter(|rule| rule.overridden && rule.namespace == namespace)
+            .cloned()
+            .collect()
+    }
+
+    /// Namespaces with rules but no open surface, e.g. a launcher that is closed
+    fn closed_namespaces(&self) -> Vec<&str> {
+        let mut namespaces: Vec<&str> = self
+            .rules
+            .iter()
+            .map(|rule| rule.namespace.as_str())
+            .filter(|namespace| {
+                !self
+                    .layers
+                    .values()
+                    .flatten()
+                    .any(|layer| layer.namespace == *namespace)
+            })
+            .collect();
+        namespaces.sort_unstable();
+        namespaces.dedup();
+        namespaces
+    }
+
+    /// Load the overridden rules of a namespace into the editor
+    fn select(&mut self, namespace: String, window: &mut Window, cx: &mut Context<Self>) {
+        let rules = self.overridden_rules(&namespace);
+        let find = |name: &str| rules.iter().find(|rule| rule.name() == name);
+
+        self.flags = LAYER_FLAG_RULES
+            .iter()
+            .map(|(name, _)| *name)
+            .filter(|name| find(name).is_some())
+            .collect();
+        let alpha = find("ignorealpha").map(|rule| rule.params().to_string());
+        self.ignore_alpha = alpha.is_some();
+        self.animation = find("animation").map(|rule| rule.params().to_string());
+
+        let animation = self
+            .animation
+            .clone()
+            .unwrap_or_else(|| NO_ANIMATION.to_string());
+        self.animation_dropdown.update(cx, |state, cx| {
+            state.set_selected_value(&animation, window, cx);
+        });
+        self.alpha_input.update(cx, |state, cx| {
+            state.set_value(alpha.unwrap_or_default(), window, cx);
+        });
+
+        self.selected = Some(namespace);
+        self.error = None;
+        cx.notify();
+    }
+
+    fn add_namespace(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let namespace = self.namespace_input.read(cx).value().trim().to_string();
+        if namespace.is_empty() || namespace.contains(',') {
+            self.error = Some("Namespaces can not be empty or contain commas".to_string());
+            cx.notify();
+            return;
+        }
+
+        self.namespace_input.update(cx, |state, cx| {
+            state.set_value("", window, cx);
+        });
+        self.select(namespace, window, cx);
+    }
+
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        self.layers = load_layers();
+        self.rules = load_rules();
+        cx.notify();
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        let Some(namespace) = self.selected.clone() else {
+            return;
+        };
+
+        let mut rules: Vec<LayerRule> = self
+            .flags
+            .iter()
+            .map(|flag| LayerRule::new(flag, &namespace))
+            .collect();
+
+        if self.ignore_alpha {
+            let alpha = self.alpha_input.read(cx).value().trim().to_string();
+            if let Err(e) = validate_alpha(&alpha) {
+                self.error = Some(e.to_string());
+                cx.notify();
+                return;
+            }
+            rules.push(LayerRule::new(
+                &format!("ignorealpha {}", alpha),
+                &namespace,
+            ));
+        }
+        if let Some(animation) = &self.animation {
+            rules.push(LayerRule::new(
+                &format!("animation {}", animation),
+                &namespace,
+            ));
+        }
+
+        replace_layer_rules(&self.overridden_rules(&namespace), &rules);
+
+        self.error = None;
+        self.refresh(cx);
+    }
+
+    fn render_namespace_row(
+        &self,
+        namespace: &str,
+        level: Option<usize>,
+        cx: &mut Context<Self>,
+    ) -> Div {
+        let selected = self.selected.as_deref() == Some(namespace);
+        let rules: Vec<&LayerRule> = self
+            .rules
+            .iter()
+            .filter(|rule| rule.namespace == namespace)
+            .collect();
+        let namespace = namespace.to_string();
+
+        div()
+            .h_flex()
+            .gap_2()
+            .items_center()
+            .py_1()
+            .child(
+                div()
+                    .min_w(px(200.0))
+                    .text_color(if selected {
+                        cx.theme().primary
+                    } else {
+                        cx.theme().foreground
+                    })
+                    .child(namespace.clone()),
+            )
+            .when_some(level, |this, level| {
+                this.child(
+                    item_pill(cx)
+                        .text_size(px(10.0))
+                        .child(LAYER_LEVELS.get(level).copied().unwrap_or("unknown")),
+                )
+            })
+            .child(
+                div()
+                    .flex_1()
+                    .h_flex()
+                    .gap_1()
+                    .children(rules.into_iter().map(|rule| {
+                        item_pill(cx)
+                            .text_size(px(10.0))
+                            .when(!rule.overridden, |this| {
+                                this.text_color(cx.theme().muted_foreground)
+                            })
+                            .child(rule.rule.clone())
+                    })),
+            )
+            .child(
+                Button::new(SharedString::from(format!("edit-layer-{}", namespace)))
+                    .label("Edit")
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.select(namespace.clone(), window, cx);
+                    })),
+            )
+    }
+
+    fn render_layers(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let closed = self.closed_namespaces();
+
+        section_container(cx)
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(with_tooltip(
+                        "Layer shell surfaces such as bars, launchers and notifications, grouped by monitor. Greyed out rules come from hyprland.conf.",
+                        div()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(cx.theme().foreground)
+                            .child("Layers"),
+                        cx,
+                    ))
+                    .child(
+                        Button::new("refresh-layers")
+                            .label("Refresh")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.refresh(cx);
+                            })),
+                    ),
+            )
+            .when(self.layers.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_size(px(14.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child("No layers found"),
+                )
+            })
+            .children(self.layers.iter().map(|(monitor, layers)| {
+                div()
+                    .v_flex()
+                    .gap_1()
+                    .child(
+                        div()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(cx.theme().foreground)
+                            .child(monitor.clone()),
+                    )
+                    .children(layers.iter().map(|layer| {
+                        self.render_namespace_row(&layer.namespace, Some(layer.level), cx)
+                    }))
+            }))
+            .when(!closed.is_empty(), |this| {
+                this.child(
+                    div()
+                        .v_flex()
+                        .gap_1()
+                        .child(
+                            div()
+                                .font_weight(FontWeight::BOLD)
+                                .text_color(cx.theme().foreground)
+                                .child("Not open"),
+                        )
+                        .children(
+                            closed
+                                .into_iter()
+                                .map(|namespace| self.render_namespace_row(namespace, None, cx)),
+                        ),
+                )
+            })
+            .when(self.selected.is_none(), |this| {
+                this.when_some(self.error.clone(), |this, error| {
+                    this.child(
+                        div()
+                            .text_size(px(12.0))
+                            .text_color(cx.theme().danger)
+                            .child(error),
+                    )
+                })
+            })
+            .child(
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(div().w(px(240.0)).child(TextInput::new(&self.namespace_input)))
+                    .child(with_tooltip(
+                        "Add rules for a namespace that is not open right now.",
+                        Button::new("add-namespace")
+                            .label("Add namespace")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.add_namespace(window, cx);
+                            })),
+                        cx,
+                    )),
+            )
+    }
+
+    fn render_editor(&self, namespace: &str, cx: &mut Context<Self>) -> impl IntoElement {
+        section_container(cx)
+            .child(
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child(format!("Rules for {}", namespace)),
+            )
+            .children(LAYER_FLAG_RULES.iter().map(|(name, description)| {
+                let enabled = self.flags.contains(name);
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(with_tooltip(
+                        *description,
+                        div().min_w(px(120.0)).child(*name),
+                        cx,
+                    ))
+                    .child(
+                        Switch::new(SharedString::from(format!("layer-{}", name)))
+                            .checked(enabled)
+                            .on_click(cx.listener(move |this, checked: &bool, _, cx| {
+                                this.flags.retain(|flag| flag != name);
+                                if *checked {
+                                    this.flags.push(name);
+                                }
+                                cx.notify();
+                            })),
+                    )
+            }))
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(with_tooltip(
+                        "Only blur pixels more opaque than this value.",
+                        div().min_w(px(120.0)).child("ignorealpha"),
+                        cx,
+                    ))
+                    .child(
+                        Switch::new("layer-ignorealpha")
+                            .checked(self.ignore_alpha)
+                            .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                this.ignore_alpha = *checked;
+                                cx.notify();
+                            })),
+                    )
+                    .when(self.ignore_alpha, |this| {
+                        this.child(div().w(px(120.0)).child(TextInput::new(&self.alpha_input)))
+                    }),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(with_tooltip(
+                        "Animation used when the surface opens and closes.",
+                        div().min_w(px(120.0)).child("animation"),
+                        cx,
+                    ))
+                    .child(Dropdown::new(&self.animation_dropdown).w(px(240.0))),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(cx.theme().danger)
+                        .child(error),
+                )
+            })
+            .child(
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(div().min_w(px(120.0)))
+                    .child(with_tooltip(
+                        "Writes the rules to the overrides file. Surfaces that are already open pick them up once they are restarted.",
+                        Button::new("save-layer-rules")
+                            .label("Save rules")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.save(cx);
+                            })),
+                        cx,
+                    )),
+            )
+    }
+}
+
+impl Render for LayerRuleSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .v_flex()
+            .gap_4()
+            .when_some(self.selected.clone(), |this, namespace| {
+                this.child(self.render_editor(&namespace, cx))
+            })
+            .child(self.render_layers(cx))
+    }
+}
@@ -0,0 +1,457 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::IndexPath;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::dropdown::*;
+use gpui_component::slider::{Slider, SliderEvent, SliderState};
+use gpui_component::switch::Switch;
+
+use crate::option_registry::write_option;
+use crate::ui::highlight::highlight_row;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::layout::{
+    FORCE_SPLIT_MODES, LAYOUTS, LayoutOptions, MASTER_NEW_STATUSES, MASTER_ORIENTATIONS,
+    get_layout_options, preview_windows,
+};
+
+const PREVIEW_WIDTH: f32 = 320.0;
+const PREVIEW_HEIGHT: f32 = 180.0;
+
+pub struct LayoutSettings {
+    options: LayoutOptions,
+    layout_dropdown: Entity<DropdownState<Vec<String>>>,
+    force_split_dropdown: Entity<DropdownState<Vec<String>>>,
+    new_status_dropdown: Entity<DropdownState<Vec<String>>>,
+    orientation_dropdown: Entity<DropdownState<Vec<String>>>,
+    mfact_slider: Entity<SliderState>,
+    window_count_slider: Entity<SliderState>,
+    window_count: usize,
+    /// Option the command palette jumped to
+    highlighted: Option<&'static str>,
+}
+
+/// Dropdown over a list of values with the current one selected
+fn value_dropdown(
+    values: &[&str],
+    current: &str,
+    window: &mut Window,
+    cx: &mut App,
+) -> Entity<DropdownState<Vec<String>>> {
+    let selected = values
+        .iter()
+        .position(|v| *v == current)
+        .map(IndexPath::new);
+    let values = values.iter().map(|v| v.to_string()).collect();
+
+    cx.new(|cx| DropdownState::new(values, selected, window, cx))
+}
+
+impl LayoutSettings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let options = get_layout_options();
+
+        let layout_dropdown = value_dropdown(&LAYOUTS, &options.layout, window, cx);
+        let new_status_dropdown =
+            value_dropdown(&MASTER_NEW_STATUSES, &options.new_status, window, cx);
+        let orientation_dropdown =
+            value_dropdown(&MASTER_ORIENTATIONS, &options.orientation, window, cx);
+        let force_split_dropdown = cx.new(|cx| {
+            DropdownState::new(
+                FORCE_SPLIT_MODES.map(String::from).to_vec(),
+                FORCE_SPLIT_MODES
+                    .get(options.force_split as usize)
+                    .map(|_| IndexPath::new(options.force_split as usize)),
+                window,
+                cx,
+            )
+        });
+
+        cx.subscribe(
+            &layout_dropdown,
+            |this, _, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(layout)) = event {
+                    this.options.layout = layout.clone();
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        cx.subscribe(
+            &force_split_dropdown,
+            |this, _, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(label)) = event
+                    && let Some(mode) = label
+                        .split_once(' ')
+                        .and_then(|(mode, _)| mode.parse().ok())
+                {
+                    this.options.force_split = mode;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        cx.subscribe(
+            &new_status_dropdown,
+            |this, _, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(status)) = event {
+                    this.options.new_status = status.clone();
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        cx.subscribe(
+            &orientation_dropdown,
+            |this, _, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(orientation)) = event {
+                    this.options.orientation = orientation.clone();
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        let mfact_slider = cx.new(|_cx| SliderState::new().min(0.1).max(0.9).step(0.05));
+        mfact_slider.update(cx, |state, cx| {
+            state.set_value(options.mfact, window, cx);
+        });
+
+        cx.subscribe(
+            &mfact_slider,
+            |this, _, event: &SliderEvent, cx| match event {
+                SliderEvent::Change(value) => {
+                    this.options.mfact = value.start();
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        let window_count = 4;
+        let window_count_slider = cx.new(|_cx| SliderState::new().min(1.0).max(6.0).step(1.0));
+        window_count_slider.update(cx, |state, cx| {
+            state.set_value(window_count as f32, window, cx);
+        });
+
+        cx.subscribe(
+            &window_count_slider,
+            |this, _, event: &SliderEvent, cx| match event {
+                SliderEvent::Change(value) => {
+                    this.window_count = value.start().round() as usize;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        Self {
+            options,
+            layout_dropdown,
+            force_split_dropdown,
+            new_status_dropdown,
+            orientation_dropdown,
+            mfact_slider,
+            window_count_slider,
+            window_count,
+            highlighted: None,
+        }
+    }
+
+    /// Outline the row of an option, `None` clears it
+    pub fn highlight(&mut self, path: Option<&'static str>, cx: &mut Context<Self>) {
+        self.highlighted = path;
+        cx.notify();
+    }
+
+    fn option_row(&self, option: &str, row: Div, cx: &mut Context<Self>) -> Div {
+        highlight_row(row, self.highlighted == Some(option), cx)
+    }
+
+    fn switch_row(
+        &self,
+        option: &'static str,
+        label: &'static str,
+        description: &'static str,
+        checked: bool,
+        set: fn(&mut LayoutOptions, bool),
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        self.option_row(option, div().h_flex().gap_4().items_center(), cx)
+            .child(with_tooltip(
+                description,
+                div().min_w(px(200.0)).child(label),
+                cx,
+            ))
+            .child(
+                Switch::new(SharedString::from(format!("{}-switch", option)))
+                    .checked(checked)
+                    .on_click(cx.listener(move |view, checked, _, cx| {
+                        set(&mut view.options, *checked);
+                        cx.notify();
+                    })),
+            )
+    }
+
+    fn dropdown_row(
+        &self,
+        option: &'static str,
+        label: &'static str,
+        description: &'static str,
+        dropdown: &Entity<DropdownState<Vec<String>>>,
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        self.option_row(option, div().h_flex().gap_4().items_center(), cx)
+            .child(with_tooltip(
+                description,
+                div().min_w(px(200.0)).child(label),
+                cx,
+            ))
+            .child(Dropdown::new(dropdown).min_w(px(260.0)))
+    }
+
+    /// Monitor drawn with the windows numbered in the order they were opened
+    fn render_preview(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let windows = preview_windows(
+            &self.options,
+            self.window_count,
+            PREVIEW_WIDTH / PREVIEW_HEIGHT,
+        );
+        let gap = 3.0;
+
+        section_container(cx)
+            .child(with_tooltip(
+                "How windows are placed when they are opened one after another, each new window taking focus. Numbers show the order they were opened in.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Preview"),
+                cx,
+            ))
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_start()
+                    .child(
+                        div()
+                            .relative()
+                            .w(px(PREVIEW_WIDTH))
+                            .h(px(PREVIEW_HEIGHT))
+                            .bg(cx.theme().background)
+                            .border_1()
+                            .border_color(cx.theme().border)
+                            .rounded_md()
+                            .overflow_hidden()
+                            .children(windows.into_iter().map(|w| {
+                                div()
+                                    .absolute()
+                                    .left(px(w.x * PREVIEW_WIDTH + gap))
+                                    .top(px(w.y * PREVIEW_HEIGHT + gap))
+                                    .w(px(w.width * PREVIEW_WIDTH - gap * 2.0))
+                                    .h(px(w.height * PREVIEW_HEIGHT - gap * 2.0))
+                                    .flex()
+                                    .items_center()
+                                    .justify_center()
+                                    .bg(rgb(0x3b4252))
+                                    .border_2()
+                                    .border_color(if w.number == self.window_count {
+                                        rgb(0x88c0d0)
+                                    } else {
+                                        rgb(0x4c566a)
+                                    })
+                                    .rounded_md()
+                                    .text_color(rgb(0xeceff4))
+                                    .child(w.number.to_string())
+                            })),
+                    )
+                    .child(
+                        div()
+                            .v_flex()
+                            .flex_1()
+                            .gap_1()
+                            .child(
+                                div()
+                                    .h_flex()
+                                    .gap_4()
+                                    .items_center()
+                                    .child("Windows:")
+                                    .child(
+                                        div()
+                                            .text_size(px(14.0))
+                                            .text_color(cx.theme().muted_foreground)
+                                            .child(self.window_count.to_string()),
+                                    ),
+                            )
+                            .child(
+                                Slider::new(&self.window_count_slider)
+                                    .w_full()
+                                    .text_color(cx.theme().foreground),
+                            ),
+                    ),
+            )
+    }
+
+    fn render_dwindle(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let options = self.options.clone();
+
+        section_container(cx)
+            .child(with_tooltip(
+                "Dwindle splits the focused window in two for every new window, side by side or on top of each other depending on its shape.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Dwindle"),
+                cx,
+            ))
+            .child(self.dropdown_row(
+                "dwindle:force_split",
+                "Force split",
+                "Which half of the split window the new window takes. 0 follows the cursor, the preview assumes it in the bottom right.",
+                &self.force_split_dropdown,
+                cx,
+            ))
+            .child(self.switch_row(
+                "dwindle:preserve_split",
+                "Preserve split",
+                "Keep the split direction when the window is resized or moved instead of picking it from its shape.",
+                options.preserve_split,
+                |o, v| o.preserve_split = v,
+                cx,
+            ))
+            .child(self.switch_row(
+                "dwindle:smart_split",
+                "Smart split",
+                "Split towards the edge of the window the cursor is closest to. Overrides force split.",
+                options.smart_split,
+                |o, v| o.smart_split = v,
+                cx,
+            ))
+            .child(self.switch_row(
+                "dwindle:pseudotile",
+                "Pseudotile",
+                "Let pseudotiled windows keep their floating size inside their tile.",
+                options.pseudotile,
+                |o, v| o.pseudotile = v,
+                cx,
+            ))
+    }
+
+    fn render_master(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        section_container(cx)
+            .child(with_tooltip(
+                "Master keeps one window in the master area and stacks all other windows next to it.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Master"),
+                cx,
+            ))
+            .child(self.dropdown_row(
+                "master:new_status",
+                "New window status",
+                "slave: new windows join the stack. master: new windows take the master area. inherit: new windows get the status of the focused window.",
+                &self.new_status_dropdown,
+                cx,
+            ))
+            .child(self.dropdown_row(
+                "master:orientation",
+                "Orientation",
+                "Where the master area sits. center puts the stack on both sides once there are at least two stacked windows.",
+                &self.orientation_dropdown,
+                cx,
+            ))
+            .child(
+                self.option_row("master:mfact", div().v_flex().gap_1(), cx)
+                    .child(
+                        div()
+                            .h_flex()
+                            .gap_4()
+                            .items_center()
+                            .child(with_tooltip(
+                                "Share of the monitor taken by the master area.",
+                                div().min_w(px(200.0)).child("Master size:"),
+                                cx,
+                            ))
+                            .child(
+                                div()
+                                    .text_size(px(14.0))
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(format!("{:.2}", self.options.mfact)),
+                            ),
+                    )
+                    .child(
+                        Slider::new(&self.mfact_slider)
+                            .w_full()
+                            .text_color(cx.theme().foreground),
+                    ),
+            )
+    }
+}
+
+impl Render for LayoutSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let options = self.options.clone();
+        let master = options.layout == "master";
+        // the section of a highlighted option is shown even if its layout isn't picked
+        let highlighted = self.highlighted.unwrap_or_default();
+        let show_master = master || highlighted.starts_with("master:");
+        let show_dwindle = !master || highlighted.starts_with("dwindle:");
+
+        div()
+            .v_flex()
+            .gap_4()
+            .child(
+                section_container(cx)
+                    .child(with_tooltip(
+                        "Tiling layout used on all workspaces. Workspace rules can pick a different one per workspace.",
+                        div()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(cx.theme().foreground)
+                            .child("Layout"),
+                        cx,
+                    ))
+                    .child(
+                        self.option_row("general:layout", div().h_flex().gap_4().items_center(), cx)
+                            .child(div().min_w(px(200.0)).child("Layout"))
+                            .child(Dropdown::new(&self.layout_dropdown).min_w(px(260.0))),
+                    )
+                    .child(
+                        div()
+                            .h_flex()
+                            .gap_4()
+                            .items_center()
+                            .child(div().min_w(px(200.0)))
+                            .child(
+                                Button::new("apply-layout-settings")
+                                    .label("Apply layout setting")
+                                    .on_click(move |_, _, _cx| {
+                                        let values = [
+                                            ("general:layout", options.layout.clone()),
+                                            ("dwindle:pseudotile", options.pseudotile.to_string()),
+                                            ("dwindle:preserve_split", options.preserve_split.to_string()),
+                                            ("dwindle:smart_split", options.smart_split.to_string()),
+                                            ("dwindle:force_split", options.force_split.to_string()),
+                                            ("master:new_status", options.new_status.clone()),
+                                            ("master:mfact", format!("{:.2}", options.mfact)),
+                                            ("master:orientation", options.orientation.clone()),
+                                        ];
+
+                                        for (path, value) in values {
+                                            if let Err(e) = write_option(path, value) {
+                                                eprintln!("Failed to write override: {}", e);
+                                            }
+                                        }
+                                    }),
+                            ),
+                    ),
+            )
+            .child(self.render_preview(cx))
+            .when(show_master, |this| this.child(self.render_master(cx)))
+            .when(show_dwindle, |this| this.child(self.render_dwindle(cx)))
+    }
+}
@@ -0,0 +1,55 @@
+use gpui::*;
+use gpui_component::StyledExt;
+
+use crate::option_registry::options_in;
+use crate::ui::option_form::OptionForm;
+
+pub struct MiscSettings {
+    misc_form: Entity<OptionForm>,
+    xwayland_form: Entity<OptionForm>,
+}
+
+impl MiscSettings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let misc_form = cx.new(|cx| {
+            OptionForm::new(
+                "Misc",
+                "Options that don't fit anywhere else, from the background logo to window swallowing.",
+                options_in("misc:"),
+                window,
+                cx,
+            )
+        });
+        let xwayland_form = cx.new(|cx| {
+            OptionForm::new(
+                "XWayland",
+                "How X11 apps are run and scaled.",
+                options_in("xwayland:"),
+                window,
+                cx,
+            )
+        });
+
+        Self {
+            misc_form,
+            xwayland_form,
+        }
+    }
+
+    pub fn highlight(&mut self, path: Option<&'static str>, cx: &mut Context<Self>) {
+        self.misc_form
+            .update(cx, |form, cx| form.highlight(path, cx));
+        self.xwayland_form
+            .update(cx, |form, cx| form.highlight(path, cx));
+    }
+}
+
+impl Render for MiscSettings {
+    fn render(&mut self, _window: &mut Window, _cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .v_flex()
+            .gap_4()
+            .child(self.misc_form.clone())
+            .child(self.xwayland_form.clone())
+    }
+}
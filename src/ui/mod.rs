@@ -1,8 +1,32 @@
+pub mod all_options;
+pub mod animation_settings;
+pub mod appearance_settings;
+pub mod autostart_settings;
+pub mod backup_settings;
+pub mod blur_shadow_settings;
+pub mod cursor_settings;
+pub mod env_settings;
+pub mod gesture_settings;
+pub mod keybind_settings;
 pub mod keyboard_settings;
+pub mod layer_rule_settings;
+pub mod layout_settings;
+pub mod misc_settings;
 pub mod monitor_visualizer;
 pub mod mouse_settings;
+pub mod pending_changes;
+pub mod submap_settings;
+pub mod tablet_settings;
+pub mod touchpad_settings;
+pub mod window_rule_settings;
+pub mod workspace_rule_settings;
 
+pub mod colour_picker;
+pub mod command_palette;
+pub mod highlight;
 pub mod item_pill;
+pub mod key_capture;
+pub mod option_form;
 pub mod section_container;
 
 pub mod sidebar;
@@ -9,7 +9,7 @@ use std::process::Command;
 // use crate::setting::{monitor_override, write_override_line};
 use crate::setting_writer::SettingWriter;
 use crate::ui::tooltip::with_tooltip;
-use crate::util::monitor::MonitorInfo;
+use crate::util::monitor::{MonitorInfo, monitor_line_value};
 
 const PADDING: f32 = 40.0;
 const MIN_CANVAS_WIDTH: f32 = 600.0;
@@ -200,7 +200,7 @@ impl MonitorVisualizer {
         }
     }
 
-    fn parse_resolution(resolution: &str) -> (i32, i32) {
+    pub(crate) fn parse_resolution(resolution: &str) -> (i32, i32) {
         if let Some((w, h)) = resolution.split_once('x') {
             (w.parse().unwrap_or(1920), h.parse().unwrap_or(1080))
         } else {
@@ -363,9 +363,10 @@ impl MonitorVisualizer {
             resolution, refresh_rate, position.0, position.1, monitor_name
         );
 
-        let monitor_setting_value = format!(
-            "{},{}@{},{}x{},1",
-            monitor_name, resolution, refresh_rate, position.0, position.1
+        let monitor_setting_value = monitor_line_value(
+            monitor_name,
+            &format!("{}@{}", resolution, refresh_rate),
+            position,
         );
         if let Err(e) = SettingWriter::build_single("monitor=", monitor_setting_value.clone())
             .and_then(|w| w.write())
@@ -373,13 +374,8 @@ impl MonitorVisualizer {
             println!("Failed to write override: {}", e);
         }
 
-        let setting_value = format!(
-            "{},{}@{},{}x{},1",
-            monitor_name, resolution, refresh_rate, position.0, position.1
-        );
-
         match Command::new("hyprctl")
-            .args(["keyword", "monitor", &setting_value])
+            .args(["keyword", "monitor", &monitor_setting_value])
             .output()
         {
             Ok(output) => {
@@ -397,13 +393,14 @@ impl MonitorVisualizer {
     }
 
     fn apply_monitor_setting_immediately(&self, monitor_box: &MonitorBox) {
-        let setting_value = format!(
-            "{},{}@{},{}x{},1",
-            monitor_box.monitor.name,
-            monitor_box.monitor.current_resolution,
-            monitor_box.monitor.current_refresh_rate,
-            monitor_box.monitor.position.0,
-            monitor_box.monitor.position.1
+        let monitor = &monitor_box.monitor;
+        let setting_value = monitor_line_value(
+            &monitor.name,
+            &format!(
+                "{}@{}",
+                monitor.current_resolution, monitor.current_refresh_rate
+            ),
+            monitor.position,
         );
 
         println!("Applying monitor position via hyprctl: {}", setting_value);
@@ -571,13 +568,15 @@ impl Render for MonitorVisualizer {
                                             monitor_box.monitor.position = new_position;
 
                                             // Write the new position to setting file
-                                            let monitor_setting_value = format!(
-                                                "{},{}@{},{}x{},1",
-                                                monitor_box.monitor.name,
-                                                monitor_box.monitor.current_resolution,
-                                                monitor_box.monitor.current_refresh_rate,
-                                                new_position.0,
-                                                new_position.1
+                                            let monitor = &monitor_box.monitor;
+                                            let monitor_setting_value = monitor_line_value(
+                                                &monitor.name,
+                                                &format!(
+                                                    "{}@{}",
+                                                    monitor.current_resolution,
+                                                    monitor.current_refresh_rate
+                                                ),
+                                                new_position,
                                             );
                                             if let Err(e) = SettingWriter::build_single("monitor=", monitor_setting_value.clone())
                                                 .and_then(|w| w.write()) {
@@ -1,18 +1,31 @@
 use gpui::*;
 use gpui_component::ActiveTheme as _;
+use gpui_component::IndexPath;
 use gpui_component::StyledExt;
 use gpui_component::button::Button;
+use gpui_component::dropdown::*;
 use gpui_component::slider::{Slider, SliderEvent, SliderState};
 use gpui_component::switch::Switch;
 
-use crate::setting_writer;
+use crate::option_registry::{choice_value, read_bool, read_number, write_option};
+use crate::ui::highlight::highlight_row;
 use crate::ui::{section_container::section_container, tooltip::with_tooltip};
-use crate::util::mouse::{get_accel_setting, get_current_sensitivity};
+use crate::util::mouse::{
+    FLOAT_SWITCH_MODES, FLOAT_SWITCH_OPTION, FOLLOW_MOUSE_MODES, FOLLOW_MOUSE_OPTION,
+    FORCE_NO_ACCEL_OPTION, HIDE_ON_KEY_PRESS_OPTION, INACTIVE_TIMEOUT_OPTION, MOUSE_REFOCUS_OPTION,
+    NO_WARPS_OPTION, PointerBehaviour, SENSITIVITY_OPTION, get_pointer_behaviour,
+};
 
 pub struct MouseSettings {
     force_no_accel_checked: bool,
     mouse_sensitivity_slider: Entity<SliderState>,
     current_sensitivity: f32,
+    pointer_behaviour: PointerBehaviour,
+    follow_mouse_dropdown: Entity<DropdownState<Vec<String>>>,
+    float_switch_dropdown: Entity<DropdownState<Vec<String>>>,
+    inactive_timeout_slider: Entity<SliderState>,
+    /// Option the command palette jumped to
+    highlighted: Option<&'static str>,
 }
 
 // helper to convert sensitivity to slider value and back
@@ -29,8 +42,8 @@ fn slider_to_sensitivity(slider: f32) -> f32 {
 
 impl MouseSettings {
     pub fn new(window: &mut Window, cx: &mut gpui::Context<Self>) -> Self {
-        let current_sens = get_current_sensitivity().unwrap_or(0.0);
-        let accel_setting = get_accel_setting().unwrap_or(false);
+        let current_sens: f32 = read_number(SENSITIVITY_OPTION);
+        let accel_setting = read_bool(FORCE_NO_ACCEL_OPTION);
 
         println!(
             "DEBUG: Initializing slider with sensitivity: {}",
@@ -63,12 +76,233 @@ impl MouseSettings {
         )
         .detach();
 
+        let pointer_behaviour = get_pointer_behaviour();
+
+        let follow_mouse_dropdown = cx.new(|cx| {
+            DropdownState::new(
+                FOLLOW_MOUSE_MODES.map(String::from).to_vec(),
+                FOLLOW_MOUSE_MODES
+                    .get(pointer_behaviour.follow_mouse as usize)
+                    .map(|_| IndexPath::new(pointer_behaviour.follow_mouse as usize)),
+                window,
+                cx,
+            )
+        });
+
+        let float_switch_dropdown = cx.new(|cx| {
+            DropdownState::new(
+                FLOAT_SWITCH_MODES.map(String::from).to_vec(),
+                FLOAT_SWITCH_MODES
+                    .get(pointer_behaviour.float_switch_override_focus as usize)
+                    .map(|_| {
+                        IndexPath::new(pointer_behaviour.float_switch_override_focus as usize)
+                    }),
+                window,
+                cx,
+            )
+        });
+
+        cx.subscribe(
+            &follow_mouse_dropdown,
+            |this, _, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(label)) = event
+                    && let Ok(mode) = choice_value(label).parse()
+                {
+                    this.pointer_behaviour.follow_mouse = mode;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        cx.subscribe(
+            &float_switch_dropdown,
+            |this, _, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(label)) = event
+                    && let Ok(mode) = choice_value(label).parse()
+                {
+                    this.pointer_behaviour.float_switch_override_focus = mode;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        let inactive_timeout_slider = cx.new(|_cx| SliderState::new().min(0.0).max(30.0).step(1.0));
+
+        inactive_timeout_slider.update(cx, |state, cx| {
+            state.set_value(pointer_behaviour.inactive_timeout, window, cx);
+        });
+
+        cx.subscribe(
+            &inactive_timeout_slider,
+            |this, _, event: &SliderEvent, cx| match event {
+                SliderEvent::Change(value) => {
+                    this.pointer_behaviour.inactive_timeout = value.start();
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
         Self {
             mouse_sensitivity_slider,
             current_sensitivity: current_sens,
             force_no_accel_checked: accel_setting,
+            pointer_behaviour,
+            follow_mouse_dropdown,
+            float_switch_dropdown,
+            inactive_timeout_slider,
+            highlighted: None,
         }
     }
+
+    /// Outline the row of an option, `None` clears it
+    pub fn highlight(&mut self, path: Option<&'static str>, cx: &mut Context<Self>) {
+        self.highlighted = path;
+        cx.notify();
+    }
+
+    fn option_row(&self, option: &str, row: Div, cx: &mut Context<Self>) -> Div {
+        highlight_row(row, self.highlighted == Some(option), cx)
+    }
+
+    fn switch_row(
+        &self,
+        option: &'static str,
+        label: &'static str,
+        description: &'static str,
+        checked: bool,
+        set: fn(&mut PointerBehaviour, bool),
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        self.option_row(option, div().h_flex().gap_4().items_center(), cx)
+            .child(with_tooltip(
+                description,
+                div().min_w(px(200.0)).child(label),
+                cx,
+            ))
+            .child(
+                Switch::new(SharedString::from(format!("{}-switch", option)))
+                    .checked(checked)
+                    .on_click(cx.listener(move |view, checked, _, cx| {
+                        set(&mut view.pointer_behaviour, *checked);
+                        cx.notify();
+                    })),
+            )
+    }
+
+    fn render_pointer_behaviour(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let pointer = self.pointer_behaviour.clone();
+
+        section_container(cx)
+            .child(with_tooltip(
+                "Controls how keyboard focus follows the pointer and when the cursor is hidden or moved for you.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Pointer behaviour".to_string()),
+                cx,
+            ))
+            .child(
+                self.option_row(FOLLOW_MOUSE_OPTION, div().h_flex().gap_4().items_center(), cx)
+                    .child(with_tooltip(
+                        "0: focus only changes on click. 1: focus always follows the cursor. 2: the cursor can scroll or hover unfocused windows but keyboard focus needs a click. 3: keyboard focus is fully separate from the cursor.",
+                        div().min_w(px(200.0)).child("Follow mouse"),
+                        cx,
+                    ))
+                    .child(Dropdown::new(&self.follow_mouse_dropdown).min_w(px(260.0))),
+            )
+            .child(self.switch_row(
+                MOUSE_REFOCUS_OPTION,
+                "Mouse refocus",
+                "When focus follows the mouse, refocus the window under the cursor even if the cursor has not crossed a window border.",
+                pointer.mouse_refocus,
+                |p, v| p.mouse_refocus = v,
+                cx,
+            ))
+            .child(
+                self.option_row(FLOAT_SWITCH_OPTION, div().h_flex().gap_4().items_center(), cx)
+                    .child(with_tooltip(
+                        "Whether moving the cursor between tiled and floating windows changes focus. 2 also changes focus between two floating windows.",
+                        div().min_w(px(200.0)).child("Floating focus switch"),
+                        cx,
+                    ))
+                    .child(Dropdown::new(&self.float_switch_dropdown).min_w(px(260.0))),
+            )
+            .child(
+                self.option_row(INACTIVE_TIMEOUT_OPTION, div().v_flex().gap_1(), cx)
+                    .child(
+                        div()
+                            .h_flex()
+                            .gap_4()
+                            .items_center()
+                            .child(with_tooltip(
+                                "Hide the cursor after this many seconds without movement. 0 never hides it.",
+                                div().min_w(px(200.0)).child("Hide after inactivity:"),
+                                cx,
+                            ))
+                            .child(
+                                div()
+                                    .text_size(px(14.0))
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(if pointer.inactive_timeout > 0.0 {
+                                        format!("{:.0}s", pointer.inactive_timeout)
+                                    } else {
+                                        "Never".to_string()
+                                    }),
+                            ),
+                    )
+                    .child(
+                        Slider::new(&self.inactive_timeout_slider)
+                            .w_full()
+                            .text_color(cx.theme().foreground),
+                    ),
+            )
+            .child(self.switch_row(
+                NO_WARPS_OPTION,
+                "No cursor warps",
+                "Never move the cursor automatically, for example when focus is changed with the keyboard.",
+                pointer.no_warps,
+                |p, v| p.no_warps = v,
+                cx,
+            ))
+            .child(self.switch_row(
+                HIDE_ON_KEY_PRESS_OPTION,
+                "Hide while typing",
+                "Hide the cursor on key presses until the mouse is moved again.",
+                pointer.hide_on_key_press,
+                |p, v| p.hide_on_key_press = v,
+                cx,
+            ))
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(200.0)))
+                    .child(
+                        Button::new("apply-pointer-behaviour")
+                            .label("Apply pointer behaviour")
+                            .on_click(move |_, _, _cx| {
+                                let values = [
+                                    (FOLLOW_MOUSE_OPTION, pointer.follow_mouse.to_string()),
+                                    (MOUSE_REFOCUS_OPTION, pointer.mouse_refocus.to_string()),
+                                    (FLOAT_SWITCH_OPTION, pointer.float_switch_override_focus.to_string()),
+                                    (INACTIVE_TIMEOUT_OPTION, format!("{:.0}", pointer.inactive_timeout)),
+                                    (NO_WARPS_OPTION, pointer.no_warps.to_string()),
+                                    (HIDE_ON_KEY_PRESS_OPTION, pointer.hide_on_key_press.to_string()),
+                                ];
+
+                                for (path, value) in values {
+                                    if let Err(e) = write_option(path, value) {
+                                        eprintln!("Failed to write override: {}", e);
+                                    }
+                                }
+                            }),
+                    ),
+            )
+    }
 }
 
 impl Render for MouseSettings {
@@ -77,7 +311,7 @@ impl Render for MouseSettings {
         let current_sens = slider_to_sensitivity(slider_raw);
         let accel_setting = self.force_no_accel_checked;
 
-        section_container(cx)
+        let mouse_settings = section_container(cx)
             .min_h(px(200.0))
             .child(
                 with_tooltip(
@@ -90,9 +324,7 @@ impl Render for MouseSettings {
                 )
             )
             .child(
-                div()
-                    .v_flex()
-                    .gap_1()
+                self.option_row(SENSITIVITY_OPTION, div().v_flex().gap_1(), cx)
                     .child(
                         div()
                             .h_flex()
@@ -132,10 +364,7 @@ impl Render for MouseSettings {
                     ),
             )
             .child(
-                div()
-                    .h_flex()
-                    .gap_4()
-                    .items_center()
+                self.option_row(FORCE_NO_ACCEL_OPTION, div().h_flex().gap_4().items_center(), cx)
                     .child("Acceleration".to_string())
                     .child(
                         Switch::new("force-no-accel-switch")
@@ -156,10 +385,24 @@ impl Render for MouseSettings {
                         Button::new("apply-mouse-settings")
                             .label("Apply mouse setting")
                             .on_click(move |_, _, _cx| {
-                                setting_writer::SettingWriter::build_single("input:sensitivity=", current_sens).and_then(|w| w.write()).unwrap();
-                                setting_writer::SettingWriter::build_single("input:force_no_accel=", accel_setting).and_then(|w| w.write()).unwrap();
+                                let values = [
+                                    (SENSITIVITY_OPTION, format!("{:.2}", current_sens)),
+                                    (FORCE_NO_ACCEL_OPTION, accel_setting.to_string()),
+                                ];
+
+                                for (path, value) in values {
+                                    if let Err(e) = write_option(path, value) {
+                                        eprintln!("Failed to write override: {}", e);
+                                    }
+                                }
                             }),
                     ),
-            )
+            );
+
+        div()
+            .v_flex()
+            .gap_4()
+            .child(mouse_settings)
+            .child(self.render_pointer_behaviour(cx))
     }
 }
@@ -0,0 +1,372 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::IndexPath;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::dropdown::*;
+use gpui_component::input::{InputEvent, InputState, TextInput};
+use gpui_component::slider::{Slider, SliderEvent, SliderState};
+use gpui_component::switch::Switch;
+
+use crate::option_registry::{OptionSpec, OptionType, choice_value, parse_bool, test_regex};
+use crate::setting_writer;
+use crate::ui::highlight::highlight_row;
+use crate::ui::item_pill::item_pill;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::option::get_hyprland_version;
+
+/// Control of a single option, picked from its type
+enum Control {
+    Switch,
+    Slider(Entity<SliderState>),
+    Dropdown(Entity<DropdownState<Vec<String>>>),
+    Text(Entity<InputState>),
+    Regex {
+        input: Entity<InputState>,
+        test_input: Entity<InputState>,
+    },
+}
+
+/// Form editing a list of registered options, each with the control matching its type
+pub struct OptionForm {
+    title: &'static str,
+    description: &'static str,
+    options: Vec<&'static OptionSpec>,
+    values: Vec<String>,
+    controls: Vec<Control>,
+    version: Option<String>,
+    error: Option<String>,
+    /// Option the command palette jumped to
+    highlighted: Option<&'static str>,
+}
+
+impl OptionForm {
+    pub fn new(
+        title: &'static str,
+        description: &'static str,
+        options: Vec<&'static OptionSpec>,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Self {
+        let values: Vec<String> = options.iter().map(|option| option.read()).collect();
+        let controls = options
+            .iter()
+            .enumerate()
+            .map(|(idx, option)| Self::control(idx, option, &values[idx], window, cx))
+            .collect();
+
+        Self {
+            title,
+            description,
+            options,
+            values,
+            controls,
+            version: get_hyprland_version().ok(),
+            error: None,
+            highlighted: None,
+        }
+    }
+
+    /// Outline the row of an option, `None` clears it
+    pub fn highlight(&mut self, path: Option<&'static str>, cx: &mut Context<Self>) {
+        self.highlighted = path.filter(|path| self.options.iter().any(|o| o.path == *path));
+        cx.notify();
+    }
+
+    fn control(
+        idx: usize,
+        option: &OptionSpec,
+        value: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Control {
+        match option.option_type {
+            OptionType::Bool => Control::Switch,
+            OptionType::Int { min, max } => {
+                Self::slider(idx, min as f32, max as f32, 1.0, value, window, cx)
+            }
+            OptionType::Float { min, max, step } => {
+                Self::slider(idx, min, max, step, value, window, cx)
+            }
+            OptionType::Choice(choices) => {
+                let selected = choices
+                    .iter()
+                    .position(|choice| choice_value(choice) == value)
+                    .map(IndexPath::new);
+                let dropdown = cx.new(|cx| {
+                    DropdownState::new(
+                        choices.iter().map(|c| c.to_string()).collect(),
+                        selected,
+                        window,
+                        cx,
+                    )
+                });
+
+                cx.subscribe(
+                    &dropdown,
+                    move |this, _, event: &DropdownEvent<Vec<String>>, cx| {
+                        if let DropdownEvent::Confirm(Some(label)) = event {
+                            this.values[idx] = choice_value(label).to_string();
+                            cx.notify();
+                        }
+                    },
+                )
+                .detach();
+
+                Control::Dropdown(dropdown)
+            }
+            OptionType::Text | OptionType::Number { .. } => {
+                Control::Text(Self::input(idx, option.default, value, window, cx))
+            }
+            OptionType::Regex => {
+                let input = Self::input(idx, "Regex", value, window, cx);
+                let test_input =
+                    cx.new(|cx| InputState::new(window, cx).placeholder("Test a value"));
+
+                cx.subscribe(&test_input, |_, _, event: &InputEvent, cx| {
+                    if let InputEvent::Change = event {
+                        cx.notify();
+                    }
+                })
+                .detach();
+
+                Control::Regex { input, test_input }
+            }
+        }
+    }
+
+    fn slider(
+        idx: usize,
+        min: f32,
+        max: f32,
+        step: f32,
+        value: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Control {
+        let slider = cx.new(|_cx| SliderState::new().min(min).max(max).step(step));
+        slider.update(cx, |state, cx| {
+            state.set_value(value.parse().unwrap_or(min), window, cx);
+        });
+
+        cx.subscribe(
+            &slider,
+            move |this, _, event: &SliderEvent, cx| match event {
+                SliderEvent::Change(value) => {
+                    this.values[idx] = if step.fract() == 0.0 {
+                        format!("{:.0}", value.start())
+                    } else {
+                        format!("{:.2}", value.start())
+                    };
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        Control::Slider(slider)
+    }
+
+    fn input(
+        idx: usize,
+        placeholder: &str,
+        value: &str,
+        window: &mut Window,
+        cx: &mut Context<Self>,
+    ) -> Entity<InputState> {
+        let placeholder = placeholder.to_string();
+        let value = value.to_string();
+        let input = cx.new(|cx| {
+            let mut state = InputState::new(window, cx).placeholder(placeholder);
+            state.set_value(value, window, cx);
+            state
+        });
+
+        cx.subscribe(&input, move |this, input, event: &InputEvent, cx| {
+            if let InputEvent::Change = event {
+                this.values[idx] = input.read(cx).value().to_string();
+                cx.notify();
+            }
+        })
+        .detach();
+
+        input
+    }
+
+    fn supported(&self, option: &OptionSpec) -> bool {
+        option.supported(self.version.as_deref())
+    }
+
+    /// Write every option the running Hyprland knows, nothing is written if a value is invalid
+    fn apply(&mut self, cx: &mut Context<Self>) {
+        self.error = self
+            .options
+            .iter()
+            .zip(&self.values)
+            .find_map(|(option, value)| option.validate(value).err())
+            .map(|e| e.to_string());
+
+        if self.error.is_none() {
+            for (option, value) in self.options.iter().zip(&self.values) {
+                if !self.supported(option) {
+                    continue;
+                }
+                if let Err(e) = setting_writer::SettingWriter::build_single(&option.prefix(), value)
+                    .and_then(|w| w.write())
+                {
+                    self.error = Some(format!("Failed to write {}: {}", option.path, e));
+                }
+            }
+        }
+        cx.notify();
+    }
+
+    fn render_control(&self, idx: usize, cx: &mut Context<Self>) -> AnyElement {
+        let option = self.options[idx];
+        let value = self.values[idx].clone();
+
+        match &self.controls[idx] {
+            Control::Switch => {
+                Switch::new(SharedString::from(format!("option-{}-switch", option.path)))
+                    .checked(parse_bool(&value).unwrap_or(false))
+                    .on_click(cx.listener(move |view, checked: &bool, _, cx| {
+                        view.values[idx] = checked.to_string();
+                        cx.notify();
+                    }))
+                    .into_any_element()
+            }
+            Control::Slider(slider) => div()
+                .h_flex()
+                .flex_1()
+                .gap_4()
+                .items_center()
+                .child(
+                    div()
+                        .min_w(px(40.0))
+                        .text_size(px(14.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child(value),
+                )
+                .child(
+                    Slider::new(slider)
+                        .w_full()
+                        .text_color(cx.theme().foreground),
+                )
+                .into_any_element(),
+            Control::Dropdown(dropdown) => {
+                Dropdown::new(dropdown).min_w(px(260.0)).into_any_element()
+            }
+            Control::Text(input) => div()
+                .flex_1()
+                .child(TextInput::new(input))
+                .into_any_element(),
+            Control::Regex { input, test_input } => {
+                let test = test_input.read(cx).value().to_string();
+                let result = test_regex(&value, &test);
+
+                div()
+                    .v_flex()
+                    .flex_1()
+                    .gap_1()
+                    .child(
+                        div()
+                            .h_flex()
+                            .gap_2()
+                            .items_center()
+                            .child(div().flex_1().child(TextInput::new(input)))
+                            .child(div().w(px(200.0)).child(TextInput::new(test_input))),
+                    )
+                    .child(
+                        div()
+                            .text_size(px(12.0))
+                            .text_color(if result.is_err() {
+                                cx.theme().danger
+                            } else {
+                                cx.theme().muted_foreground
+                            })
+                            .child(match result {
+                                Err(e) => e.to_string(),
+                                Ok(_) if test.is_empty() => {
+                                    "Enter a value to test the regex".to_string()
+                                }
+                                Ok(true) => format!("{} matches", test),
+                                Ok(false) => format!("{} does not match", test),
+                            }),
+                    )
+                    .into_any_element()
+            }
+        }
+    }
+
+    fn render_row(&self, idx: usize, cx: &mut Context<Self>) -> Div {
+        let option = self.options[idx];
+
+        let row = div().h_flex().gap_4().items_center();
+
+        highlight_row(row, self.highlighted == Some(option.path), cx)
+            .child(with_tooltip(
+                option.description,
+                div().min_w(px(200.0)).child(option.label()),
+                cx,
+            ))
+            .child(self.render_control(idx, cx))
+            .when_some(
+                option.since.filter(|_| !self.supported(option)),
+                |this, since| {
+                    this.child(with_tooltip(
+                        format!(
+                            "Running Hyprland {}, this option is not written until you update.",
+                            self.version.clone().unwrap_or_default()
+                        ),
+                        item_pill(cx)
+                            .text_size(px(10.0))
+                            .text_color(cx.theme().danger)
+                            .child(format!("Hyprland {}+", since)),
+                        cx,
+                    ))
+                },
+            )
+    }
+}
+
+impl Render for OptionForm {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let rows: Vec<_> = (0..self.options.len())
+            .map(|idx| self.render_row(idx, cx))
+            .collect();
+
+        section_container(cx)
+            .child(with_tooltip(
+                self.description,
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child(self.title),
+                cx,
+            ))
+            .children(rows)
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(200.0)))
+                    .child(
+                        Button::new(SharedString::from(format!("apply-{}", self.title)))
+                            .label(format!("Apply {} setting", self.title.to_lowercase()))
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.apply(cx);
+                            })),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(cx.theme().danger)
+                        .child(error),
+                )
+            })
+    }
+}
@@ -0,0 +1,337 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::Disableable;
+use gpui_component::StyledExt;
+use gpui_component::button::{Button, ButtonVariants as _};
+
+use crate::change_set;
+use crate::history::{self, HistoryEntry};
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::config_tree::display_path;
+use crate::util::diff::DiffLine;
+
+actions!(history, [UndoChange, RedoChange]);
+
+/// Text inputs keep their own Ctrl+Z, these only apply while no input is focused
+pub fn init(cx: &mut App) {
+    cx.bind_keys([
+        KeyBinding::new("ctrl-z", UndoChange, None),
+        KeyBinding::new("ctrl-shift-z", RedoChange, None),
+        KeyBinding::new("ctrl-y", RedoChange, None),
+    ]);
+}
+
+/// Edits staged by every page, with a diff of the overrides file and one button writing them all
+pub struct PendingChanges {
+    status: Option<String>,
+    error: Option<String>,
+    /// Diff of the overrides file and the change set revision it was computed for
+    diff: Vec<DiffLine>,
+    diff_revision: Option<u64>,
+}
+
+impl PendingChanges {
+    pub fn new(_window: &mut Window, _cx: &mut Context<Self>) -> Self {
+        Self {
+            status: None,
+            error: None,
+            diff: Vec::new(),
+            diff_revision: None,
+        }
+    }
+
+    /// Diff again once the change set changed, other pages stage changes while this one is hidden
+    fn refresh_diff(&mut self) {
+        let revision = change_set::revision();
+        if self.diff_revision == Some(revision) {
+            return;
+        }
+
+        self.diff = change_set::diff().unwrap_or_else(|e| {
+            eprintln!("Failed to diff the overrides file: {}", e);
+            Vec::new()
+        });
+        self.diff_revision = Some(revision);
+    }
+
+    fn apply(&mut self, cx: &mut Context<Self>) {
+        match change_set::apply() {
+            Ok(count) => {
+                self.status = Some(format!("Wrote {} changes to the overrides file", count));
+                self.error = None;
+            }
+            Err(e) => {
+                eprintln!("Failed to write pending changes: {}", e);
+                self.error = Some(e.to_string());
+            }
+        }
+        cx.notify();
+    }
+
+    /// Revert the last saved change, also bound to Ctrl+Z
+    pub fn undo(&mut self, cx: &mut Context<Self>) {
+        self.report(history::undo(), "Undid", cx);
+    }
+
+    /// Save the last undone change again, also bound to Ctrl+Shift+Z and Ctrl+Y
+    pub fn redo(&mut self, cx: &mut Context<Self>) {
+        self.report(history::redo(), "Redid", cx);
+    }
+
+    fn report(
+        &mut self,
+        result: anyhow::Result<Option<String>>,
+        verb: &str,
+        cx: &mut Context<Self>,
+    ) {
+        match result {
+            Ok(Some(summary)) => {
+                self.status = Some(format!("{} {}", verb, summary));
+                self.error = None;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                eprintln!("{}", e);
+                self.error = Some(e.to_string());
+            }
+        }
+        // undo and redo rewrite the file on disk without touching the change set
+        self.diff_revision = None;
+        cx.notify();
+    }
+
+    fn discard(&mut self, id: Option<usize>, cx: &mut Context<Self>) {
+        match id {
+            Some(id) => change_set::discard(id),
+            None => change_set::discard_all(),
+        }
+        self.status = None;
+        cx.notify();
+    }
+
+    fn render_changes(&self, changes: &[(usize, String)], cx: &mut Context<Self>) -> Div {
+        section_container(cx)
+            .child(with_tooltip(
+                "Edits made on every page since the last save, in order. Values previewed live stay applied until Hyprland reloads, even when their change is discarded.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Pending changes"),
+                cx,
+            ))
+            .when(changes.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_size(px(14.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Nothing to save"),
+                )
+            })
+            .children(changes.iter().map(|(id, summary)| {
+                let id = *id;
+
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .py_1()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_color(cx.theme().foreground)
+                            .child(summary.clone()),
+                    )
+                    .child(
+                        Button::new(("discard-change", id))
+                            .label("Discard")
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.discard(Some(id), cx);
+                            })),
+                    )
+            }))
+            .child(
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("apply-pending-changes")
+                            .primary()
+                            .label("Save all")
+                            .disabled(changes.is_empty())
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.apply(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("discard-pending-changes")
+                            .label("Discard all")
+                            .disabled(changes.is_empty())
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.discard(None, cx);
+                            })),
+                    ),
+            )
+            .when_some(self.status.clone(), |this, status| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child(status),
+                )
+            })
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(cx.theme().danger)
+                        .child(error),
+                )
+            })
+    }
+
+    fn render_diff(&self, cx: &mut Context<Self>) -> Div {
+        let diff = &self.diff;
+
+        section_container(cx)
+            .child(with_tooltip(
+                "conf-overrides.conf as it is on disk against how it will look once the pending changes are saved.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Diff"),
+                cx,
+            ))
+            .when(diff.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_size(px(14.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child("The overrides file is unchanged"),
+                )
+            })
+            .child(
+                div()
+                    .v_flex()
+                    .font_family("monospace")
+                    .text_size(px(12.0))
+                    .children(diff.iter().map(|line| {
+                        let (text, color) = match line {
+                            DiffLine::Hunk(text) => (text.clone(), cx.theme().muted_foreground),
+                            DiffLine::Context(text) => (format!("  {}", text), cx.theme().foreground),
+                            DiffLine::Added(text) => (format!("+ {}", text), cx.theme().success),
+                            DiffLine::Removed(text) => (format!("- {}", text), cx.theme().danger),
+                        };
+                        div().text_color(color).child(text)
+                    })),
+            )
+    }
+
+    fn render_entry(&self, entry: &HistoryEntry, cx: &mut Context<Self>) -> Div {
+        let lines = entry
+            .old_lines
+            .iter()
+            .map(|line| (format!("- {}", line), cx.theme().danger))
+            .chain(
+                entry
+                    .new_lines
+                    .iter()
+                    .map(|line| (format!("+ {}", line), cx.theme().success)),
+            )
+            .map(|(line, color)| div().text_color(color).child(line));
+
+        div()
+            .v_flex()
+            .py_1()
+            .child(
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_color(cx.theme().foreground)
+                            .child(entry.summary.clone()),
+                    )
+                    .child(
+                        div()
+                            .text_size(px(12.0))
+                            .text_color(cx.theme().muted_foreground)
+                            .child(format!("{} · {}", display_path(&entry.file), entry.age())),
+                    ),
+            )
+            .child(
+                div()
+                    .v_flex()
+                    .font_family("monospace")
+                    .text_size(px(12.0))
+                    .children(lines),
+            )
+    }
+
+    fn render_history(&self, cx: &mut Context<Self>) -> Div {
+        let undo = history::undo_entries();
+        let redo = history::redo_entries();
+        let entries: Vec<Div> = undo
+            .iter()
+            .map(|entry| self.render_entry(entry, cx))
+            .collect();
+
+        section_container(cx)
+            .child(with_tooltip(
+                "Saved changes, newest first. Undo writes back what a change replaced and applies the old values right away where Hyprland allows it.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("History"),
+                cx,
+            ))
+            .child(
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .child(
+                        Button::new("undo-change")
+                            .label("Undo")
+                            .disabled(undo.is_empty())
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.undo(cx);
+                            })),
+                    )
+                    .child(
+                        Button::new("redo-change")
+                            .label(match redo.len() {
+                                0 => "Redo".to_string(),
+                                count => format!("Redo ({})", count),
+                            })
+                            .disabled(redo.is_empty())
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.redo(cx);
+                            })),
+                    ),
+            )
+            .when(entries.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_size(px(14.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child("Nothing saved yet"),
+                )
+            })
+            .children(entries)
+    }
+}
+
+impl Render for PendingChanges {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let changes = change_set::pending_changes();
+        self.refresh_diff();
+
+        div()
+            .v_flex()
+            .gap_4()
+            .child(self.render_changes(&changes, cx))
+            .child(self.render_diff(cx))
+            .child(self.render_history(cx))
+    }
+}
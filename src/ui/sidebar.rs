@@ -41,6 +41,152 @@ pub fn create_sidebar(
                             .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
                                 view.set_active_section(ActiveSection::Mouse, cx);
                             })),
+                    )
+                    .child(
+                        SidebarMenuItem::new("Touchpad")
+                            .active(active_section == ActiveSection::Touchpad)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::Touchpad, cx);
+                            })),
+                    )
+                    .child(
+                        SidebarMenuItem::new("Tablet & touch")
+                            .active(active_section == ActiveSection::Tablet)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::Tablet, cx);
+                            })),
+                    )
+                    .child(
+                        SidebarMenuItem::new("Cursor")
+                            .active(active_section == ActiveSection::Cursor)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::Cursor, cx);
+                            })),
+                    ),
+            ),
+        )
+        .child(
+            SidebarGroup::new("Appearance").child(
+                SidebarMenu::new()
+                    .child(
+                        SidebarMenuItem::new("Windows")
+                            .active(active_section == ActiveSection::Appearance)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::Appearance, cx);
+                            })),
+                    )
+                    .child(
+                        SidebarMenuItem::new("Blur & shadow")
+                            .active(active_section == ActiveSection::BlurShadow)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::BlurShadow, cx);
+                            })),
+                    )
+                    .child(
+                        SidebarMenuItem::new("Animations")
+                            .active(active_section == ActiveSection::Animations)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::Animations, cx);
+                            })),
+                    )
+                    .child(
+                        SidebarMenuItem::new("Layouts")
+                            .active(active_section == ActiveSection::Layouts)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::Layouts, cx);
+                            })),
+                    ),
+            ),
+        )
+        .child(
+            SidebarGroup::new("Keybinds").child(
+                SidebarMenu::new()
+                    .child(
+                        SidebarMenuItem::new("Binds")
+                            .active(active_section == ActiveSection::Keybinds)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::Keybinds, cx);
+                            })),
+                    )
+                    .child(
+                        SidebarMenuItem::new("Submaps")
+                            .active(active_section == ActiveSection::Submaps)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::Submaps, cx);
+                            })),
+                    ),
+            ),
+        )
+        .child(
+            SidebarGroup::new("Rules").child(
+                SidebarMenu::new()
+                    .child(
+                        SidebarMenuItem::new("Window Rules")
+                            .active(active_section == ActiveSection::WindowRules)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::WindowRules, cx);
+                            })),
+                    )
+                    .child(
+                        SidebarMenuItem::new("Layer Rules")
+                            .active(active_section == ActiveSection::LayerRules)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::LayerRules, cx);
+                            })),
+                    )
+                    .child(
+                        SidebarMenuItem::new("Workspace Rules")
+                            .active(active_section == ActiveSection::WorkspaceRules)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::WorkspaceRules, cx);
+                            })),
+                    ),
+            ),
+        )
+        .child(
+            SidebarGroup::new("System").child(
+                SidebarMenu::new()
+                    .child(
+                        SidebarMenuItem::new("Autostart")
+                            .active(active_section == ActiveSection::Autostart)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::Autostart, cx);
+                            })),
+                    )
+                    .child(
+                        SidebarMenuItem::new("Environment")
+                            .active(active_section == ActiveSection::Environment)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::Environment, cx);
+                            })),
+                    )
+                    .child(
+                        SidebarMenuItem::new("Misc & XWayland")
+                            .active(active_section == ActiveSection::Misc)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::Misc, cx);
+                            })),
+                    )
+                    .child(
+                        SidebarMenuItem::new("All options")
+                            .active(active_section == ActiveSection::AllOptions)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::AllOptions, cx);
+                            })),
+                    )
+                    .child(
+                        SidebarMenuItem::new("Pending changes")
+                            .active(active_section == ActiveSection::PendingChanges)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::PendingChanges, cx);
+                            })),
+                    )
+                    .child(
+                        SidebarMenuItem::new("Backups")
+                            .active(active_section == ActiveSection::Backups)
+                            .on_click(cx.listener(|view: &mut Hyprsetting, _, _, cx| {
+                                view.set_active_section(ActiveSection::Backups, cx);
+                            })),
                     ),
             ),
         )
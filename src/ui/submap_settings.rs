@@ -0,0 +1,505 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::dropdown::*;
+use gpui_component::input::{InputState, TextInput};
+use gpui_component::switch::Switch;
+
+use crate::setting_writer::SettingWriter;
+use crate::ui::item_pill::item_pill;
+use crate::ui::key_capture::{KeyCapture, KeyCaptureEvent};
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::dispatchers::{DISPATCHERS, validate_args};
+use crate::util::keybinds::{Keybind, Submap, get_keybinds, get_submaps, replace_bind};
+
+pub struct SubmapSettings {
+    submaps: Vec<Submap>,
+    /// Each submap as it was loaded or last saved, its binds are unbound when saving
+    saved: Vec<Submap>,
+    selected: Option<usize>,
+    name_input: Entity<InputState>,
+    entry_capture: Entity<KeyCapture>,
+    exit_capture: Entity<KeyCapture>,
+    bind_capture: Entity<KeyCapture>,
+    /// Bind being added to the selected submap
+    draft: Keybind,
+    dispatcher_dropdown: Entity<DropdownState<SearchableVec<String>>>,
+    arg_input: Entity<InputState>,
+    error: Option<String>,
+}
+
+fn display_combo(bind: &Keybind) -> String {
+    bind.combo().replace('+', " + ")
+}
+
+fn key_row(
+    label: &'static str,
+    description: &'static str,
+    capture: &Entity<KeyCapture>,
+    cx: &mut App,
+) -> impl IntoElement {
+    div()
+        .h_flex()
+        .gap_4()
+        .items_center()
+        .child(with_tooltip(
+            description,
+            div().min_w(px(120.0)).child(label),
+            cx,
+        ))
+        .child(capture.clone())
+}
+
+impl SubmapSettings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let binds = get_keybinds().unwrap_or_else(|e| {
+            eprintln!("Failed to get binds: {}", e);
+            Vec::new()
+        });
+        let submaps = get_submaps(&binds);
+
+        let name_input = cx.new(|cx| InputState::new(window, cx).placeholder("Submap name"));
+        let entry_capture = cx.new(|cx| KeyCapture::new("Key entering the submap", cx));
+        let exit_capture = cx.new(|cx| KeyCapture::new("Key leaving the submap", cx));
+        let bind_capture = cx.new(|cx| KeyCapture::new("Key inside the submap", cx));
+        let dispatcher_dropdown = cx.new(|cx| {
+            DropdownState::new(
+                SearchableVec::new(
+                    DISPATCHERS
+                        .iter()
+                        .map(|d| d.name.to_string())
+                        .collect::<Vec<_>>(),
+                ),
+                None,
+                window,
+                cx,
+            )
+        });
+        let arg_input = cx.new(|cx| InputState::new(window, cx).placeholder("Arguments"));
+
+        cx.subscribe(&entry_capture, |this, _, event: &KeyCaptureEvent, cx| {
+            let KeyCaptureEvent::Change { modmask, key } = event;
+            if let Some(submap) = this.selected_submap_mut() {
+                let name = submap.name.clone();
+                submap.entry = Some(Keybind::new(*modmask, key.clone(), "submap", &name));
+            }
+            cx.notify();
+        })
+        .detach();
+
+        cx.subscribe(&exit_capture, |this, _, event: &KeyCaptureEvent, cx| {
+            let KeyCaptureEvent::Change { modmask, key } = event;
+            if let Some(submap) = this.selected_submap_mut() {
+                submap.exit = Some(Keybind::new(*modmask, key.clone(), "submap", "reset"));
+            }
+            cx.notify();
+        })
+        .detach();
+
+        cx.subscribe(&bind_capture, |this, _, event: &KeyCaptureEvent, cx| {
+            let KeyCaptureEvent::Change { modmask, key } = event;
+            this.draft.modmask = *modmask;
+            this.draft.key = key.clone();
+            this.error = None;
+            cx.notify();
+        })
+        .detach();
+
+        cx.subscribe(
+            &dispatcher_dropdown,
+            |this, _, event: &DropdownEvent<SearchableVec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(dispatcher)) = event {
+                    this.draft.dispatcher = dispatcher.clone();
+                    this.error = None;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        let mut settings = Self {
+            saved: submaps.clone(),
+            submaps,
+            selected: None,
+            name_input,
+            entry_capture,
+            exit_capture,
+            bind_capture,
+            draft: Keybind::default(),
+            dispatcher_dropdown,
+            arg_input,
+            error: None,
+        };
+        settings.select(0, cx);
+        settings
+    }
+
+    fn selected_submap_mut(&mut self) -> Option<&mut Submap> {
+        self.selected.and_then(|idx| self.submaps.get_mut(idx))
+    }
+
+    /// Show the entry and exit keys of a submap in the editor
+    fn select(&mut self, idx: usize, cx: &mut Context<Self>) {
+        let Some(submap) = self.submaps.get(idx) else {
+            self.selected = None;
+            return;
+        };
+        self.selected = Some(idx);
+        self.error = None;
+
+        let combo = |bind: &Option<Keybind>| {
+            bind.as_ref()
+                .map(|b| (b.modmask, b.key_name()))
+                .unwrap_or_default()
+        };
+        let (entry_mods, entry_key) = combo(&submap.entry);
+        let (exit_mods, exit_key) = combo(&submap.exit);
+
+        self.entry_capture.update(cx, |capture, cx| {
+            capture.set_combo(entry_mods, entry_key, cx);
+        });
+        self.exit_capture.update(cx, |capture, cx| {
+            capture.set_combo(exit_mods, exit_key, cx);
+        });
+        cx.notify();
+    }
+
+    fn new_submap(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let name = self.name_input.read(cx).value().trim().to_string();
+
+        if name.is_empty() || name == "reset" || name.contains([',', ' ']) {
+            self.error = Some(
+                "Submap names can not be empty, reset or contain commas or spaces".to_string(),
+            );
+            cx.notify();
+            return;
+        }
+        if self.submaps.iter().any(|s| s.name == name) {
+            self.error = Some(format!("A submap named {} already exists", name));
+            cx.notify();
+            return;
+        }
+
+        // leaving with escape is what most submaps do
+        let submap = Submap {
+            name: name.clone(),
+            exit: Some(Keybind::new(0, "Escape".to_string(), "submap", "reset")),
+            ..Default::default()
+        };
+        self.saved.push(Submap {
+            name,
+            ..Default::default()
+        });
+        self.submaps.push(submap);
+        self.name_input.update(cx, |state, cx| {
+            state.set_value("", window, cx);
+        });
+        self.select(self.submaps.len() - 1, cx);
+    }
+
+    fn add_bind(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(idx) = self.selected else {
+            return;
+        };
+        self.draft.arg = self.arg_input.read(cx).value().trim().to_string();
+        self.draft.submap = self.submaps[idx].name.clone();
+
+        let submap = &self.submaps[idx];
+        let combo = self.draft.combo();
+        let used = submap
+            .binds
+            .iter()
+            .chain(submap.exit.iter())
+            .any(|b| b.combo() == combo);
+
+        self.error = if self.draft.key_name().is_empty() {
+            Some("Press the key combination for this bind".to_string())
+        } else if self.draft.dispatcher.is_empty() {
+            Some("Pick a dispatcher".to_string())
+        } else if used {
+            Some(format!(
+                "{} is already used in this submap",
+                display_combo(&self.draft)
+            ))
+        } else {
+            validate_args(&self.draft.dispatcher, &self.draft.arg, false)
+                .err()
+                .map(|e| e.to_string())
+        };
+        if self.error.is_some() {
+            cx.notify();
+            return;
+        }
+
+        // keep the dispatcher and repeat flag, binds in a submap often share them
+        let bind = std::mem::take(&mut self.draft);
+        self.draft.dispatcher = bind.dispatcher.clone();
+        self.draft.repeat = bind.repeat;
+        self.submaps[idx].binds.push(bind);
+
+        self.bind_capture.update(cx, |capture, cx| {
+            capture.set_combo(0, String::new(), cx);
+        });
+        self.arg_input.update(cx, |state, cx| {
+            state.set_value("", window, cx);
+        });
+        cx.notify();
+    }
+
+    fn save(&mut self, cx: &mut Context<Self>) {
+        let Some(idx) = self.selected else {
+            return;
+        };
+        let submap = &self.submaps[idx];
+        let saved = &self.saved[idx];
+
+        let Some(entry) = &submap.entry else {
+            self.error = Some("Press the key entering the submap".to_string());
+            cx.notify();
+            return;
+        };
+        if submap.exit.is_none() {
+            self.error = Some("Press the key leaving the submap".to_string());
+            cx.notify();
+            return;
+        }
+
+        // the region goes first so the entry bind never points at a missing submap
+        if let Err(e) = SettingWriter::build_submap(submap.setting(saved)).and_then(|w| w.write()) {
+            eprintln!("Failed to write override: {}", e);
+        }
+        replace_bind(saved.entry.as_ref(), Some(entry));
+
+        self.saved[idx] = submap.clone();
+        self.error = None;
+        cx.notify();
+    }
+
+    fn delete(&mut self, cx: &mut Context<Self>) {
+        let Some(idx) = self.selected else {
+            return;
+        };
+        let submap = self.submaps.remove(idx);
+        let saved = self.saved.remove(idx);
+
+        if let Err(e) = SettingWriter::build_submap(submap.setting(&saved)).and_then(|w| w.remove())
+        {
+            eprintln!("Failed to remove override: {}", e);
+        }
+        if saved.entry.is_some() {
+            replace_bind(saved.entry.as_ref(), None);
+        }
+
+        self.select(0, cx);
+    }
+
+    fn render_list(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .v_flex()
+            .gap_1()
+            .when(self.submaps.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_size(px(14.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child("No submaps yet"),
+                )
+            })
+            .children(self.submaps.iter().enumerate().map(|(idx, submap)| {
+                div()
+                    .id(("submap", idx))
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .px_2()
+                    .py_1()
+                    .rounded_md()
+                    .cursor_pointer()
+                    .when(self.selected == Some(idx), |this| {
+                        this.bg(cx.theme().accent)
+                    })
+                    .on_click(cx.listener(move |this, _, _, cx| {
+                        this.select(idx, cx);
+                    }))
+                    .child(
+                        div()
+                            .min_w(px(160.0))
+                            .text_color(cx.theme().foreground)
+                            .child(submap.name.clone()),
+                    )
+                    .child(
+                        div()
+                            .text_size(px(12.0))
+                            .text_color(cx.theme().muted_foreground)
+                            .child(
+                                submap
+                                    .entry
+                                    .as_ref()
+                                    .map(display_combo)
+                                    .unwrap_or_else(|| "no entry bind".to_string()),
+                            ),
+                    )
+                    .child(
+                        item_pill(cx)
+                            .text_size(px(10.0))
+                            .child(format!("{} binds", submap.binds.len())),
+                    )
+            }))
+    }
+
+    fn render_editor(&self, submap: &Submap, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .v_flex()
+            .gap_3()
+            .child(
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child(format!("submap = {}", submap.name)),
+            )
+            .child(key_row(
+                "Enter with:",
+                "Global bind that switches to this submap.",
+                &self.entry_capture,
+                cx,
+            ))
+            .child(key_row(
+                "Leave with:",
+                "Bind inside the submap that goes back to the global binds.",
+                &self.exit_capture,
+                cx,
+            ))
+            .child(
+                div()
+                    .v_flex()
+                    .gap_1()
+                    .children(submap.binds.iter().enumerate().map(|(idx, bind)| {
+                        div()
+                            .h_flex()
+                            .gap_2()
+                            .items_center()
+                            .child(div().min_w(px(160.0)).child(display_combo(bind)))
+                            .child(
+                                div()
+                                    .flex_1()
+                                    .text_size(px(12.0))
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(format!("{} {}", bind.dispatcher, bind.arg)),
+                            )
+                            .when(bind.repeat, |this| {
+                                this.child(item_pill(cx).text_size(px(10.0)).child("repeat"))
+                            })
+                            .child(
+                                Button::new(("remove-submap-bind", idx))
+                                    .label("Remove")
+                                    .on_click(cx.listener(move |this, _, _, cx| {
+                                        if let Some(submap) = this.selected_submap_mut() {
+                                            submap.binds.remove(idx);
+                                        }
+                                        cx.notify();
+                                    })),
+                            )
+                    })),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .flex_wrap()
+                    .child(self.bind_capture.clone())
+                    .child(Dropdown::new(&self.dispatcher_dropdown).w(px(200.0)))
+                    .child(div().w(px(200.0)).child(TextInput::new(&self.arg_input)))
+                    .child(with_tooltip(
+                        "Repeat the bind while the keys are held, e.g. for resizing.",
+                        Switch::new("submap-bind-repeat")
+                            .label("Repeat")
+                            .checked(self.draft.repeat)
+                            .on_click(cx.listener(|this, checked: &bool, _, cx| {
+                                this.draft.repeat = *checked;
+                                cx.notify();
+                            })),
+                        cx,
+                    ))
+                    .child(
+                        Button::new("add-submap-bind")
+                            .label("Add bind")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.add_bind(window, cx);
+                            })),
+                    ),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(with_tooltip(
+                        "Writes the submap as one block to the overrides file together with its entry bind.",
+                        Button::new("save-submap")
+                            .label("Save submap")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.save(cx);
+                            })),
+                        cx,
+                    ))
+                    .child(
+                        Button::new("delete-submap")
+                            .label("Delete submap")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.delete(cx);
+                            })),
+                    ),
+            )
+    }
+}
+
+impl Render for SubmapSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let selected = self.selected.and_then(|idx| self.submaps.get(idx)).cloned();
+
+        section_container(cx)
+            .child(with_tooltip(
+                "Submaps are modes with their own binds, e.g. a resize mode. Hyprland reloads the overrides file after saving.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Submaps".to_string()),
+                cx,
+            ))
+            .child(
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(div().w(px(200.0)).child(TextInput::new(&self.name_input)))
+                    .child(
+                        Button::new("new-submap")
+                            .label("New submap")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.new_submap(window, cx);
+                            })),
+                    ),
+            )
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(cx.theme().danger)
+                        .child(error),
+                )
+            })
+            .child(
+                div()
+                    .h_flex()
+                    .gap_6()
+                    .items_start()
+                    .child(self.render_list(cx))
+                    .when_some(selected, |this, submap| {
+                        this.child(div().flex_1().child(self.render_editor(&submap, cx)))
+                    }),
+            )
+    }
+}
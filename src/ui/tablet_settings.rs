@@ -0,0 +1,424 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::Disableable;
+use gpui_component::IndexPath;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::dropdown::*;
+use gpui_component::switch::Switch;
+
+use crate::option_registry::{read_text, write_option};
+use crate::ui::highlight::highlight_row;
+use crate::ui::monitor_visualizer::MonitorVisualizer;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::monitor::MonitorInfo;
+use crate::util::tablet::{
+    TOUCHDEVICE_OUTPUT, TabletDevice, TabletOptions, TouchDevice, get_tablet_options, get_tablets,
+    get_touch_devices, tablet_option,
+};
+
+const REGION_CANVAS_WIDTH: f32 = 320.0;
+
+pub struct TabletSettings {
+    tablets: Vec<TabletDevice>,
+    touch_devices: Vec<TouchDevice>,
+    monitors: Vec<MonitorInfo>,
+    options: TabletOptions,
+    map_touch_devices: bool,
+    monitor_dropdown: Entity<DropdownState<Vec<String>>>,
+    canvas_bounds: Bounds<Pixels>,
+    drag_start: Option<Point<f32>>,
+    drag_end: Point<f32>,
+    /// Option the command palette jumped to
+    highlighted: Option<&'static str>,
+}
+
+impl TabletSettings {
+    pub fn new(monitors: Vec<MonitorInfo>, window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let tablets = get_tablets().unwrap_or_else(|e| {
+            eprintln!("Failed to get tablets: {}", e);
+            vec![]
+        });
+        let touch_devices = get_touch_devices().unwrap_or_else(|e| {
+            eprintln!("Failed to get touch devices: {}", e);
+            vec![]
+        });
+
+        let mut options = get_tablet_options();
+        // touchscreens are only remapped when they already follow the tablet
+        let map_touch_devices =
+            !options.output.is_empty() && read_text(TOUCHDEVICE_OUTPUT) == options.output;
+
+        let monitor_names: Vec<String> = monitors.iter().map(|m| m.name.clone()).collect();
+        let current_monitor_idx = monitor_names
+            .iter()
+            .position(|name| name == &options.output)
+            .or(if monitor_names.is_empty() {
+                None
+            } else {
+                Some(0)
+            });
+
+        if let Some(idx) = current_monitor_idx {
+            options.output = monitor_names[idx].clone();
+        }
+
+        let monitor_dropdown = cx.new(|cx| {
+            DropdownState::new(
+                monitor_names,
+                current_monitor_idx.map(IndexPath::new),
+                window,
+                cx,
+            )
+        });
+
+        cx.subscribe(
+            &monitor_dropdown,
+            |this, _dropdown, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(monitor_name)) = event {
+                    this.options.output = monitor_name.clone();
+                    // A region only makes sense for the monitor it was drawn on
+                    this.options.region_position = (0, 0);
+                    this.options.region_size = (0, 0);
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        Self {
+            tablets,
+            touch_devices,
+            monitors,
+            options,
+            map_touch_devices,
+            monitor_dropdown,
+            canvas_bounds: Bounds::default(),
+            drag_start: None,
+            drag_end: Point::default(),
+            highlighted: None,
+        }
+    }
+
+    /// Outline the row of an option, `None` clears it
+    pub fn highlight(&mut self, path: Option<&'static str>, cx: &mut Context<Self>) {
+        self.highlighted = path;
+        cx.notify();
+    }
+
+    fn is_highlighted(&self, path: &str) -> bool {
+        self.highlighted == Some(path)
+    }
+
+    fn selected_monitor(&self) -> Option<&MonitorInfo> {
+        self.monitors.iter().find(|m| m.name == self.options.output)
+    }
+
+    /// Size of the selected monitor in pixels and the scale used to draw it on the canvas
+    fn monitor_geometry(&self) -> ((i32, i32), f32) {
+        let resolution = self
+            .selected_monitor()
+            .map(|m| MonitorVisualizer::parse_resolution(&m.current_resolution))
+            .unwrap_or((1920, 1080));
+
+        (resolution, REGION_CANVAS_WIDTH / resolution.0 as f32)
+    }
+
+    /// Convert a window position into canvas coordinates, clamped to the canvas
+    fn local_position(&self, position: Point<Pixels>) -> Point<f32> {
+        let width: f32 = self.canvas_bounds.size.width.into();
+        let height: f32 = self.canvas_bounds.size.height.into();
+        let x: f32 = (position.x - self.canvas_bounds.left()).into();
+        let y: f32 = (position.y - self.canvas_bounds.top()).into();
+
+        point(x.clamp(0.0, width.max(0.0)), y.clamp(0.0, height.max(0.0)))
+    }
+
+    /// Convert the dragged rectangle on the canvas into a region in monitor pixels
+    fn finish_region(&mut self) {
+        let Some(start) = self.drag_start.take() else {
+            return;
+        };
+        let (_, scale) = self.monitor_geometry();
+
+        let left = start.x.min(self.drag_end.x);
+        let top = start.y.min(self.drag_end.y);
+        let width = (start.x - self.drag_end.x).abs();
+        let height = (start.y - self.drag_end.y).abs();
+
+        // A click without a drag clears the region, mapping the tablet to the whole monitor
+        if width < 4.0 || height < 4.0 {
+            self.options.region_position = (0, 0);
+            self.options.region_size = (0, 0);
+            return;
+        }
+
+        self.options.region_position =
+            ((left / scale).round() as i32, (top / scale).round() as i32);
+        self.options.region_size = (
+            (width / scale).round() as i32,
+            (height / scale).round() as i32,
+        );
+    }
+
+    fn apply_tablet_setting(options: &TabletOptions, map_touch_devices: bool) {
+        let values = [
+            ("output", options.output.clone()),
+            (
+                "region_position",
+                format!(
+                    "{} {}",
+                    options.region_position.0, options.region_position.1
+                ),
+            ),
+            (
+                "region_size",
+                format!("{} {}", options.region_size.0, options.region_size.1),
+            ),
+            ("relative_input", options.relative_input.to_string()),
+        ];
+
+        for (name, value) in values {
+            if let Err(e) = write_option(&tablet_option(name), value) {
+                eprintln!("Failed to write override: {}", e);
+            }
+        }
+
+        if map_touch_devices && let Err(e) = write_option(TOUCHDEVICE_OUTPUT, &options.output) {
+            eprintln!("Failed to write override: {}", e);
+        }
+    }
+
+    fn render_region_canvas(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let ((monitor_width, monitor_height), scale) = self.monitor_geometry();
+        let canvas_height = monitor_height as f32 * scale;
+        let view = cx.entity();
+
+        // While dragging show the live rectangle, otherwise the saved region
+        let region = if let Some(start) = self.drag_start {
+            Some((
+                start.x.min(self.drag_end.x),
+                start.y.min(self.drag_end.y),
+                (start.x - self.drag_end.x).abs(),
+                (start.y - self.drag_end.y).abs(),
+            ))
+        } else if self.options.region_size.0 > 0 && self.options.region_size.1 > 0 {
+            Some((
+                self.options.region_position.0 as f32 * scale,
+                self.options.region_position.1 as f32 * scale,
+                self.options.region_size.0 as f32 * scale,
+                self.options.region_size.1 as f32 * scale,
+            ))
+        } else {
+            None
+        };
+
+        div()
+            .relative()
+            .w(px(REGION_CANVAS_WIDTH))
+            .h(px(canvas_height))
+            .bg(rgb(0x3b4252))
+            .border_2()
+            .border_color(rgb(0x4c566a))
+            .rounded_md()
+            .overflow_hidden()
+            .cursor_crosshair()
+            .child(
+                canvas(
+                    move |bounds, _, cx| view.update(cx, |this, _| this.canvas_bounds = bounds),
+                    |_, _, _, _| {},
+                )
+                .absolute()
+                .size_full(),
+            )
+            .on_mouse_down(
+                MouseButton::Left,
+                cx.listener(|this, event: &MouseDownEvent, _window, cx| {
+                    let position = this.local_position(event.position);
+                    this.drag_start = Some(position);
+                    this.drag_end = position;
+                    cx.notify();
+                }),
+            )
+            .on_mouse_move(cx.listener(|this, event: &MouseMoveEvent, _window, cx| {
+                if this.drag_start.is_some() {
+                    this.drag_end = this.local_position(event.position);
+                    cx.notify();
+                }
+            }))
+            .on_mouse_up(
+                MouseButton::Left,
+                cx.listener(|this, _event: &MouseUpEvent, _window, cx| {
+                    this.finish_region();
+                    cx.notify();
+                }),
+            )
+            .child(
+                div()
+                    .absolute()
+                    .size_full()
+                    .flex()
+                    .items_center()
+                    .justify_center()
+                    .text_color(rgb(0xeceff4))
+                    .text_size(px(10.0))
+                    .child(format!(
+                        "{} ({}x{})",
+                        self.options.output, monitor_width, monitor_height
+                    )),
+            )
+            .when_some(region, |this, (x, y, w, h)| {
+                this.child(
+                    div()
+                        .absolute()
+                        .left(px(x))
+                        .top(px(y))
+                        .w(px(w))
+                        .h(px(h))
+                        .bg(rgba(0x88c0d055))
+                        .border_1()
+                        .border_color(rgb(0x88c0d0)),
+                )
+            })
+    }
+}
+
+impl Render for TabletSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let options = self.options.clone();
+        let map_touch_devices = self.map_touch_devices;
+
+        let region_label = if options.region_size.0 > 0 && options.region_size.1 > 0 {
+            format!(
+                "{}x{} at {}x{}",
+                options.region_size.0,
+                options.region_size.1,
+                options.region_position.0,
+                options.region_position.1
+            )
+        } else {
+            "Whole monitor".to_string()
+        };
+
+        let output_row = highlight_row(
+            div().h_flex().gap_4().items_center(),
+            self.is_highlighted(&tablet_option("output")),
+            cx,
+        );
+        let region_row = highlight_row(
+            div().v_flex().gap_1(),
+            self.is_highlighted(&tablet_option("region_position"))
+                || self.is_highlighted(&tablet_option("region_size")),
+            cx,
+        );
+        let relative_input_row = highlight_row(
+            div().h_flex().gap_4().items_center(),
+            self.is_highlighted(&tablet_option("relative_input")),
+            cx,
+        );
+        let touch_output_row = highlight_row(
+            div().h_flex().gap_4().items_center(),
+            self.is_highlighted(TOUCHDEVICE_OUTPUT),
+            cx,
+        );
+
+        section_container(cx)
+            .min_h(px(200.0))
+            .child(with_tooltip(
+                "Tablets and touchscreens detected by Hyprland. Tablet pads and pens are part of the tablet and are not listed separately.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Tablet & touch devices".to_string()),
+                cx,
+            ))
+            .child(
+                div()
+                    .v_flex()
+                    .gap_1()
+                    .text_size(px(14.0))
+                    .text_color(cx.theme().muted_foreground)
+                    .when(self.tablets.is_empty() && self.touch_devices.is_empty(), |this| {
+                        this.child("No tablets or touchscreens detected")
+                    })
+                    .children(
+                        self.tablets
+                            .iter()
+                            .map(|t| format!("Tablet: {} ({})", t.name, t.address)),
+                    )
+                    .children(
+                        self.touch_devices
+                            .iter()
+                            .map(|t| format!("Touchscreen: {} ({})", t.name, t.address)),
+                    ),
+            )
+            .child(
+                output_row
+                    .child(div().min_w(px(200.0)).child("Target monitor:"))
+                    .child(Dropdown::new(&self.monitor_dropdown).min_w(px(200.0))),
+            )
+            .child(
+                region_row
+                    .child(with_tooltip(
+                        "Drag on the monitor to select the area the tablet maps to. Click without dragging to use the whole monitor.",
+                        div().child("Active region:"),
+                        cx,
+                    ))
+                    .child(self.render_region_canvas(cx))
+                    .child(
+                        div()
+                            .text_size(px(12.0))
+                            .text_color(cx.theme().muted_foreground)
+                            .child(region_label),
+                    ),
+            )
+            .child(
+                relative_input_row
+                    .child(with_tooltip(
+                        "Move the cursor relative to its position like a mouse instead of jumping to where the pen touches.",
+                        div().min_w(px(200.0)).child("Relative input"),
+                        cx,
+                    ))
+                    .child(
+                        Switch::new("tablet-relative-input-switch")
+                            .checked(options.relative_input)
+                            .on_click(cx.listener(|view, checked, _, cx| {
+                                view.options.relative_input = *checked;
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .child(
+                touch_output_row
+                    .child(with_tooltip(
+                        "Also map touchscreens to the target monitor.",
+                        div().min_w(px(200.0)).child("Map touchscreens"),
+                        cx,
+                    ))
+                    .child(
+                        Switch::new("touchdevice-output-switch")
+                            .checked(map_touch_devices)
+                            .on_click(cx.listener(|view, checked, _, cx| {
+                                view.map_touch_devices = *checked;
+                                cx.notify();
+                            })),
+                    ),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(200.0)))
+                    .child(
+                        Button::new("apply-tablet-settings")
+                            .label("Apply tablet setting")
+                            .disabled(options.output.is_empty())
+                            .on_click(move |_, _, _cx| {
+                                Self::apply_tablet_setting(&options, map_touch_devices);
+                            }),
+                    ),
+            )
+    }
+}
@@ -0,0 +1,202 @@
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::slider::{Slider, SliderEvent, SliderState};
+use gpui_component::switch::Switch;
+
+use crate::option_registry::write_option;
+use crate::ui::highlight::highlight_row;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::touchpad::{TouchpadOptions, get_touchpad_options, touchpad_option};
+
+pub struct TouchpadSettings {
+    options: TouchpadOptions,
+    scroll_factor_slider: Entity<SliderState>,
+    /// Option the command palette jumped to
+    highlighted: Option<&'static str>,
+}
+
+impl TouchpadSettings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let options = get_touchpad_options();
+
+        let scroll_factor_slider = cx.new(|_cx| SliderState::new().min(0.1).max(3.0).step(0.05));
+
+        scroll_factor_slider.update(cx, |state, cx| {
+            state.set_value(options.scroll_factor, window, cx);
+        });
+
+        cx.subscribe(
+            &scroll_factor_slider,
+            |this, _, event: &SliderEvent, cx| match event {
+                SliderEvent::Change(value) => {
+                    this.options.scroll_factor = value.start();
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        Self {
+            options,
+            scroll_factor_slider,
+            highlighted: None,
+        }
+    }
+
+    /// Outline the row of an option, `None` clears it
+    pub fn highlight(&mut self, path: Option<&'static str>, cx: &mut Context<Self>) {
+        self.highlighted = path;
+        cx.notify();
+    }
+
+    fn is_highlighted(&self, name: &str) -> bool {
+        self.highlighted
+            .is_some_and(|path| path == touchpad_option(name))
+    }
+
+    fn switch_row(
+        &self,
+        name: &'static str,
+        label: &'static str,
+        description: &'static str,
+        checked: bool,
+        set: fn(&mut TouchpadOptions, bool),
+        cx: &mut Context<Self>,
+    ) -> impl IntoElement {
+        let row = div().h_flex().gap_4().items_center();
+
+        highlight_row(row, self.is_highlighted(name), cx)
+            .child(with_tooltip(
+                description,
+                div().min_w(px(200.0)).child(label),
+                cx,
+            ))
+            .child(
+                Switch::new(SharedString::from(format!("touchpad-{}-switch", name)))
+                    .checked(checked)
+                    .on_click(cx.listener(move |view, checked, _, cx| {
+                        set(&mut view.options, *checked);
+                        cx.notify();
+                    })),
+            )
+    }
+}
+
+impl Render for TouchpadSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        let options = self.options.clone();
+        let scroll_factor_row = highlight_row(
+            div().v_flex().gap_1(),
+            self.is_highlighted("scroll_factor"),
+            cx,
+        );
+
+        section_container(cx)
+            .min_h(px(200.0))
+            .child(
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Touchpad settings".to_string()),
+            )
+            .child(self.switch_row(
+                "natural_scroll",
+                "Natural scroll",
+                "Invert the scroll direction so content follows your fingers, like on a phone.",
+                options.natural_scroll,
+                |o, v| o.natural_scroll = v,
+                cx,
+            ))
+            .child(self.switch_row(
+                "tap-to-click",
+                "Tap to click",
+                "Tapping the touchpad with 1, 2 or 3 fingers sends a left, right or middle click.",
+                options.tap_to_click,
+                |o, v| o.tap_to_click = v,
+                cx,
+            ))
+            .child(self.switch_row(
+                "disable_while_typing",
+                "Disable while typing",
+                "Ignore touchpad input while the keyboard is in use to avoid accidental palm clicks.",
+                options.disable_while_typing,
+                |o, v| o.disable_while_typing = v,
+                cx,
+            ))
+            .child(self.switch_row(
+                "clickfinger_behavior",
+                "Clickfinger behavior",
+                "Pressing the touchpad with 1, 2 or 3 fingers sends a left, right or middle click instead of using button areas.",
+                options.clickfinger_behavior,
+                |o, v| o.clickfinger_behavior = v,
+                cx,
+            ))
+            .child(self.switch_row(
+                "drag_lock",
+                "Drag lock",
+                "Lifting a finger briefly while tap-dragging will not drop the dragged item.",
+                options.drag_lock,
+                |o, v| o.drag_lock = v,
+                cx,
+            ))
+            .child(self.switch_row(
+                "middle_button_emulation",
+                "Middle button emulation",
+                "Pressing left and right buttons at the same time sends a middle click.",
+                options.middle_button_emulation,
+                |o, v| o.middle_button_emulation = v,
+                cx,
+            ))
+            .child(
+                scroll_factor_row
+                    .child(
+                        div()
+                            .h_flex()
+                            .gap_4()
+                            .items_center()
+                            .child(div().min_w(px(200.0)).child("Scroll factor:"))
+                            .child(
+                                div()
+                                    .text_size(px(14.0))
+                                    .text_color(cx.theme().muted_foreground)
+                                    .child(format!("{:.2}", options.scroll_factor)),
+                            ),
+                    )
+                    .child(
+                        Slider::new(&self.scroll_factor_slider)
+                            .w_full()
+                            .text_color(cx.theme().foreground),
+                    ),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(200.0)))
+                    .child(
+                        Button::new("apply-touchpad-settings")
+                            .label("Apply touchpad setting")
+                            .on_click(move |_, _, _cx| {
+                                let values = [
+                                    ("natural_scroll", options.natural_scroll.to_string()),
+                                    ("tap-to-click", options.tap_to_click.to_string()),
+                                    ("disable_while_typing", options.disable_while_typing.to_string()),
+                                    ("clickfinger_behavior", options.clickfinger_behavior.to_string()),
+                                    ("scroll_factor", format!("{:.2}", options.scroll_factor)),
+                                    ("drag_lock", options.drag_lock.to_string()),
+                                    ("middle_button_emulation", options.middle_button_emulation.to_string()),
+                                ];
+
+                                for (name, value) in values {
+                                    if let Err(e) = write_option(&touchpad_option(name), value) {
+                                        eprintln!("Failed to write override: {}", e);
+                                    }
+                                }
+                            }),
+                    ),
+            )
+    }
+}
@@ -0,0 +1,505 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::dropdown::*;
+use gpui_component::input::{InputEvent, InputState, TextInput};
+
+use crate::setting_writer::SettingWriter;
+use crate::ui::item_pill::item_pill;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::option::set_keyword;
+use crate::util::window_rules::{
+    Client, RULES, RuleParam, WINDOW_RULE_KEYWORD, WindowRule, exact_matcher, find_rule,
+    get_clients, get_window_rules,
+};
+
+pub struct WindowRuleSettings {
+    clients: Vec<Client>,
+    rules: Vec<WindowRule>,
+    class_input: Entity<InputState>,
+    title_input: Entity<InputState>,
+    rule_dropdown: Entity<DropdownState<SearchableVec<String>>>,
+    rule_name: String,
+    /// Used for rules taking one of a fixed set of values
+    param_dropdown: Entity<DropdownState<Vec<String>>>,
+    param_choice: String,
+    param_input: Entity<InputState>,
+    error: Option<String>,
+}
+
+/// Placeholder describing the parameters a rule expects
+fn param_hint(param: RuleParam) -> &'static str {
+    match param {
+        RuleParam::None => "No parameters",
+        RuleParam::Size => "Size, e.g. 800 600 or 50% 50%",
+        RuleParam::Position => "Position, e.g. 100 100 or 100%-w-10 10",
+        RuleParam::Opacity => "Opacity, e.g. 0.9 or 0.9 0.8",
+        RuleParam::Workspace => "Workspace, e.g. 2, name:web or special:term silent",
+        RuleParam::Number => "Number, e.g. 10",
+        RuleParam::Colour => "Colour, e.g. rgb(ff0000)",
+        RuleParam::Choice(_) | RuleParam::Text => "Parameters",
+    }
+}
+
+fn load_clients() -> Vec<Client> {
+    get_clients().unwrap_or_else(|e| {
+        eprintln!("Failed to get clients: {}", e);
+        Vec::new()
+    })
+}
+
+fn load_rules() -> Vec<WindowRule> {
+    get_window_rules().unwrap_or_else(|e| {
+        eprintln!("Failed to get window rules: {}", e);
+        Vec::new()
+    })
+}
+
+fn input_row(
+    label: &'static str,
+    tooltip: &'static str,
+    input: &Entity<InputState>,
+    cx: &mut App,
+) -> impl IntoElement {
+    div()
+        .h_flex()
+        .gap_4()
+        .items_center()
+        .child(with_tooltip(
+            tooltip,
+            div().min_w(px(120.0)).child(label),
+            cx,
+        ))
+        .child(div().flex_1().child(TextInput::new(input)))
+}
+
+impl WindowRuleSettings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let class_input = cx.new(|cx| InputState::new(window, cx).placeholder("e.g. ^(kitty)$"));
+        let title_input = cx.new(|cx| InputState::new(window, cx).placeholder("e.g. ^(htop)$"));
+        let rule_dropdown = cx.new(|cx| {
+            DropdownState::new(
+                SearchableVec::new(RULES.iter().map(|r| r.name.to_string()).collect::<Vec<_>>()),
+                None,
+                window,
+                cx,
+            )
+        });
+        let param_dropdown = cx.new(|cx| DropdownState::new(Vec::new(), None, window, cx));
+        let param_input = cx.new(|cx| InputState::new(window, cx).placeholder("Parameters"));
+
+        // the match preview follows the matchers as they are typed
+        for input in [&class_input, &title_input] {
+            cx.subscribe(input, |this, _, event: &InputEvent, cx| {
+                if let InputEvent::Change = event {
+                    this.error = None;
+                    cx.notify();
+                }
+            })
+            .detach();
+        }
+
+        cx.subscribe_in(
+            &rule_dropdown,
+            window,
+            |this, _, event: &DropdownEvent<SearchableVec<String>>, window, cx| {
+                if let DropdownEvent::Confirm(Some(rule)) = event {
+                    this.rule_name = rule.clone();
+                    this.sync_param_controls(window, cx);
+                }
+            },
+        )
+        .detach();
+
+        cx.subscribe(
+            &param_dropdown,
+            |this, _, event: &DropdownEvent<Vec<String>>, cx| {
+                if let DropdownEvent::Confirm(Some(param)) = event {
+                    this.param_choice = param.clone();
+                    this.error = None;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        Self {
+            clients: load_clients(),
+            rules: load_rules(),
+            class_input,
+            title_input,
+            rule_dropdown,
+            rule_name: String::new(),
+            param_dropdown,
+            param_choice: String::new(),
+            param_input,
+            error: None,
+        }
+    }
+
+    fn param_kind(&self) -> RuleParam {
+        find_rule(&self.rule_name)
+            .map(|r| r.param)
+            .unwrap_or(RuleParam::Text)
+    }
+
+    /// Show the parameter controls matching the selected rule
+    fn sync_param_controls(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let kind = self.param_kind();
+
+        if let RuleParam::Choice(choices) = kind {
+            let choices: Vec<String> = choices.iter().map(|c| c.to_string()).collect();
+            self.param_dropdown.update(cx, |state, cx| {
+                state.set_items(choices, window, cx);
+            });
+        }
+        self.param_choice.clear();
+        self.param_input.update(cx, |state, cx| {
+            state.set_placeholder(param_hint(kind), window, cx);
+            state.set_value("", window, cx);
+        });
+
+        self.error = None;
+        cx.notify();
+    }
+
+    /// Rule built from the editor, matchers left empty are skipped
+    fn draft(&self, cx: &App) -> WindowRule {
+        let params = if let RuleParam::Choice(_) = self.param_kind() {
+            self.param_choice.clone()
+        } else {
+            self.param_input.read(cx).value().trim().to_string()
+        };
+        let rule = if params.is_empty() {
+            self.rule_name.clone()
+        } else {
+            format!("{} {}", self.rule_name, params)
+        };
+
+        let matchers = [("class", &self.class_input), ("title", &self.title_input)]
+            .into_iter()
+            .map(|(name, input)| (name.to_string(), input.read(cx).value().trim().to_string()))
+            .filter(|(_, value)| !value.is_empty())
+            .collect();
+
+        WindowRule {
+            rule,
+            matchers,
+            overridden: true,
+        }
+    }
+
+    /// Fill the matchers with the exact class and title of an open window
+    fn use_client(&mut self, idx: usize, window: &mut Window, cx: &mut Context<Self>) {
+        let Some(client) = self.clients.get(idx) else {
+            return;
+        };
+        let class = exact_matcher(&client.class);
+        let title = exact_matcher(&client.title);
+
+        self.class_input.update(cx, |state, cx| {
+            state.set_value(class, window, cx);
+        });
+        self.title_input.update(cx, |state, cx| {
+            state.set_value(title, window, cx);
+        });
+        self.error = None;
+        cx.notify();
+    }
+
+    fn refresh(&mut self, cx: &mut Context<Self>) {
+        self.clients = load_clients();
+        self.rules = load_rules();
+        cx.notify();
+    }
+
+    fn add_rule(&mut self, cx: &mut Context<Self>) {
+        let rule = self.draft(cx);
+        if let Err(e) = rule.validate() {
+            self.error = Some(e.to_string());
+            cx.notify();
+            return;
+        }
+
+        let value = rule.line_value();
+        if let Err(e) = SettingWriter::build_single(&format!("{} = ", WINDOW_RULE_KEYWORD), &value)
+            .and_then(|w| w.write())
+        {
+            eprintln!("Failed to write override: {}", e);
+        }
+        // only applies to windows opened from now on
+        if let Err(e) = set_keyword(WINDOW_RULE_KEYWORD, &value) {
+            eprintln!("{}", e);
+        }
+
+        self.error = None;
+        self.refresh(cx);
+    }
+
+    fn remove_rule(&mut self, idx: usize, cx: &mut Context<Self>) {
+        let Some(rule) = self.rules.get(idx) else {
+            return;
+        };
+
+        if let Err(e) =
+            SettingWriter::build_single(&format!("{} = ", WINDOW_RULE_KEYWORD), rule.line_value())
+                .and_then(|w| w.remove())
+        {
+            eprintln!("Failed to remove override: {}", e);
+        }
+        self.refresh(cx);
+    }
+
+    fn matching_clients<'a>(&'a self, rule: &'a WindowRule) -> impl Iterator<Item = &'a Client> {
+        self.clients.iter().filter(|client| rule.matches(client))
+    }
+
+    fn render_clients(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        section_container(cx)
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(with_tooltip(
+                        "Windows open right now. Use one to fill in the matchers of a new rule.",
+                        div()
+                            .font_weight(FontWeight::BOLD)
+                            .text_color(cx.theme().foreground)
+                            .child("Open windows"),
+                        cx,
+                    ))
+                    .child(
+                        Button::new("refresh-clients")
+                            .label("Refresh")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.refresh(cx);
+                            })),
+                    ),
+            )
+            .when(self.clients.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_size(px(14.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child("No windows found"),
+                )
+            })
+            .children(self.clients.iter().enumerate().map(|(idx, client)| {
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .py_1()
+                    .child(
+                        div()
+                            .min_w(px(200.0))
+                            .text_color(cx.theme().foreground)
+                            .child(client.class.clone()),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_size(px(12.0))
+                            .text_color(cx.theme().muted_foreground)
+                            .child(client.title.clone()),
+                    )
+                    .child(
+                        item_pill(cx)
+                            .text_size(px(10.0))
+                            .child(format!("workspace {}", client.workspace.name)),
+                    )
+                    .when(client.floating, |this| {
+                        this.child(item_pill(cx).text_size(px(10.0)).child("floating"))
+                    })
+                    .child(
+                        Button::new(("use-client", idx))
+                            .label("Use")
+                            .on_click(cx.listener(move |this, _, window, cx| {
+                                this.use_client(idx, window, cx);
+                            })),
+                    )
+            }))
+    }
+
+    fn render_editor(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let rule = find_rule(&self.rule_name);
+        let kind = self.param_kind();
+        let draft = self.draft(cx);
+        // invalid regexes match nothing, so a half typed matcher shows no matches
+        let matches: Vec<&Client> = self.matching_clients(&draft).collect();
+
+        section_container(cx)
+            .child(
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("New rule"),
+            )
+            .child(input_row(
+                "Class:",
+                "Regex matched against the whole window class, leave empty to match any class.",
+                &self.class_input,
+                cx,
+            ))
+            .child(input_row(
+                "Title:",
+                "Regex matched against the whole window title, leave empty to match any title.",
+                &self.title_input,
+                cx,
+            ))
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(120.0)).child("Rule:"))
+                    .child(Dropdown::new(&self.rule_dropdown).w(px(240.0)))
+                    .when_some(rule, |this, rule| {
+                        this.child(
+                            div()
+                                .text_size(px(12.0))
+                                .text_color(cx.theme().muted_foreground)
+                                .child(rule.description),
+                        )
+                    }),
+            )
+            .when(kind != RuleParam::None, |this| {
+                this.child(
+                    div()
+                        .h_flex()
+                        .gap_4()
+                        .items_center()
+                        .child(div().min_w(px(120.0)).child("Parameters:"))
+                        .map(|this| {
+                            if let RuleParam::Choice(_) = kind {
+                                this.child(Dropdown::new(&self.param_dropdown).w(px(240.0)))
+                            } else {
+                                this.child(div().flex_1().child(TextInput::new(&self.param_input)))
+                            }
+                        }),
+                )
+            })
+            .when(!draft.matchers.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child(if matches.is_empty() {
+                            "Matches no open window".to_string()
+                        } else {
+                            format!(
+                                "Matches {}",
+                                matches
+                                    .iter()
+                                    .map(|c| format!("{} ({})", c.class, c.title))
+                                    .collect::<Vec<_>>()
+                                    .join(", ")
+                            )
+                        }),
+                )
+            })
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(cx.theme().danger)
+                        .child(error),
+                )
+            })
+            .child(
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(div().min_w(px(120.0)))
+                    .child(with_tooltip(
+                        "Writes the rule to the overrides file. It applies to windows opened from now on.",
+                        Button::new("add-rule")
+                            .label("Add rule")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.add_rule(cx);
+                            })),
+                        cx,
+                    )),
+            )
+    }
+
+    fn render_rules(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        section_container(cx)
+            .child(with_tooltip(
+                "Rules from hyprland.conf and the overrides file. Only rules added here can be removed.",
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child("Window rules"),
+                cx,
+            ))
+            .when(self.rules.is_empty(), |this| {
+                this.child(
+                    div()
+                        .text_size(px(14.0))
+                        .text_color(cx.theme().muted_foreground)
+                        .child("No window rules found"),
+                )
+            })
+            .children(self.rules.iter().enumerate().map(|(idx, rule)| {
+                let matched = self.matching_clients(rule).count();
+                let matchers = rule
+                    .matchers
+                    .iter()
+                    .map(|(name, value)| format!("{}:{}", name, value))
+                    .collect::<Vec<_>>()
+                    .join(", ");
+
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .py_1()
+                    .child(
+                        div()
+                            .min_w(px(200.0))
+                            .text_color(cx.theme().foreground)
+                            .child(rule.rule.clone()),
+                    )
+                    .child(
+                        div()
+                            .flex_1()
+                            .text_size(px(12.0))
+                            .text_color(cx.theme().muted_foreground)
+                            .child(matchers),
+                    )
+                    .child(
+                        item_pill(cx)
+                            .text_size(px(10.0))
+                            .child(format!("{} open", matched)),
+                    )
+                    .when(!rule.overridden, |this| {
+                        this.child(item_pill(cx).text_size(px(10.0)).child("hyprland.conf"))
+                    })
+                    .when(rule.overridden, |this| {
+                        this.child(
+                            Button::new(("remove-rule", idx))
+                                .label("Remove")
+                                .on_click(cx.listener(move |this, _, _, cx| {
+                                    this.remove_rule(idx, cx);
+                                })),
+                        )
+                    })
+            }))
+    }
+}
+
+impl Render for WindowRuleSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .v_flex()
+            .gap_4()
+            .child(self.render_editor(cx))
+            .child(self.render_rules(cx))
+            .child(self.render_clients(cx))
+    }
+}
@@ -0,0 +1,436 @@
+use gpui::prelude::FluentBuilder;
+use gpui::*;
+use gpui_component::ActiveTheme as _;
+use gpui_component::StyledExt;
+use gpui_component::button::Button;
+use gpui_component::dropdown::*;
+use gpui_component::input::{InputEvent, InputState, TextInput};
+
+use crate::setting_writer::SettingWriter;
+use crate::ui::item_pill::item_pill;
+use crate::ui::{section_container::section_container, tooltip::with_tooltip};
+use crate::util::option::set_keyword;
+use crate::util::workspace_rules::{
+    OptionValue, WORKSPACE_OPTIONS, WORKSPACE_RULE_KEYWORD, WorkspaceRule, find_option,
+    get_workspace_rules, selector_kind, validate_option,
+};
+
+pub struct WorkspaceRuleSettings {
+    rules: Vec<WorkspaceRule>,
+    /// Rule being edited, `None` while adding a new one
+    editing: Option<usize>,
+    editor_open: bool,
+    draft: WorkspaceRule,
+    selector_input: Entity<InputState>,
+    option_dropdown: Entity<DropdownState<SearchableVec<String>>>,
+    option_name: String,
+    value_input: Entity<InputState>,
+    error: Option<String>,
+}
+
+/// Placeholder describing the value an option expects
+fn value_hint(value: OptionValue) -> &'static str {
+    match value {
+        OptionValue::Bool => "true or false",
+        OptionValue::Number => "Number, e.g. 2",
+        OptionValue::Gaps => "Gaps, e.g. 5 or 5 10 5 10",
+        OptionValue::Text => "Value",
+    }
+}
+
+fn load_rules() -> Vec<WorkspaceRule> {
+    get_workspace_rules().unwrap_or_else(|e| {
+        eprintln!("Failed to get workspace rules: {}", e);
+        Vec::new()
+    })
+}
+
+fn workspace_prefix() -> String {
+    format!("{} = ", WORKSPACE_RULE_KEYWORD)
+}
+
+impl WorkspaceRuleSettings {
+    pub fn new(window: &mut Window, cx: &mut Context<Self>) -> Self {
+        let selector_input = cx.new(|cx| {
+            InputState::new(window, cx).placeholder("e.g. 3, name:web, special:term or r[1-5]")
+        });
+        let option_dropdown = cx.new(|cx| {
+            DropdownState::new(
+                SearchableVec::new(
+                    WORKSPACE_OPTIONS
+                        .iter()
+                        .map(|o| o.name.to_string())
+                        .collect::<Vec<_>>(),
+                ),
+                None,
+                window,
+                cx,
+            )
+        });
+        let value_input = cx.new(|cx| InputState::new(window, cx).placeholder("Value"));
+
+        // the selector kind is shown while typing
+        cx.subscribe(&selector_input, |this, _, event: &InputEvent, cx| {
+            if let InputEvent::Change = event {
+                this.error = None;
+                cx.notify();
+            }
+        })
+        .detach();
+
+        cx.subscribe_in(
+            &option_dropdown,
+            window,
+            |this, _, event: &DropdownEvent<SearchableVec<String>>, window, cx| {
+                if let DropdownEvent::Confirm(Some(option)) = event {
+                    this.option_name = option.clone();
+                    let hint = find_option(option)
+                        .map(|o| value_hint(o.value))
+                        .unwrap_or("Value");
+                    this.value_input.update(cx, |state, cx| {
+                        state.set_placeholder(hint, window, cx);
+                        state.set_value("", window, cx);
+                    });
+                    this.error = None;
+                    cx.notify();
+                }
+            },
+        )
+        .detach();
+
+        Self {
+            rules: load_rules(),
+            editing: None,
+            editor_open: false,
+            draft: WorkspaceRule::default(),
+            selector_input,
+            option_dropdown,
+            option_name: String::new(),
+            value_input,
+            error: None,
+        }
+    }
+
+    /// Open the editor for an existing rule, or for a new rule when `idx` is `None`
+    fn open_editor(&mut self, idx: Option<usize>, window: &mut Window, cx: &mut Context<Self>) {
+        let draft = idx
+            .and_then(|idx| self.rules.get(idx))
+            .cloned()
+            .unwrap_or_default();
+
+        let selector = draft.selector.clone();
+        self.selector_input.update(cx, |state, cx| {
+            state.set_value(selector, window, cx);
+        });
+
+        self.draft = draft;
+        self.editing = idx;
+        self.editor_open = true;
+        self.error = None;
+        cx.notify();
+    }
+
+    fn close_editor(&mut self, cx: &mut Context<Self>) {
+        self.editor_open = false;
+        self.editing = None;
+        cx.notify();
+    }
+
+    fn add_option(&mut self, window: &mut Window, cx: &mut Context<Self>) {
+        let value = self.value_input.read(cx).value().trim().to_string();
+
+        self.error = if self.option_name.is_empty() {
+            Some("Pick an option".to_string())
+        } else {
+            validate_option(&self.option_name, &value)
+                .err()
+                .map(|e| e.to_string())
+        };
+        if self.error.is_none() {
+            self.draft.set_option(&self.option_name, &value);
+            self.value_input.update(cx, |state, cx| {
+                state.set_value("", window, cx);
+            });
+        }
+        cx.notify();
+    }
+
+    fn remove_option(&mut self, idx: usize, cx: &mut Context<Self>) {
+        if idx < self.draft.options.len() {
+            self.draft.options.remove(idx);
+        }
+        cx.notify();
+    }
+
+    fn save_rule(&mut self, cx: &mut Context<Self>) {
+        self.draft.selector = self.selector_input.read(cx).value().trim().to_string();
+        self.draft.overridden = true;
+        if let Err(e) = self.draft.validate() {
+            self.error = Some(e.to_string());
+            cx.notify();
+            return;
+        }
+
+        // a rule moved to another workspace leaves its old line behind otherwise
+        if let Some(old) = self.editing.and_then(|idx| self.rules.get(idx))
+            && old.overridden
+            && old.selector != self.draft.selector
+        {
+            Self::remove_override(old);
+        }
+
+        let value = self.draft.line_value();
+        if let Err(e) =
+            SettingWriter::build_single(&workspace_prefix(), &value).and_then(|w| w.write())
+        {
+            eprintln!("Failed to write override: {}", e);
+        }
+        if let Err(e) = set_keyword(WORKSPACE_RULE_KEYWORD, &value) {
+            eprintln!("{}", e);
+        }
+
+        self.rules = load_rules();
+        self.close_editor(cx);
+    }
+
+    fn remove_override(rule: &WorkspaceRule) {
+        if let Err(e) = SettingWriter::build_single(&workspace_prefix(), rule.line_value())
+            .and_then(|w| w.remove())
+        {
+            eprintln!("Failed to remove override: {}", e);
+        }
+    }
+
+    fn remove_rule(&mut self, idx: usize, cx: &mut Context<Self>) {
+        let Some(rule) = self.rules.get(idx) else {
+            return;
+        };
+        Self::remove_override(rule);
+
+        self.rules = load_rules();
+        self.close_editor(cx);
+    }
+
+    fn render_rule_row(&self, idx: usize, rule: &WorkspaceRule, cx: &mut Context<Self>) -> Div {
+        let kind = selector_kind(&rule.selector).unwrap_or("invalid");
+
+        div()
+            .h_flex()
+            .gap_2()
+            .items_center()
+            .py_1()
+            .child(
+                div()
+                    .min_w(px(160.0))
+                    .text_color(cx.theme().foreground)
+                    .child(rule.selector.clone()),
+            )
+            .child(
+                div().min_w(px(80.0)).child(
+                    item_pill(cx)
+                        .text_size(px(10.0))
+                        .when(kind == "invalid", |this| this.text_color(cx.theme().danger))
+                        .child(kind),
+                ),
+            )
+            .child(
+                div()
+                    .flex_1()
+                    .h_flex()
+                    .flex_wrap()
+                    .gap_1()
+                    .children(rule.options.iter().map(|(name, value)| {
+                        item_pill(cx)
+                            .text_size(px(10.0))
+                            .child(format!("{}: {}", name, value))
+                    })),
+            )
+            .when(!rule.overridden, |this| {
+                this.child(item_pill(cx).text_size(px(10.0)).child("hyprland.conf"))
+            })
+            .child(
+                Button::new(("edit-workspace-rule", idx))
+                    .label("Edit")
+                    .on_click(cx.listener(move |this, _, window, cx| {
+                        this.open_editor(Some(idx), window, cx);
+                    })),
+            )
+            .when(rule.overridden, |this| {
+                this.child(
+                    Button::new(("remove-workspace-rule", idx))
+                        .label("Remove")
+                        .on_click(cx.listener(move |this, _, _, cx| {
+                            this.remove_rule(idx, cx);
+                        })),
+                )
+            })
+    }
+
+    fn render_editor(&self, cx: &mut Context<Self>) -> impl IntoElement {
+        let selector = self.selector_input.read(cx).value().to_string();
+        let kind = selector_kind(&selector);
+        let option = find_option(&self.option_name);
+
+        section_container(cx)
+            .child(
+                div()
+                    .font_weight(FontWeight::BOLD)
+                    .text_color(cx.theme().foreground)
+                    .child(if self.editing.is_some() {
+                        "Edit workspace rule"
+                    } else {
+                        "New workspace rule"
+                    }),
+            )
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(with_tooltip(
+                        "A workspace id, name:NAME, special:NAME, or a selector such as r[1-5] or m[DP-1].",
+                        div().min_w(px(120.0)).child("Workspace:"),
+                        cx,
+                    ))
+                    .child(div().w(px(320.0)).child(TextInput::new(&self.selector_input)))
+                    .when(!selector.trim().is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_size(px(12.0))
+                                .map(|this| match &kind {
+                                    Ok(kind) => this
+                                        .text_color(cx.theme().muted_foreground)
+                                        .child(format!("Matches by {}", kind)),
+                                    Err(e) => {
+                                        this.text_color(cx.theme().danger).child(e.to_string())
+                                    }
+                                }),
+                        )
+                    }),
+            )
+            .children(self.draft.options.iter().enumerate().map(|(idx, (name, value))| {
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(120.0)).child(format!("{}:", name)))
+                    .child(div().min_w(px(200.0)).child(value.clone()))
+                    .child(
+                        Button::new(("remove-workspace-option", idx))
+                            .label("Remove")
+                            .on_click(cx.listener(move |this, _, _, cx| {
+                                this.remove_option(idx, cx);
+                            })),
+                    )
+            }))
+            .child(
+                div()
+                    .h_flex()
+                    .gap_4()
+                    .items_center()
+                    .child(div().min_w(px(120.0)).child("Option:"))
+                    .child(Dropdown::new(&self.option_dropdown).w(px(200.0)))
+                    .child(div().w(px(200.0)).child(TextInput::new(&self.value_input)))
+                    .child(
+                        Button::new("add-workspace-option")
+                            .label("Set")
+                            .on_click(cx.listener(|this, _, window, cx| {
+                                this.add_option(window, cx);
+                            })),
+                    ),
+            )
+            .when_some(option, |this, option| {
+                this.child(
+                    div()
+                        .h_flex()
+                        .child(div().min_w(px(136.0)))
+                        .child(
+                            div()
+                                .text_size(px(12.0))
+                                .text_color(cx.theme().muted_foreground)
+                                .child(option.description),
+                        ),
+                )
+            })
+            .when_some(self.error.clone(), |this, error| {
+                this.child(
+                    div()
+                        .text_size(px(12.0))
+                        .text_color(cx.theme().danger)
+                        .child(error),
+                )
+            })
+            .child(
+                div()
+                    .h_flex()
+                    .gap_2()
+                    .items_center()
+                    .child(div().min_w(px(120.0)))
+                    .child(with_tooltip(
+                        "Writes the rule to the overrides file and applies it right away.",
+                        Button::new("save-workspace-rule")
+                            .label("Save rule")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.save_rule(cx);
+                            })),
+                        cx,
+                    ))
+                    .child(
+                        Button::new("cancel-workspace-rule")
+                            .label("Cancel")
+                            .on_click(cx.listener(|this, _, _, cx| {
+                                this.close_editor(cx);
+                            })),
+                    ),
+            )
+    }
+}
+
+impl Render for WorkspaceRuleSettings {
+    fn render(&mut self, _window: &mut Window, cx: &mut Context<Self>) -> impl IntoElement {
+        div()
+            .v_flex()
+            .gap_4()
+            .when(self.editor_open, |this| {
+                this.child(self.render_editor(cx))
+            })
+            .child(
+                section_container(cx)
+                    .child(
+                        div()
+                            .h_flex()
+                            .gap_4()
+                            .items_center()
+                            .child(with_tooltip(
+                                "Workspace rules from hyprland.conf and the overrides file. Editing a rule from hyprland.conf saves an override for the same workspace.",
+                                div()
+                                    .font_weight(FontWeight::BOLD)
+                                    .text_color(cx.theme().foreground)
+                                    .child("Workspace rules"),
+                                cx,
+                            ))
+                            .child(
+                                Button::new("add-workspace-rule")
+                                    .label("Add rule")
+                                    .on_click(cx.listener(|this, _, window, cx| {
+                                        this.open_editor(None, window, cx);
+                                    })),
+                            ),
+                    )
+                    .when(self.rules.is_empty(), |this| {
+                        this.child(
+                            div()
+                                .text_size(px(14.0))
+                                .text_color(cx.theme().muted_foreground)
+                                .child("No workspace rules found"),
+                        )
+                    })
+                    .children(
+                        self.rules
+                            .iter()
+                            .enumerate()
+                            .map(|(idx, rule)| self.render_rule_row(idx, rule, cx)),
+                    ),
+            )
+    }
+}
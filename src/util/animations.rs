@@ -0,0 +1,167 @@
+use serde::Deserialize;
+use std::process::Command;
+
+/// Parent of every animation below `global`, in the order Hyprland lists them
+const ANIMATION_PARENTS: [(&str, &str); 27] = [
+    ("windows", "global"),
+    ("windowsIn", "windows"),
+    ("windowsOut", "windows"),
+    ("windowsMove", "windows"),
+    ("layers", "global"),
+    ("layersIn", "layers"),
+    ("layersOut", "layers"),
+    ("fade", "global"),
+    ("fadeIn", "fade"),
+    ("fadeOut", "fade"),
+    ("fadeSwitch", "fade"),
+    ("fadeShadow", "fade"),
+    ("fadeDim", "fade"),
+    ("fadeLayers", "fade"),
+    ("fadeLayersIn", "fadeLayers"),
+    ("fadeLayersOut", "fadeLayers"),
+    ("fadePopups", "fade"),
+    ("fadePopupsIn", "fadePopups"),
+    ("fadePopupsOut", "fadePopups"),
+    ("border", "global"),
+    ("borderangle", "global"),
+    ("workspaces", "global"),
+    ("workspacesIn", "workspaces"),
+    ("workspacesOut", "workspaces"),
+    ("specialWorkspace", "workspaces"),
+    ("specialWorkspaceIn", "specialWorkspace"),
+    ("specialWorkspaceOut", "specialWorkspace"),
+];
+
+/// A node of the animation tree as reported by `hyprctl animations -j`
+#[derive(Debug, Clone, Deserialize)]
+pub struct AnimationNode {
+    pub name: String,
+    /// False when the node inherits its values from its parent
+    pub overridden: bool,
+    pub bezier: String,
+    pub enabled: bool,
+    pub speed: f32,
+    #[serde(default)]
+    pub style: String,
+}
+
+/// A bezier curve definition, the control points of a cubic curve from (0, 0) to (1, 1)
+#[derive(Debug, Clone, Deserialize)]
+pub struct Bezier {
+    pub name: String,
+    #[serde(rename = "X0")]
+    pub x0: f32,
+    #[serde(rename = "Y0")]
+    pub y0: f32,
+    #[serde(rename = "X1")]
+    pub x1: f32,
+    #[serde(rename = "Y1")]
+    pub y1: f32,
+}
+
+impl AnimationNode {
+    /// How deep the node sits below `global`, used to indent the tree
+    pub fn depth(&self) -> usize {
+        let mut depth = 0;
+        let mut name = self.name.as_str();
+        while let Some((_, parent)) = ANIMATION_PARENTS.iter().find(|(n, _)| *n == name) {
+            depth += 1;
+            name = parent;
+        }
+        depth
+    }
+
+    /// Styles Hyprland accepts for this node, the empty style uses the default
+    pub fn styles(&self) -> &'static [&'static str] {
+        let name = self.name.as_str();
+        if name.starts_with("windows") {
+            &["", "slide", "popin", "gnomed"]
+        } else if name.starts_with("layers") {
+            &["", "slide", "popin", "fade"]
+        } else if name.starts_with("workspaces") || name.starts_with("specialWorkspace") {
+            &[
+                "",
+                "slide",
+                "slidevert",
+                "fade",
+                "slidefade",
+                "slidefadevert",
+            ]
+        } else if name == "borderangle" {
+            &["", "once", "loop"]
+        } else {
+            &[""]
+        }
+    }
+
+    /// Value of the `animation=` line for this node
+    pub fn line_value(&self) -> String {
+        if !self.enabled {
+            return format!("{}, 0", self.name);
+        }
+
+        let mut value = format!("{}, 1, {:.1}, {}", self.name, self.speed, self.bezier);
+        if !self.style.is_empty() {
+            value.push_str(&format!(", {}", self.style));
+        }
+        value
+    }
+}
+
+impl Bezier {
+    pub fn new(name: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            x0: 0.25,
+            y0: 0.1,
+            x1: 0.25,
+            y1: 1.0,
+        }
+    }
+
+    /// Point on the curve for the curve parameter `t`
+    pub fn point(&self, t: f32) -> (f32, f32) {
+        let u = 1.0 - t;
+        let x = 3.0 * u * u * t * self.x0 + 3.0 * u * t * t * self.x1 + t * t * t;
+        let y = 3.0 * u * u * t * self.y0 + 3.0 * u * t * t * self.y1 + t * t * t;
+        (x, y)
+    }
+
+    /// Progress of the animation after `time` (0.0 to 1.0) of its duration
+    pub fn ease(&self, time: f32) -> f32 {
+        // x only grows along the curve as long as the control points stay within 0..1,
+        // so the parameter for a given time can be found by bisection
+        let (mut low, mut high) = (0.0, 1.0);
+        for _ in 0..20 {
+            let mid = (low + high) / 2.0;
+            if self.point(mid).0 < time {
+                low = mid;
+            } else {
+                high = mid;
+            }
+        }
+        self.point((low + high) / 2.0).1
+    }
+
+    /// Value of the `bezier=` line for this curve
+    pub fn line_value(&self) -> String {
+        format!(
+            "{}, {:.2}, {:.2}, {:.2}, {:.2}",
+            self.name, self.x0, self.y0, self.x1, self.y1
+        )
+    }
+}
+
+/// Get the animation tree and all bezier curves from hyprctl
+pub fn get_animations() -> anyhow::Result<(Vec<AnimationNode>, Vec<Bezier>)> {
+    let output = Command::new("hyprctl")
+        .args(["animations", "-j"])
+        .output()?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("Failed to execute hyprctl animations"));
+    }
+
+    let json_str = String::from_utf8(output.stdout)?;
+    Ok(serde_json::from_str(&json_str)?)
+}
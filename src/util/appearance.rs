@@ -0,0 +1,144 @@
+use std::fmt;
+
+use crate::option_registry::{read_bool, read_number, read_text};
+use crate::util::colour::{Colour, ColourValue};
+
+pub const GAPS_IN_OPTION: &str = "general:gaps_in";
+pub const GAPS_OUT_OPTION: &str = "general:gaps_out";
+pub const BORDER_SIZE_OPTION: &str = "general:border_size";
+pub const ACTIVE_BORDER_OPTION: &str = "general:col.active_border";
+pub const INACTIVE_BORDER_OPTION: &str = "general:col.inactive_border";
+pub const ROUNDING_OPTION: &str = "decoration:rounding";
+pub const ACTIVE_OPACITY_OPTION: &str = "decoration:active_opacity";
+pub const INACTIVE_OPACITY_OPTION: &str = "decoration:inactive_opacity";
+pub const DIM_INACTIVE_OPTION: &str = "decoration:dim_inactive";
+
+/// Current general and decoration options as reported by hyprctl
+#[derive(Debug, Clone)]
+pub struct AppearanceOptions {
+    pub gaps_in: Gaps,
+    pub gaps_out: Gaps,
+    pub border_size: i64,
+    pub active_border: ColourValue,
+    pub inactive_border: ColourValue,
+    pub rounding: i64,
+    pub active_opacity: f32,
+    pub inactive_opacity: f32,
+    pub dim_inactive: bool,
+}
+
+/// Options the running Hyprland does not report fall back to their registered defaults
+pub fn get_appearance_options() -> AppearanceOptions {
+    AppearanceOptions {
+        gaps_in: get_gaps(GAPS_IN_OPTION),
+        gaps_out: get_gaps(GAPS_OUT_OPTION),
+        border_size: read_number(BORDER_SIZE_OPTION),
+        active_border: get_colour(ACTIVE_BORDER_OPTION),
+        inactive_border: get_colour(INACTIVE_BORDER_OPTION),
+        rounding: read_number(ROUNDING_OPTION),
+        active_opacity: read_number(ACTIVE_OPACITY_OPTION),
+        inactive_opacity: read_number(INACTIVE_OPACITY_OPTION),
+        dim_inactive: read_bool(DIM_INACTIVE_OPTION),
+    }
+}
+
+/// Gaps of every side of a window in pixels
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct Gaps {
+    pub top: i64,
+    pub right: i64,
+    pub bottom: i64,
+    pub left: i64,
+}
+
+impl Gaps {
+    pub fn uniform(size: i64) -> Self {
+        Self {
+            top: size,
+            right: size,
+            bottom: size,
+            left: size,
+        }
+    }
+
+    /// One to four sizes like CSS margins, e.g. `5`, `5 10` or `5 10 5 10`
+    pub fn parse(value: &str) -> Option<Self> {
+        let sizes = value
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter(|size| !size.is_empty())
+            .map(|size| size.parse())
+            .collect::<Result<Vec<i64>, _>>()
+            .ok()?;
+
+        let (top, right, bottom, left) = match sizes[..] {
+            [size] => (size, size, size, size),
+            [vertical, horizontal] => (vertical, horizontal, vertical, horizontal),
+            [top, horizontal, bottom] => (top, horizontal, bottom, horizontal),
+            [top, right, bottom, left] => (top, right, bottom, left),
+            _ => return None,
+        };
+
+        Some(Self {
+            top,
+            right,
+            bottom,
+            left,
+        })
+    }
+
+    pub fn is_uniform(&self) -> bool {
+        *self == Self::uniform(self.top)
+    }
+}
+
+impl fmt::Display for Gaps {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_uniform() {
+            write!(f, "{}", self.top)
+        } else {
+            // the config reads per-side gaps as comma separated values
+            write!(
+                f,
+                "{},{},{},{}",
+                self.top, self.right, self.bottom, self.left
+            )
+        }
+    }
+}
+
+/// Gaps are reported as "top right bottom left", every side is kept
+fn get_gaps(name: &str) -> Gaps {
+    Gaps::parse(&read_text(name)).unwrap_or_default()
+}
+
+fn get_colour(name: &str) -> ColourValue {
+    read_text(name)
+        .parse()
+        .unwrap_or_else(|_| ColourValue::single(Colour::from_argb(0xffffffff)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gaps_keep_every_side() {
+        let gaps = Gaps::parse("5 10 5 10").unwrap();
+        assert_eq!(
+            (gaps.top, gaps.right, gaps.bottom, gaps.left),
+            (5, 10, 5, 10)
+        );
+        assert_eq!(gaps.to_string(), "5,10,5,10");
+
+        assert_eq!(Gaps::parse("5").unwrap().to_string(), "5");
+        assert_eq!(Gaps::parse("5,10").unwrap().to_string(), "5,10,5,10");
+        assert_eq!(Gaps::parse("1 2 3").unwrap().to_string(), "1,2,3,2");
+    }
+
+    #[test]
+    fn invalid_gaps_are_rejected() {
+        assert_eq!(Gaps::parse(""), None);
+        assert_eq!(Gaps::parse("5 px"), None);
+        assert_eq!(Gaps::parse("1 2 3 4 5"), None);
+    }
+}
@@ -0,0 +1,117 @@
+use std::path::{Path, PathBuf};
+
+use crate::setting_writer::{HYPR_OVERRIDES_PATH, SettingWriter, config_path};
+use crate::util::config_tree::{read_config_tree, strip_comment};
+
+/// Keywords running commands at startup, on every reload and on shutdown
+pub const AUTOSTART_KEYWORDS: [&str; 3] = ["exec-once", "exec", "exec-shutdown"];
+/// Marks an entry disabled in the overrides file, other commented lines are left to the user
+pub const DISABLED_AUTOSTART_PREFIX: &str = "#disabled ";
+
+/// An `exec-once`, `exec` or `exec-shutdown` line found in the config tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct AutostartEntry {
+    pub keyword: String,
+    pub command: String,
+    /// Disabled entries are commented out, only entries in the overrides file are read that way
+    pub enabled: bool,
+    /// File the entry was found in
+    pub path: PathBuf,
+    /// Whether the entry lives in the overrides file and can be edited
+    pub overridden: bool,
+}
+
+impl AutostartEntry {
+    pub fn new(keyword: &str, command: &str) -> Self {
+        Self {
+            keyword: keyword.to_string(),
+            command: command.to_string(),
+            enabled: true,
+            path: config_path(HYPR_OVERRIDES_PATH).unwrap_or_default(),
+            overridden: true,
+        }
+    }
+
+    /// Parse an autostart line, `#disabled exec-once = ...` is read as a disabled entry
+    fn parse(line: &str, path: &Path, overridden: bool) -> Option<Self> {
+        let line = line.trim();
+        let (line, enabled) = match line.strip_prefix(DISABLED_AUTOSTART_PREFIX) {
+            Some(disabled) => (disabled, false),
+            None if line.starts_with('#') => return None,
+            None => (line, true),
+        };
+
+        let (keyword, command) = line.split_once('=')?;
+        let keyword = keyword.trim();
+        let command = strip_comment(command);
+        let command = command.trim();
+        if !AUTOSTART_KEYWORDS.contains(&keyword) || command.is_empty() {
+            return None;
+        }
+
+        Some(Self {
+            keyword: keyword.to_string(),
+            command: command.to_string(),
+            enabled,
+            path: path.to_path_buf(),
+            overridden,
+        })
+    }
+
+    pub fn line(&self) -> String {
+        // a single `#` would start a comment
+        let line = format!("{} = {}", self.keyword, self.command.replace('#', "##"));
+        if self.enabled {
+            line
+        } else {
+            format!("{}{}", DISABLED_AUTOSTART_PREFIX, line)
+        }
+    }
+}
+
+/// Autostart entries of the whole config tree in the order Hyprland reads them
+pub fn get_autostart() -> anyhow::Result<Vec<AutostartEntry>> {
+    Ok(read_config_tree()?
+        .iter()
+        .filter_map(|line| AutostartEntry::parse(&line.line, &line.path, line.overridden))
+        .collect())
+}
+
+/// Write the entries of the overrides file, in order
+pub fn write_autostart(entries: &[AutostartEntry]) -> anyhow::Result<()> {
+    let lines = entries
+        .iter()
+        .filter(|entry| entry.overridden)
+        .map(|entry| entry.line())
+        .collect();
+
+    SettingWriter::build_autostart(lines)?.write()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(line: &str) -> Option<String> {
+        AutostartEntry::parse(line, Path::new(""), true).map(|entry| entry.line())
+    }
+
+    #[test]
+    fn escaped_hashes_survive_a_round_trip() {
+        let line = "exec-once = notify-send \"##1\"";
+        let entry = AutostartEntry::parse(line, Path::new(""), true).unwrap();
+        assert_eq!(entry.command, "notify-send \"#1\"");
+        assert_eq!(entry.line(), line);
+        assert_eq!(
+            round_trip("exec = waybar # status bar").as_deref(),
+            Some("exec = waybar")
+        );
+    }
+
+    #[test]
+    fn only_marked_comments_are_disabled_entries() {
+        let line = "#disabled exec-once = waybar";
+        assert_eq!(round_trip(line).as_deref(), Some(line));
+        assert_eq!(round_trip("# exec-once = waybar"), None);
+    }
+}
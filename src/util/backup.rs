@@ -0,0 +1,269 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::{Mutex, PoisonError};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::setting_writer::{
+    HYPR_OVERRIDES_PATH, HYPR_SETTING_PATH, config_path, write_config_file,
+};
+
+/// Backups are kept next to the config, named like `hyprland.conf.20261018-140322.bak`
+pub const HYPR_BACKUP_PATH: &str = ".config/hypr/hyprsettings-backups";
+
+/// Files backed up before they are changed
+pub const BACKED_UP_FILES: [&str; 2] = [HYPR_SETTING_PATH, HYPR_OVERRIDES_PATH];
+
+/// Backups kept per file, the oldest are deleted first
+pub const MAX_BACKUPS: usize = 10;
+
+const BACKUP_EXTENSION: &str = "bak";
+
+/// Files backed up in this session, each is only backed up before its first change
+static BACKED_UP: Mutex<Vec<PathBuf>> = Mutex::new(Vec::new());
+
+/// A copy of a config file
+#[derive(Debug, Clone)]
+pub struct Backup {
+    pub path: PathBuf,
+    /// File the backup was made of
+    pub source: PathBuf,
+    /// `YYYYMMDD-HHMMSS` in UTC, with a counter for more backups in the same second
+    pub stamp: String,
+}
+
+impl Backup {
+    /// When the backup was made, e.g. `2026-10-18 14:03:22 UTC`
+    pub fn created(&self) -> String {
+        let s = &self.stamp;
+        if s.len() < 15 || !s.is_ascii() {
+            return s.clone();
+        }
+
+        format!(
+            "{}-{}-{} {}:{}:{} UTC",
+            &s[0..4],
+            &s[4..6],
+            &s[6..8],
+            &s[9..11],
+            &s[11..13],
+            &s[13..15]
+        )
+    }
+
+    /// Second the backup was made and its counter within that second, backups sort by it
+    fn order(&self) -> (&str, u32) {
+        match self.stamp.split_once('-') {
+            Some((date, rest)) => {
+                let (time, counter) = rest.split_once('-').unwrap_or((rest, "1"));
+                (
+                    &self.stamp[..date.len() + 1 + time.len()],
+                    counter.parse().unwrap_or(1),
+                )
+            }
+            None => (&self.stamp, 1),
+        }
+    }
+}
+
+/// Year, month and day of a day counted from 1970-01-01, see
+/// http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+fn civil_from_days(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+
+    (yoe + era * 400 + i64::from(month <= 2), month, day)
+}
+
+/// `YYYYMMDD-HHMMSS` in UTC, so backups sort by name
+fn stamp(time: SystemTime) -> String {
+    let seconds = time
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs() as i64;
+    let (year, month, day) = civil_from_days(seconds.div_euclid(86_400));
+    let time = seconds.rem_euclid(86_400);
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}",
+        year,
+        month,
+        day,
+        time / 3600,
+        time % 3600 / 60,
+        time % 60
+    )
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().to_string())
+        .unwrap_or_default()
+}
+
+/// Copy a config file into the backup directory and drop its oldest backups past the limit
+pub fn create_backup(path: &Path) -> anyhow::Result<()> {
+    let dir = config_path(HYPR_BACKUP_PATH)?;
+    fs::create_dir_all(&dir)?;
+
+    let stamp = stamp(SystemTime::now());
+    let content = fs::read(path)?;
+
+    // backups from the same second get a counter, unless one already holds this content
+    for count in 1.. {
+        let suffix = if count == 1 {
+            String::new()
+        } else {
+            format!("-{}", count)
+        };
+        let backup = dir.join(format!(
+            "{}.{}{}.{}",
+            file_name(path),
+            stamp,
+            suffix,
+            BACKUP_EXTENSION
+        ));
+
+        if !backup.exists() {
+            fs::write(&backup, &content)?;
+            break;
+        }
+        if fs::read(&backup)? == content {
+            break;
+        }
+    }
+
+    BACKED_UP
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .push(path.to_path_buf());
+
+    for old in get_backups(path)?.into_iter().skip(MAX_BACKUPS) {
+        fs::remove_file(&old.path)?;
+    }
+
+    Ok(())
+}
+
+/// Back up a file before its first change in this session, later changes are covered by it
+pub fn backup_once(path: &Path) -> anyhow::Result<()> {
+    let backed_up = BACKED_UP
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner)
+        .iter()
+        .any(|backed_up| backed_up == path);
+
+    if backed_up || !path.exists() {
+        return Ok(());
+    }
+
+    create_backup(path)
+}
+
+/// Backups of a file, newest first
+pub fn get_backups(source: &Path) -> anyhow::Result<Vec<Backup>> {
+    let dir = config_path(HYPR_BACKUP_PATH)?;
+    if !dir.exists() {
+        return Ok(Vec::new());
+    }
+
+    let prefix = format!("{}.", file_name(source));
+    let suffix = format!(".{}", BACKUP_EXTENSION);
+    let mut backups: Vec<Backup> = fs::read_dir(&dir)?
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| {
+            let name = file_name(&entry.path());
+            let stamp = name.strip_prefix(&prefix)?.strip_suffix(&suffix)?;
+
+            Some(Backup {
+                path: entry.path(),
+                source: source.to_path_buf(),
+                stamp: stamp.to_string(),
+            })
+        })
+        .collect();
+
+    backups.sort_by(|a, b| b.order().cmp(&a.order()));
+    Ok(backups)
+}
+
+/// Backups of every backed up file, newest first per file
+pub fn get_all_backups() -> anyhow::Result<Vec<Backup>> {
+    let mut backups = Vec::new();
+    for file in BACKED_UP_FILES {
+        backups.extend(get_backups(&config_path(file)?)?);
+    }
+    Ok(backups)
+}
+
+/// Put a backup back in place of its file. The current file is backed up first, so a restore
+/// can be undone by restoring that backup.
+pub fn restore_backup(backup: &Backup) -> anyhow::Result<()> {
+    let content = fs::read_to_string(&backup.path)?;
+    if backup.source.exists() {
+        create_backup(&backup.source)?;
+    }
+
+    write_config_file(&backup.source, &content)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::time::Duration;
+
+    fn backup(stamp: &str) -> Backup {
+        Backup {
+            path: PathBuf::from(format!("hyprland.conf.{}.bak", stamp)),
+            source: PathBuf::from("hyprland.conf"),
+            stamp: stamp.to_string(),
+        }
+    }
+
+    #[test]
+    fn days_are_converted_to_dates() {
+        assert_eq!(civil_from_days(0), (1970, 1, 1));
+        assert_eq!(civil_from_days(11_017), (2000, 3, 1));
+        assert_eq!(civil_from_days(19_782), (2024, 2, 29));
+        assert_eq!(civil_from_days(-1), (1969, 12, 31));
+    }
+
+    #[test]
+    fn stamps_are_formatted_in_utc() {
+        assert_eq!(stamp(UNIX_EPOCH), "19700101-000000");
+
+        let time = UNIX_EPOCH + Duration::from_secs(1_792_332_202);
+        assert_eq!(stamp(time), "20261018-140322");
+        assert_eq!(backup(&stamp(time)).created(), "2026-10-18 14:03:22 UTC");
+    }
+
+    #[test]
+    fn counters_sort_as_numbers() {
+        let mut backups = [
+            backup("20261018-140322-10"),
+            backup("20261018-140322"),
+            backup("20261018-140322-2"),
+            backup("20261018-140323"),
+            backup("20261017-235959-3"),
+        ];
+        backups.sort_by(|a, b| b.order().cmp(&a.order()));
+
+        let stamps: Vec<&str> = backups.iter().map(|b| b.stamp.as_str()).collect();
+        assert_eq!(
+            stamps,
+            [
+                "20261018-140323",
+                "20261018-140322-10",
+                "20261018-140322-2",
+                "20261018-140322",
+                "20261017-235959-3",
+            ]
+        );
+    }
+}
@@ -0,0 +1,389 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// The notation a colour was written in, kept so values are written back the way they were read
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ColourFormat {
+    /// `rgba(RRGGBBAA)`
+    Rgba,
+    /// `rgba(r, g, b, a)` with channels 0-255 and alpha 0.0-1.0
+    RgbaDecimal,
+    /// `rgb(RRGGBB)`
+    Rgb,
+    /// `rgb(r, g, b)`
+    RgbDecimal,
+    /// legacy `0xAARRGGBB`, also used for the bare `AARRGGBB` hyprctl prints
+    Hex,
+}
+
+/// A single Hyprland colour
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Colour {
+    pub red: u8,
+    pub green: u8,
+    pub blue: u8,
+    /// Alpha from 0.0 to 1.0, stored as a float so decimal alpha survives a round trip
+    pub alpha: f32,
+    pub format: ColourFormat,
+    /// Whether hex digits were written in upper case, e.g. `rgba(33CCFFEE)`
+    pub uppercase: bool,
+}
+
+/// A colour option value, either a single colour or a gradient of colours with an optional angle
+#[derive(Debug, Clone, PartialEq)]
+pub struct ColourValue {
+    pub colours: Vec<Colour>,
+    pub angle: Option<f32>,
+}
+
+impl Colour {
+    pub fn new(red: u8, green: u8, blue: u8, alpha: f32) -> Self {
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+            format: ColourFormat::Rgba,
+            uppercase: false,
+        }
+    }
+
+    /// Build a colour from a packed `0xAARRGGBB` value, which is how hyprctl reports colour options
+    pub fn from_argb(argb: u32) -> Self {
+        let [alpha, red, green, blue] = argb.to_be_bytes();
+        Self {
+            red,
+            green,
+            blue,
+            alpha: alpha as f32 / 255.0,
+            format: ColourFormat::Hex,
+            uppercase: false,
+        }
+    }
+
+    pub fn alpha_u8(&self) -> u8 {
+        (self.alpha.clamp(0.0, 1.0) * 255.0).round() as u8
+    }
+
+    /// Channels as hex digits in the case the colour was written in
+    fn hex(&self, channels: &[u8]) -> String {
+        let hex: String = channels.iter().map(|c| format!("{:02x}", c)).collect();
+        if self.uppercase {
+            hex.to_uppercase()
+        } else {
+            hex
+        }
+    }
+
+    /// Replace the channels while keeping the notation, unless the notation cannot hold the alpha
+    pub fn with_channels(self, red: u8, green: u8, blue: u8, alpha: f32) -> Self {
+        let format = match self.format {
+            ColourFormat::Rgb if alpha < 1.0 => ColourFormat::Rgba,
+            ColourFormat::RgbDecimal if alpha < 1.0 => ColourFormat::RgbaDecimal,
+            format => format,
+        };
+
+        Self {
+            red,
+            green,
+            blue,
+            alpha,
+            format,
+            ..self
+        }
+    }
+}
+
+fn parse_hex_channels(hex: &str) -> anyhow::Result<Vec<u8>> {
+    if !hex.len().is_multiple_of(2) || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+        return Err(anyhow::anyhow!("Invalid hex colour: {}", hex));
+    }
+
+    (0..hex.len())
+        .step_by(2)
+        .map(|i| {
+            u8::from_str_radix(&hex[i..i + 2], 16)
+                .map_err(|e| anyhow::anyhow!("Invalid hex colour {}: {}", hex, e))
+        })
+        .collect()
+}
+
+fn parse_decimal_channels(args: &str) -> anyhow::Result<Vec<f32>> {
+    args.split(',')
+        .map(|part| {
+            part.trim()
+                .parse::<f32>()
+                .map_err(|e| anyhow::anyhow!("Invalid colour channel {}: {}", part.trim(), e))
+        })
+        .collect()
+}
+
+fn decimal_channel(value: f32) -> anyhow::Result<u8> {
+    if !(0.0..=255.0).contains(&value) {
+        return Err(anyhow::anyhow!("Colour channel out of range: {}", value));
+    }
+    Ok(value.round() as u8)
+}
+
+impl FromStr for Colour {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        let value = value.trim();
+
+        if let Some(args) = value
+            .strip_prefix("rgba(")
+            .and_then(|v| v.strip_suffix(')'))
+        {
+            if args.contains(',') {
+                let channels = parse_decimal_channels(args)?;
+                let [r, g, b, a] = channels[..] else {
+                    return Err(anyhow::anyhow!("rgba() needs 4 channels: {}", value));
+                };
+                return Ok(Colour {
+                    red: decimal_channel(r)?,
+                    green: decimal_channel(g)?,
+                    blue: decimal_channel(b)?,
+                    alpha: a.clamp(0.0, 1.0),
+                    format: ColourFormat::RgbaDecimal,
+                    uppercase: false,
+                });
+            }
+
+            let channels = parse_hex_channels(args.trim())?;
+            let [r, g, b, a] = channels[..] else {
+                return Err(anyhow::anyhow!("rgba() needs 8 hex digits: {}", value));
+            };
+            return Ok(Colour {
+                red: r,
+                green: g,
+                blue: b,
+                alpha: a as f32 / 255.0,
+                format: ColourFormat::Rgba,
+                uppercase: is_uppercase(args),
+            });
+        }
+
+        if let Some(args) = value.strip_prefix("rgb(").and_then(|v| v.strip_suffix(')')) {
+            if args.contains(',') {
+                let channels = parse_decimal_channels(args)?;
+                let [r, g, b] = channels[..] else {
+                    return Err(anyhow::anyhow!("rgb() needs 3 channels: {}", value));
+                };
+                return Ok(Colour {
+                    red: decimal_channel(r)?,
+                    green: decimal_channel(g)?,
+                    blue: decimal_channel(b)?,
+                    alpha: 1.0,
+                    format: ColourFormat::RgbDecimal,
+                    uppercase: false,
+                });
+            }
+
+            let channels = parse_hex_channels(args.trim())?;
+            let [r, g, b] = channels[..] else {
+                return Err(anyhow::anyhow!("rgb() needs 6 hex digits: {}", value));
+            };
+            return Ok(Colour {
+                red: r,
+                green: g,
+                blue: b,
+                alpha: 1.0,
+                format: ColourFormat::Rgb,
+                uppercase: is_uppercase(args),
+            });
+        }
+
+        // legacy 0xAARRGGBB, hyprctl also prints gradients as bare AARRGGBB which the config
+        // does not accept, so both are written back with the prefix
+        let hex = value.strip_prefix("0x").unwrap_or(value);
+        if hex.len() == 8 && hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            let argb = u32::from_str_radix(hex, 16)
+                .map_err(|e| anyhow::anyhow!("Invalid hex colour {}: {}", value, e))?;
+            return Ok(Colour {
+                format: ColourFormat::Hex,
+                uppercase: is_uppercase(hex),
+                ..Colour::from_argb(argb)
+            });
+        }
+
+        Err(anyhow::anyhow!("Unknown colour format: {}", value))
+    }
+}
+
+/// Whether hex digits are written in upper case, a value without letters counts as lower case
+fn is_uppercase(hex: &str) -> bool {
+    hex.chars().any(|c| c.is_ascii_uppercase()) && !hex.chars().any(|c| c.is_ascii_lowercase())
+}
+
+impl fmt::Display for Colour {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let (r, g, b) = (self.red, self.green, self.blue);
+        let a = self.alpha_u8();
+
+        match self.format {
+            ColourFormat::Rgba => write!(f, "rgba({})", self.hex(&[r, g, b, a])),
+            ColourFormat::RgbaDecimal => write!(f, "rgba({}, {}, {}, {})", r, g, b, self.alpha),
+            ColourFormat::Rgb => write!(f, "rgb({})", self.hex(&[r, g, b])),
+            ColourFormat::RgbDecimal => write!(f, "rgb({}, {}, {})", r, g, b),
+            ColourFormat::Hex => write!(f, "0x{}", self.hex(&[a, r, g, b])),
+        }
+    }
+}
+
+/// Split a value on whitespace, keeping `rgba(r, g, b, a)` style arguments together
+fn split_tokens(value: &str) -> Vec<&str> {
+    let mut tokens = Vec::new();
+    let mut depth = 0;
+    let mut start = None;
+
+    for (i, c) in value.char_indices() {
+        match c {
+            '(' => depth += 1,
+            ')' => depth -= 1,
+            c if c.is_whitespace() && depth == 0 => {
+                if let Some(s) = start.take() {
+                    tokens.push(&value[s..i]);
+                }
+                continue;
+            }
+            _ => {}
+        }
+        start.get_or_insert(i);
+    }
+
+    if let Some(s) = start {
+        tokens.push(&value[s..]);
+    }
+
+    tokens
+}
+
+impl ColourValue {
+    pub fn single(colour: Colour) -> Self {
+        Self {
+            colours: vec![colour],
+            angle: None,
+        }
+    }
+
+    pub fn is_gradient(&self) -> bool {
+        self.colours.len() > 1
+    }
+}
+
+impl FromStr for ColourValue {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        let mut colours = Vec::new();
+        let mut angle = None;
+
+        for token in split_tokens(value) {
+            if let Some(degrees) = token.strip_suffix("deg") {
+                angle = Some(
+                    degrees
+                        .parse::<f32>()
+                        .map_err(|e| anyhow::anyhow!("Invalid gradient angle {}: {}", token, e))?,
+                );
+            } else if angle.is_some() {
+                return Err(anyhow::anyhow!("Gradient angle must come last: {}", value));
+            } else {
+                colours.push(token.parse::<Colour>()?);
+            }
+        }
+
+        if colours.is_empty() {
+            return Err(anyhow::anyhow!("No colour given: {}", value));
+        }
+
+        Ok(Self { colours, angle })
+    }
+}
+
+impl fmt::Display for ColourValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let colours = self
+            .colours
+            .iter()
+            .map(|c| c.to_string())
+            .collect::<Vec<_>>()
+            .join(" ");
+
+        match self.angle {
+            Some(angle) => write!(f, "{} {}deg", colours, angle),
+            None => write!(f, "{}", colours),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round_trip(value: &str) {
+        let parsed: ColourValue = value.parse().unwrap();
+        assert_eq!(parsed.to_string(), value);
+    }
+
+    #[test]
+    fn single_colours_round_trip() {
+        round_trip("rgba(33ccffee)");
+        round_trip("rgba(33CCFFEE)");
+        round_trip("rgba(51, 204, 255, 0.5)");
+        round_trip("rgb(33ccff)");
+        round_trip("rgb(00FF99)");
+        round_trip("rgb(51, 204, 255)");
+        round_trip("0xee33ccff");
+        round_trip("0xEE33CCFF");
+    }
+
+    #[test]
+    fn bare_hex_is_written_with_a_prefix() {
+        let gradient: ColourValue = "ee33ccff ee00ff99 45deg".parse().unwrap();
+        assert_eq!(gradient.to_string(), "0xee33ccff 0xee00ff99 45deg");
+    }
+
+    #[test]
+    fn gradients_round_trip() {
+        round_trip("rgba(33ccffee) rgba(00ff99ee) 45deg");
+        round_trip("0xff33ccff 0xff00ff99 90deg");
+        round_trip("rgba(51, 204, 255, 1) rgb(00ff99)");
+    }
+
+    #[test]
+    fn channels_are_parsed() {
+        let colour: Colour = "0x80ff0000".parse().unwrap();
+        assert_eq!((colour.red, colour.green, colour.blue), (255, 0, 0));
+        assert_eq!(colour.alpha_u8(), 0x80);
+
+        let gradient: ColourValue = "rgb(ff0000) rgb(0000ff) 45deg".parse().unwrap();
+        assert!(gradient.is_gradient());
+        assert_eq!(gradient.angle, Some(45.0));
+    }
+
+    #[test]
+    fn invalid_colours_are_rejected() {
+        assert!("rgba(33ccff)".parse::<Colour>().is_err());
+        assert!("rgb(300, 0, 0)".parse::<Colour>().is_err());
+        assert!(
+            "rgb(ff0000) 45deg rgb(0000ff)"
+                .parse::<ColourValue>()
+                .is_err()
+        );
+        assert!("".parse::<ColourValue>().is_err());
+    }
+
+    #[test]
+    fn changed_channels_keep_the_notation() {
+        let colour: Colour = "rgb(FF0000)".parse().unwrap();
+        assert_eq!(
+            colour.with_channels(0, 0, 255, 1.0).to_string(),
+            "rgb(0000FF)"
+        );
+        assert_eq!(
+            colour.with_channels(0, 0, 255, 0.5).to_string(),
+            "rgba(0000FF80)"
+        );
+    }
+}
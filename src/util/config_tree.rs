@@ -0,0 +1,311 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use dirs::home_dir;
+
+use crate::change_set;
+use crate::setting_writer::{HYPR_OVERRIDES_PATH, HYPR_SETTING_PATH, config_path};
+
+const SOURCE_KEYWORD: &str = "source";
+
+/// A line of a file in the config tree
+#[derive(Debug, Clone)]
+pub struct ConfigLine {
+    pub line: String,
+    /// File the line was found in
+    pub path: PathBuf,
+    /// Whether the line lives in the overrides file
+    pub overridden: bool,
+}
+
+/// A `keyword = value` line of the config tree with its comment stripped
+#[derive(Debug, Clone)]
+pub struct ConfigEntry {
+    pub keyword: String,
+    pub value: String,
+    /// File the entry was found in
+    pub path: PathBuf,
+    /// Whether the entry lives in the overrides file
+    pub overridden: bool,
+}
+
+/// Drop a trailing `# comment` from a value, `##` stands for a literal `#`
+pub fn strip_comment(value: &str) -> String {
+    let mut stripped = String::new();
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        if c == '#' && chars.next_if_eq(&'#').is_none() {
+            break;
+        }
+        stripped.push(c);
+    }
+    stripped.trim_end().to_string()
+}
+
+/// Replace `$name` with a `$name =` variable of the config, or an environment variable
+fn expand_variables(value: &str, variables: &HashMap<String, String>) -> String {
+    let mut expanded = String::new();
+    let mut rest = value;
+    while let Some(idx) = rest.find('$') {
+        expanded.push_str(&rest[..idx]);
+        rest = &rest[idx + 1..];
+
+        let len = rest
+            .find(|c: char| !c.is_ascii_alphanumeric() && c != '_')
+            .unwrap_or(rest.len());
+        let name = &rest[..len];
+        match variables
+            .get(name)
+            .cloned()
+            .or_else(|| std::env::var(name).ok())
+        {
+            Some(value) if !name.is_empty() => expanded.push_str(&value),
+            _ => {
+                expanded.push('$');
+                expanded.push_str(name);
+            }
+        }
+        rest = &rest[len..];
+    }
+    expanded.push_str(rest);
+    expanded
+}
+
+/// Resolve the path of a `source =` line, relative paths start at the including file
+fn source_path(value: &str, including: &Path) -> Option<PathBuf> {
+    let value = value.trim();
+    let path = match value.strip_prefix("~/") {
+        Some(rest) => home_dir()?.join(rest),
+        None => PathBuf::from(value),
+    };
+
+    if path.is_absolute() {
+        Some(path)
+    } else {
+        Some(including.parent()?.join(path))
+    }
+}
+
+/// Files matching a path whose file name may contain `*` wildcards
+fn expand_glob(path: &Path) -> Vec<PathBuf> {
+    let Some(pattern) = path.file_name().and_then(|name| name.to_str()) else {
+        return Vec::new();
+    };
+    if !pattern.contains('*') {
+        return vec![path.to_path_buf()];
+    }
+
+    let Some(dir) = path.parent() else {
+        return Vec::new();
+    };
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let parts: Vec<&str> = pattern.split('*').collect();
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok())
+        .map(|entry| entry.path())
+        .filter(|path| {
+            path.file_name()
+                .and_then(|name| name.to_str())
+                .is_some_and(|name| glob_matches(&parts, name))
+        })
+        .collect();
+    paths.sort();
+    paths
+}
+
+fn glob_matches(parts: &[&str], name: &str) -> bool {
+    let (Some(first), Some(last)) = (parts.first(), parts.last()) else {
+        return false;
+    };
+    let Some(mut rest) = name.strip_prefix(first) else {
+        return false;
+    };
+
+    for part in &parts[1..parts.len() - 1] {
+        match rest.find(part) {
+            Some(idx) => rest = &rest[idx + part.len()..],
+            None => return false,
+        }
+    }
+    rest.len() >= last.len() && rest.ends_with(last)
+}
+
+/// Collect the lines of a config file in order, the lines of sourced files take the place of
+/// their `source =` line
+fn collect_lines(
+    path: &Path,
+    overrides: &Path,
+    visited: &mut Vec<PathBuf>,
+    variables: &mut HashMap<String, String>,
+    lines: &mut Vec<ConfigLine>,
+) {
+    let path = fs::canonicalize(path).unwrap_or_else(|_| path.to_path_buf());
+    if visited.contains(&path) {
+        return;
+    }
+    visited.push(path.clone());
+
+    let overridden = path == overrides;
+    // the overrides file is read with the pending changes applied
+    let content = if overridden {
+        change_set::read_overrides()
+    } else {
+        fs::read_to_string(&path).map_err(anyhow::Error::from)
+    };
+    let Ok(content) = content else {
+        eprintln!("Failed to read {}", path.display());
+        return;
+    };
+
+    for line in content.lines() {
+        // only the overrides file is ours, comments elsewhere are just comments
+        if !overridden && line.trim().starts_with('#') {
+            continue;
+        }
+
+        if let Some((keyword, value)) = line.split_once('=') {
+            let keyword = keyword.trim();
+            // variables are read in order, a source line only sees the ones defined above it
+            if let Some(name) = keyword.strip_prefix('$') {
+                let value = expand_variables(&strip_comment(value), variables);
+                variables.insert(name.to_string(), value.trim().to_string());
+            } else if keyword == SOURCE_KEYWORD {
+                let value = expand_variables(&strip_comment(value), variables);
+                for source in source_path(&value, &path)
+                    .map(|source| expand_glob(&source))
+                    .unwrap_or_default()
+                {
+                    collect_lines(&source, overrides, visited, variables, lines);
+                }
+                continue;
+            }
+        }
+
+        lines.push(ConfigLine {
+            line: line.to_string(),
+            path: path.clone(),
+            overridden,
+        });
+    }
+}
+
+/// Every line of `hyprland.conf` and the files it sources, in the order Hyprland reads them.
+/// Commented lines are only kept for the overrides file, where they mark disabled entries.
+pub fn read_config_tree() -> anyhow::Result<Vec<ConfigLine>> {
+    let main = config_path(HYPR_SETTING_PATH)?;
+    let overrides = config_path(HYPR_OVERRIDES_PATH)?;
+    let overrides = fs::canonicalize(&overrides).unwrap_or(overrides);
+
+    let mut lines = Vec::new();
+    collect_lines(
+        &main,
+        &overrides,
+        &mut Vec::new(),
+        &mut HashMap::new(),
+        &mut lines,
+    );
+    Ok(lines)
+}
+
+/// Keyword and value of a line, commented out lines have neither
+fn split_entry(line: &str) -> Option<(String, String)> {
+    if line.trim().starts_with('#') {
+        return None;
+    }
+    let line = strip_comment(line);
+    let (keyword, value) = line.split_once('=')?;
+    Some((keyword.trim().to_string(), value.trim().to_string()))
+}
+
+/// Every `keyword = value` line of the config tree in the order Hyprland reads them
+pub fn read_config_entries() -> anyhow::Result<Vec<ConfigEntry>> {
+    Ok(read_config_tree()?
+        .into_iter()
+        .filter_map(|line| {
+            let (keyword, value) = split_entry(&line.line)?;
+            Some(ConfigEntry {
+                keyword,
+                value,
+                path: line.path,
+                overridden: line.overridden,
+            })
+        })
+        .collect())
+}
+
+/// Path of a config file shortened to start at the home directory, e.g. `~/.config/hypr/a.conf`
+pub fn display_path(path: &Path) -> String {
+    home_dir()
+        .and_then(|home| path.strip_prefix(home).ok().map(|p| p.to_path_buf()))
+        .map(|path| format!("~/{}", path.display()))
+        .unwrap_or_else(|| path.display().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trailing_comments_are_stripped() {
+        assert_eq!(strip_comment(" ~/a.conf # theme"), " ~/a.conf");
+        assert_eq!(strip_comment("~/a##b.conf"), "~/a#b.conf");
+    }
+
+    #[test]
+    fn entries_keep_escaped_hashes() {
+        assert_eq!(
+            split_entry("windowrulev2 = float, title:^(##1)$ # note"),
+            Some((
+                "windowrulev2".to_string(),
+                "float, title:^(#1)$".to_string()
+            ))
+        );
+        assert_eq!(split_entry("# windowrulev2 = float, class:foo"), None);
+    }
+
+    #[test]
+    fn sourced_files_are_read_in_place() {
+        let dir = std::env::temp_dir().join(format!("hyprconfig-tree-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(
+            dir.join("hyprland.conf"),
+            "$rules = rules.conf\nsource = ./$rules # split config\nlayerrule = blur, waybar\n",
+        )
+        .unwrap();
+        fs::write(dir.join("rules.conf"), "windowrulev2 = float, class:foo\n").unwrap();
+
+        let mut lines = Vec::new();
+        collect_lines(
+            &dir.join("hyprland.conf"),
+            &dir.join("overrides.conf"),
+            &mut Vec::new(),
+            &mut HashMap::new(),
+            &mut lines,
+        );
+        fs::remove_dir_all(&dir).unwrap();
+
+        let entries: Vec<_> = lines
+            .iter()
+            .filter_map(|line| split_entry(&line.line))
+            .map(|(keyword, _)| keyword)
+            .collect();
+        assert_eq!(entries, ["$rules", "windowrulev2", "layerrule"]);
+    }
+
+    #[test]
+    fn variables_are_expanded() {
+        let variables = HashMap::from([("dir".to_string(), "/etc/hypr".to_string())]);
+        assert_eq!(
+            expand_variables("$dir/a.conf", &variables),
+            "/etc/hypr/a.conf"
+        );
+        assert_eq!(
+            expand_variables("$HYPRCONFIG_UNSET_VAR/a", &variables),
+            "$HYPRCONFIG_UNSET_VAR/a"
+        );
+    }
+}
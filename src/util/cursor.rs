@@ -0,0 +1,218 @@
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+use dirs::home_dir;
+
+pub const DEFAULT_CURSOR_SIZE: u32 = 24;
+
+/// Cursor file names tried in order when looking for a preview image
+const PREVIEW_CURSORS: [&str; 3] = ["left_ptr", "default", "arrow"];
+const XCURSOR_IMAGE_TYPE: u32 = 0xfffd_0002;
+const PREVIEW_NOMINAL_SIZE: u32 = 32;
+/// Largest width or height libXcursor accepts, anything above is a broken file
+const XCURSOR_MAX_SIZE: u32 = 0x7fff;
+
+#[derive(Debug, Clone)]
+pub struct CursorTheme {
+    /// Directory name, which is what the cursor environment variables expect
+    pub name: String,
+    pub path: PathBuf,
+    pub xcursor: bool,
+    pub hyprcursor: bool,
+}
+
+/// Directories searched for cursor themes, user directories first so they shadow system themes
+fn icon_dirs() -> Vec<PathBuf> {
+    let mut dirs = Vec::new();
+
+    if let Some(home) = home_dir() {
+        dirs.push(home.join(".local/share/icons"));
+        dirs.push(home.join(".icons"));
+    }
+
+    let data_dirs =
+        std::env::var("XDG_DATA_DIRS").unwrap_or_else(|_| "/usr/local/share:/usr/share".into());
+    for dir in data_dirs.split(':').filter(|d| !d.is_empty()) {
+        dirs.push(Path::new(dir).join("icons"));
+    }
+
+    dirs
+}
+
+/// Scan the icon directories for installed XCursor and hyprcursor themes
+pub fn get_cursor_themes() -> Vec<CursorTheme> {
+    let mut seen = HashSet::new();
+    let mut themes = Vec::new();
+
+    for dir in icon_dirs() {
+        let Ok(entries) = std::fs::read_dir(&dir) else {
+            continue;
+        };
+
+        for entry in entries.flatten() {
+            let path = entry.path();
+            let name = entry.file_name().to_string_lossy().to_string();
+
+            let xcursor = path.join("cursors").is_dir();
+            let hyprcursor =
+                path.join("manifest.hl").is_file() || path.join("manifest.toml").is_file();
+
+            if !(xcursor || hyprcursor) || !seen.insert(name.clone()) {
+                continue;
+            }
+
+            themes.push(CursorTheme {
+                name,
+                path,
+                xcursor,
+                hyprcursor,
+            });
+        }
+    }
+
+    themes.sort_by_key(|t| t.name.to_lowercase());
+    themes
+}
+
+/// The cursor theme and size the current session was started with
+pub fn current_cursor() -> (Option<String>, u32) {
+    let theme = std::env::var("HYPRCURSOR_THEME")
+        .or_else(|_| std::env::var("XCURSOR_THEME"))
+        .ok();
+    let size = std::env::var("HYPRCURSOR_SIZE")
+        .or_else(|_| std::env::var("XCURSOR_SIZE"))
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(DEFAULT_CURSOR_SIZE);
+
+    (theme, size)
+}
+
+/// Apply a cursor theme to the running compositor
+pub fn set_cursor(theme: &str, size: u32) -> anyhow::Result<()> {
+    let output = Command::new("hyprctl")
+        .args(["setcursor", theme, &size.to_string()])
+        .output()?;
+
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        return Err(anyhow::anyhow!("Failed to set cursor: {}", stderr));
+    }
+
+    Ok(())
+}
+
+/// Render the theme's pointer cursor as a BMP image so it can be shown without extra decoders.
+/// Only XCursor themes have previews, hyprcursor shapes are stored in compressed archives.
+pub fn cursor_preview(theme: &CursorTheme) -> Option<Vec<u8>> {
+    if !theme.xcursor {
+        return None;
+    }
+
+    PREVIEW_CURSORS.iter().find_map(|cursor| {
+        let data = std::fs::read(theme.path.join("cursors").join(cursor)).ok()?;
+        let (width, height, pixels) = parse_xcursor(&data)?;
+        encode_bmp(width, height, &pixels)
+    })
+}
+
+fn read_u32(data: &[u8], offset: usize) -> Option<u32> {
+    let bytes = data.get(offset..offset + 4)?;
+    Some(u32::from_le_bytes(bytes.try_into().ok()?))
+}
+
+/// Decode the image closest to the preview size from an XCursor file as ARGB pixels
+fn parse_xcursor(data: &[u8]) -> Option<(u32, u32, Vec<u32>)> {
+    if data.get(0..4)? != b"Xcur" {
+        return None;
+    }
+
+    let toc_count = read_u32(data, 12)? as usize;
+
+    // table of contents entries are (type, nominal size, position)
+    let position = (0..toc_count)
+        .filter_map(|i| {
+            let entry = 16 + i * 12;
+            (read_u32(data, entry)? == XCURSOR_IMAGE_TYPE)
+                .then(|| Some((read_u32(data, entry + 4)?, read_u32(data, entry + 8)?)))?
+        })
+        .min_by_key(|(size, _)| size.abs_diff(PREVIEW_NOMINAL_SIZE))
+        .map(|(_, position)| position as usize)?;
+
+    let width = read_u32(data, position + 16)?;
+    let height = read_u32(data, position + 20)?;
+    if width > XCURSOR_MAX_SIZE || height > XCURSOR_MAX_SIZE {
+        return None;
+    }
+
+    // check the file holds every pixel before allocating them
+    let pixel_start = position + 36;
+    let pixel_count = (width as usize).checked_mul(height as usize)?;
+    if pixel_count.checked_mul(4)?.checked_add(pixel_start)? > data.len() {
+        return None;
+    }
+
+    let pixels = (0..pixel_count)
+        .map(|i| read_u32(data, pixel_start + i * 4))
+        .collect::<Option<Vec<u32>>>()?;
+
+    Some((width, height, pixels))
+}
+
+/// Encode premultiplied ARGB pixels as a top-down 32 bit BMP with an alpha channel, `None` if
+/// the image is too large for a BMP or the pixels don't match its size
+fn encode_bmp(width: u32, height: u32, pixels: &[u32]) -> Option<Vec<u8>> {
+    const FILE_HEADER_SIZE: u32 = 14;
+    const INFO_HEADER_SIZE: u32 = 108;
+
+    if width > XCURSOR_MAX_SIZE || height > XCURSOR_MAX_SIZE {
+        return None;
+    }
+    let pixel_count = width.checked_mul(height)?;
+    if pixels.len() != pixel_count as usize {
+        return None;
+    }
+    let image_size = pixel_count.checked_mul(4)?;
+    let file_size = image_size.checked_add(FILE_HEADER_SIZE + INFO_HEADER_SIZE)?;
+    let mut bmp = Vec::with_capacity(file_size as usize);
+
+    bmp.extend_from_slice(b"BM");
+    bmp.extend_from_slice(&file_size.to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    bmp.extend_from_slice(&(FILE_HEADER_SIZE + INFO_HEADER_SIZE).to_le_bytes());
+
+    // BITMAPV4HEADER, negative height means rows are stored top to bottom
+    bmp.extend_from_slice(&INFO_HEADER_SIZE.to_le_bytes());
+    bmp.extend_from_slice(&(width as i32).to_le_bytes());
+    bmp.extend_from_slice(&(-(height as i32)).to_le_bytes());
+    bmp.extend_from_slice(&1u16.to_le_bytes());
+    bmp.extend_from_slice(&32u16.to_le_bytes());
+    bmp.extend_from_slice(&3u32.to_le_bytes()); // BI_BITFIELDS
+    bmp.extend_from_slice(&image_size.to_le_bytes());
+    bmp.extend_from_slice(&2835i32.to_le_bytes());
+    bmp.extend_from_slice(&2835i32.to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    bmp.extend_from_slice(&0u32.to_le_bytes());
+    bmp.extend_from_slice(&0x00ff_0000u32.to_le_bytes());
+    bmp.extend_from_slice(&0x0000_ff00u32.to_le_bytes());
+    bmp.extend_from_slice(&0x0000_00ffu32.to_le_bytes());
+    bmp.extend_from_slice(&0xff00_0000u32.to_le_bytes());
+    bmp.extend_from_slice(b"BGRs");
+    bmp.extend_from_slice(&[0u8; 48]); // colour space endpoints and gamma
+
+    for pixel in pixels {
+        let [b, g, r, a] = pixel.to_le_bytes();
+        // XCursor pixels are premultiplied, BMP expects straight alpha
+        let unpremultiply = |c: u8| {
+            if a == 0 {
+                0
+            } else {
+                ((c as u32 * 255) / a as u32).min(255) as u8
+            }
+        };
+        bmp.extend_from_slice(&[unpremultiply(b), unpremultiply(g), unpremultiply(r), a]);
+    }
+
+    Some(bmp)
+}
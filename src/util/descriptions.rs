@@ -0,0 +1,86 @@
+use std::process::Command;
+
+use serde::Deserialize;
+use serde_json::Value;
+
+/// Read the descriptions from this file instead of asking the compositor, e.g.
+/// `fixtures/hyprctl_descriptions.json` when Hyprland is not running
+pub const DESCRIPTIONS_FILE_VAR: &str = "HYPRSETTINGS_DESCRIPTIONS";
+
+/// An option as described by `hyprctl descriptions -j`
+#[derive(Debug, Clone, Deserialize)]
+pub struct Description {
+    /// Option path, e.g. `general:border_size`
+    pub value: String,
+    pub description: String,
+    /// 0 bool, 1 int, 2 float, 3 and 4 string, 5 colour, 6 choice, 7 gradient, 8 vector
+    #[serde(rename = "type")]
+    pub option_type: u8,
+    /// Default, range and choices, their fields depend on the type
+    #[serde(default)]
+    pub data: Value,
+}
+
+impl Description {
+    pub fn default_value(&self) -> String {
+        match self.data.get("default") {
+            Some(Value::String(value)) => value.clone(),
+            Some(Value::Bool(value)) => value.to_string(),
+            Some(Value::Number(value)) => value.to_string(),
+            _ => String::new(),
+        }
+    }
+
+    pub fn min(&self) -> Option<f64> {
+        self.data.get("min").and_then(Value::as_f64)
+    }
+
+    pub fn max(&self) -> Option<f64> {
+        self.data.get("max").and_then(Value::as_f64)
+    }
+
+    /// Choices with the number written for them, e.g. `(0, "off")`
+    pub fn choices(&self) -> Vec<(i64, String)> {
+        let first = self
+            .data
+            .get("firstIndex")
+            .and_then(Value::as_i64)
+            .unwrap_or(0);
+        let options = self
+            .data
+            .get("options")
+            .and_then(Value::as_str)
+            .unwrap_or_default();
+
+        options
+            .split(',')
+            .filter(|o| !o.is_empty())
+            .enumerate()
+            .map(|(idx, option)| (first + idx as i64, option.trim().to_string()))
+            .collect()
+    }
+}
+
+/// Descriptions of every option the running Hyprland knows
+pub fn get_descriptions() -> anyhow::Result<Vec<Description>> {
+    let json_str = match std::env::var(DESCRIPTIONS_FILE_VAR) {
+        Ok(path) => std::fs::read_to_string(path)?,
+        Err(_) => {
+            let output = Command::new("hyprctl")
+                .args(["descriptions", "-j"])
+                .output()?;
+
+            if !output.status.success() {
+                return Err(anyhow::anyhow!("Failed to execute hyprctl descriptions"));
+            }
+            String::from_utf8(output.stdout)?
+        }
+    };
+
+    parse_descriptions(&json_str)
+}
+
+/// Descriptions from the JSON printed by `hyprctl descriptions -j`
+pub fn parse_descriptions(json: &str) -> anyhow::Result<Vec<Description>> {
+    Ok(serde_json::from_str(json)?)
+}
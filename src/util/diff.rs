@@ -0,0 +1,153 @@
+/// A line of a unified diff
+#[derive(Debug, Clone, PartialEq)]
+pub enum DiffLine {
+    /// `@@ -1,3 +1,4 @@` header starting a hunk
+    Hunk(String),
+    Context(String),
+    Added(String),
+    Removed(String),
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Op {
+    Same,
+    Added,
+    Removed,
+}
+
+/// Lines changed between two versions of a file, with `context` unchanged lines around each
+/// change. Identical files give an empty diff.
+pub fn unified_diff(old: &str, new: &str, context: usize) -> Vec<DiffLine> {
+    let old: Vec<&str> = old.lines().collect();
+    let new: Vec<&str> = new.lines().collect();
+
+    // length of the longest common subsequence of old[i..] and new[j..]
+    let mut lcs = vec![vec![0usize; new.len() + 1]; old.len() + 1];
+    for i in (0..old.len()).rev() {
+        for j in (0..new.len()).rev() {
+            lcs[i][j] = if old[i] == new[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    // every op with the old and new line it starts at
+    let mut ops = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < old.len() || j < new.len() {
+        if i < old.len() && j < new.len() && old[i] == new[j] {
+            ops.push((Op::Same, i, j));
+            i += 1;
+            j += 1;
+        } else if j == new.len() || (i < old.len() && lcs[i + 1][j] >= lcs[i][j + 1]) {
+            ops.push((Op::Removed, i, j));
+            i += 1;
+        } else {
+            ops.push((Op::Added, i, j));
+            j += 1;
+        }
+    }
+
+    let changes: Vec<usize> = ops
+        .iter()
+        .enumerate()
+        .filter(|(_, (op, _, _))| *op != Op::Same)
+        .map(|(idx, _)| idx)
+        .collect();
+
+    // changes closer than twice the context share a hunk
+    let mut hunks: Vec<(usize, usize)> = Vec::new();
+    for idx in changes {
+        let start = idx.saturating_sub(context);
+        let end = (idx + context + 1).min(ops.len());
+
+        match hunks.last_mut() {
+            Some((_, last_end)) if start <= *last_end => *last_end = end,
+            _ => hunks.push((start, end)),
+        }
+    }
+
+    let mut lines = Vec::new();
+    for (start, end) in hunks {
+        let hunk = &ops[start..end];
+        let old_count = hunk.iter().filter(|(op, _, _)| *op != Op::Added).count();
+        let new_count = hunk.iter().filter(|(op, _, _)| *op != Op::Removed).count();
+        let (_, old_start, new_start) = hunk[0];
+
+        lines.push(DiffLine::Hunk(format!(
+            "@@ -{},{} +{},{} @@",
+            old_start + 1,
+            old_count,
+            new_start + 1,
+            new_count
+        )));
+        lines.extend(hunk.iter().map(|(op, i, j)| match op {
+            Op::Same => DiffLine::Context(old[*i].to_string()),
+            Op::Removed => DiffLine::Removed(old[*i].to_string()),
+            Op::Added => DiffLine::Added(new[*j].to_string()),
+        }));
+    }
+
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_input_gives_an_empty_diff() {
+        assert!(unified_diff("", "", 3).is_empty());
+        assert!(unified_diff("a\nb\nc\n", "a\nb\nc\n", 3).is_empty());
+    }
+
+    #[test]
+    fn hunk_headers_count_old_and_new_lines() {
+        let old = "a\nb\nc\nd\ne\n";
+        let new = "a\nb\nC\nd\ne\nf\n";
+
+        assert_eq!(
+            unified_diff(old, new, 1),
+            [
+                DiffLine::Hunk("@@ -2,4 +2,5 @@".to_string()),
+                DiffLine::Context("b".to_string()),
+                DiffLine::Removed("c".to_string()),
+                DiffLine::Added("C".to_string()),
+                DiffLine::Context("d".to_string()),
+                DiffLine::Context("e".to_string()),
+                DiffLine::Added("f".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn distant_changes_get_their_own_hunk() {
+        let old = "1\n2\n3\n4\n5\n6\n7\n8\n";
+        let new = "0\n2\n3\n4\n5\n6\n7\n9\n";
+
+        let hunks: Vec<DiffLine> = unified_diff(old, new, 1)
+            .into_iter()
+            .filter(|line| matches!(line, DiffLine::Hunk(_)))
+            .collect();
+        assert_eq!(
+            hunks,
+            [
+                DiffLine::Hunk("@@ -1,2 +1,2 @@".to_string()),
+                DiffLine::Hunk("@@ -7,2 +7,2 @@".to_string()),
+            ]
+        );
+    }
+
+    #[test]
+    fn added_file_starts_at_line_one() {
+        assert_eq!(
+            unified_diff("", "a\n", 3),
+            [
+                DiffLine::Hunk("@@ -1,0 +1,1 @@".to_string()),
+                DiffLine::Added("a".to_string()),
+            ]
+        );
+    }
+}
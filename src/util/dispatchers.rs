@@ -0,0 +1,489 @@
+use std::process::Command;
+
+/// What a dispatcher expects as its argument
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ArgKind {
+    /// Takes no argument
+    None,
+    /// A shell command
+    Command,
+    /// A workspace such as `1`, `+1`, `name:web` or `special:term`
+    Workspace,
+    /// A direction, `l`, `r`, `u` or `d`
+    Direction,
+    /// A window such as `class:firefox` or `title:^(vim)$`
+    Window,
+    /// A monitor name, direction or relative id
+    Monitor,
+    /// Two deltas in pixels or percent, optionally prefixed with `exact`
+    Resize,
+    /// A number, optionally prefixed with `+` or `-` to make it relative
+    Number,
+    /// One of a fixed set of values
+    Choice(&'static [&'static str]),
+    /// Free form text
+    Text,
+}
+
+/// A Hyprland dispatcher and the argument it expects
+#[derive(Debug)]
+pub struct Dispatcher {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub arg: ArgKind,
+    /// Whether the argument may be left empty
+    pub optional: bool,
+}
+
+const fn dispatcher(
+    name: &'static str,
+    description: &'static str,
+    arg: ArgKind,
+    optional: bool,
+) -> Dispatcher {
+    Dispatcher {
+        name,
+        description,
+        arg,
+        optional,
+    }
+}
+
+/// Dispatchers offered when editing binds, see the Hyprland wiki for the full list
+pub static DISPATCHERS: [Dispatcher; 48] = [
+    dispatcher("exec", "Run a shell command.", ArgKind::Command, false),
+    dispatcher(
+        "execr",
+        "Run a raw shell command without window rules.",
+        ArgKind::Command,
+        false,
+    ),
+    dispatcher("pass", "Pass the key to a window.", ArgKind::Window, false),
+    dispatcher(
+        "sendshortcut",
+        "Send a key combination to a window, e.g. `SUPER, Q, class:kitty`.",
+        ArgKind::Text,
+        false,
+    ),
+    dispatcher(
+        "killactive",
+        "Close the active window.",
+        ArgKind::None,
+        true,
+    ),
+    dispatcher(
+        "forcekillactive",
+        "Kill the active window's process.",
+        ArgKind::None,
+        true,
+    ),
+    dispatcher("closewindow", "Close a window.", ArgKind::Window, false),
+    dispatcher(
+        "killwindow",
+        "Kill a window's process.",
+        ArgKind::Window,
+        false,
+    ),
+    dispatcher(
+        "workspace",
+        "Switch to a workspace.",
+        ArgKind::Workspace,
+        false,
+    ),
+    dispatcher(
+        "movetoworkspace",
+        "Move the active window to a workspace and follow it.",
+        ArgKind::Workspace,
+        false,
+    ),
+    dispatcher(
+        "movetoworkspacesilent",
+        "Move the active window to a workspace without following it.",
+        ArgKind::Workspace,
+        false,
+    ),
+    dispatcher(
+        "togglespecialworkspace",
+        "Show or hide a special workspace, the default one when empty.",
+        ArgKind::Text,
+        true,
+    ),
+    dispatcher(
+        "focusworkspaceoncurrentmonitor",
+        "Switch to a workspace on the focused monitor.",
+        ArgKind::Workspace,
+        false,
+    ),
+    dispatcher(
+        "movecurrentworkspacetomonitor",
+        "Move the active workspace to a monitor.",
+        ArgKind::Monitor,
+        false,
+    ),
+    dispatcher(
+        "togglefloating",
+        "Toggle floating, the active window when empty.",
+        ArgKind::Window,
+        true,
+    ),
+    dispatcher(
+        "setfloating",
+        "Make a window floating, the active window when empty.",
+        ArgKind::Window,
+        true,
+    ),
+    dispatcher(
+        "settiled",
+        "Make a window tiled, the active window when empty.",
+        ArgKind::Window,
+        true,
+    ),
+    dispatcher(
+        "fullscreen",
+        "Toggle fullscreen, 0 is fullscreen, 1 maximize.",
+        ArgKind::Choice(&["0", "1"]),
+        true,
+    ),
+    dispatcher(
+        "pin",
+        "Pin a floating window to all workspaces.",
+        ArgKind::Window,
+        true,
+    ),
+    dispatcher(
+        "centerwindow",
+        "Center the active floating window.",
+        ArgKind::Choice(&["1"]),
+        true,
+    ),
+    dispatcher("pseudo", "Toggle pseudo tiling.", ArgKind::Window, true),
+    dispatcher(
+        "movefocus",
+        "Move focus in a direction.",
+        ArgKind::Direction,
+        false,
+    ),
+    dispatcher(
+        "movewindow",
+        "Move the active window in a direction or to a monitor with `mon:`.",
+        ArgKind::Direction,
+        false,
+    ),
+    dispatcher(
+        "swapwindow",
+        "Swap the active window with the one in a direction.",
+        ArgKind::Direction,
+        false,
+    ),
+    dispatcher(
+        "resizeactive",
+        "Resize the active window, e.g. `10 -10` or `exact 50% 50%`.",
+        ArgKind::Resize,
+        false,
+    ),
+    dispatcher(
+        "moveactive",
+        "Move the active window, e.g. `10 -10` or `exact 0 0`.",
+        ArgKind::Resize,
+        false,
+    ),
+    dispatcher(
+        "cyclenext",
+        "Focus the next window.",
+        ArgKind::Choice(&["prev", "tiled", "floating", "visible"]),
+        true,
+    ),
+    dispatcher(
+        "swapnext",
+        "Swap the active window with the next one.",
+        ArgKind::Choice(&["prev"]),
+        true,
+    ),
+    dispatcher("focuswindow", "Focus a window.", ArgKind::Window, false),
+    dispatcher("focusmonitor", "Focus a monitor.", ArgKind::Monitor, false),
+    dispatcher(
+        "focusurgentorlast",
+        "Focus the urgent window or the last focused one.",
+        ArgKind::None,
+        true,
+    ),
+    dispatcher(
+        "focuscurrentorlast",
+        "Focus the last focused window.",
+        ArgKind::None,
+        true,
+    ),
+    dispatcher(
+        "splitratio",
+        "Change the split ratio, e.g. `+0.1` or `exact 0.5`.",
+        ArgKind::Number,
+        false,
+    ),
+    dispatcher(
+        "togglesplit",
+        "Toggle the split direction (dwindle).",
+        ArgKind::None,
+        true,
+    ),
+    dispatcher(
+        "layoutmsg",
+        "Send a message to the current layout.",
+        ArgKind::Text,
+        false,
+    ),
+    dispatcher(
+        "movecursortocorner",
+        "Move the cursor to a corner of the active window.",
+        ArgKind::Choice(&["0", "1", "2", "3"]),
+        false,
+    ),
+    dispatcher(
+        "workspaceopt",
+        "Toggle a workspace option.",
+        ArgKind::Choice(&["allfloat", "allpseudo"]),
+        false,
+    ),
+    dispatcher(
+        "togglegroup",
+        "Turn the active window into a group or back.",
+        ArgKind::None,
+        true,
+    ),
+    dispatcher(
+        "changegroupactive",
+        "Switch to the next (f) or previous (b) window in a group.",
+        ArgKind::Choice(&["f", "b"]),
+        false,
+    ),
+    dispatcher(
+        "moveintogroup",
+        "Move the active window into the group in a direction.",
+        ArgKind::Direction,
+        false,
+    ),
+    dispatcher(
+        "moveoutofgroup",
+        "Move a window out of its group.",
+        ArgKind::Window,
+        true,
+    ),
+    dispatcher(
+        "lockgroups",
+        "Lock or unlock all groups.",
+        ArgKind::Choice(&["lock", "unlock", "toggle"]),
+        false,
+    ),
+    dispatcher(
+        "submap",
+        "Enter a submap, `reset` goes back to the global one.",
+        ArgKind::Text,
+        false,
+    ),
+    dispatcher(
+        "global",
+        "Trigger a global shortcut, `app:shortcut`.",
+        ArgKind::Text,
+        false,
+    ),
+    dispatcher(
+        "dpms",
+        "Turn monitors on or off.",
+        ArgKind::Choice(&["on", "off", "toggle"]),
+        false,
+    ),
+    dispatcher(
+        "forcerendererreload",
+        "Reload all shaders.",
+        ArgKind::None,
+        true,
+    ),
+    dispatcher("exit", "Exit Hyprland.", ArgKind::None, true),
+    dispatcher(
+        "resizewindow",
+        "Resize with the mouse, for bindm only.",
+        ArgKind::None,
+        true,
+    ),
+];
+
+/// Dispatchers usable with `bindm`, they take no argument
+pub const MOUSE_DISPATCHERS: [&str; 2] = ["movewindow", "resizewindow"];
+
+const WINDOW_PREFIXES: [&str; 9] = [
+    "class:",
+    "initialclass:",
+    "title:",
+    "initialtitle:",
+    "tag:",
+    "pid:",
+    "address:",
+    "activewindow",
+    "floating",
+];
+
+pub fn find_dispatcher(name: &str) -> Option<&'static Dispatcher> {
+    DISPATCHERS.iter().find(|d| d.name == name)
+}
+
+fn is_number(value: &str) -> bool {
+    value.parse::<f64>().is_ok()
+}
+
+/// Pixel or percent value, optionally signed
+fn is_size(value: &str) -> bool {
+    is_number(value.strip_suffix('%').unwrap_or(value))
+}
+
+/// Whether `arg` is a workspace as dispatchers and rules accept it
+pub fn is_workspace(arg: &str) -> bool {
+    // movetoworkspace also accepts a window after a comma
+    let workspace = arg.split(',').next().unwrap_or_default().trim();
+
+    if ["special", "empty", "previous", "previous_per_monitor"].contains(&workspace) {
+        return true;
+    }
+    if let Some(name) = workspace
+        .strip_prefix("name:")
+        .or_else(|| workspace.strip_prefix("special:"))
+    {
+        return !name.is_empty();
+    }
+
+    // relative to the monitor (m), the open workspaces (e) or with an offset (r)
+    let relative = workspace
+        .strip_prefix('m')
+        .or_else(|| workspace.strip_prefix('e'))
+        .or_else(|| workspace.strip_prefix('r'))
+        .unwrap_or(workspace);
+
+    relative.parse::<i64>().is_ok()
+}
+
+/// Whether an argument selects a window, anything without a known prefix is a class regex
+fn is_window(arg: &str) -> bool {
+    if WINDOW_PREFIXES.iter().any(|prefix| arg.starts_with(prefix)) {
+        // a prefix like `class:` needs a value after it
+        return !arg.ends_with(':');
+    }
+    !arg.is_empty()
+}
+
+/// Check an argument against the dispatcher's schema, unknown dispatchers are not checked
+pub fn validate_args(name: &str, arg: &str, mouse: bool) -> anyhow::Result<()> {
+    let arg = arg.trim();
+
+    if mouse {
+        if !MOUSE_DISPATCHERS.contains(&name) {
+            return Err(anyhow::anyhow!(
+                "Mouse binds only support {}",
+                MOUSE_DISPATCHERS.join(" and ")
+            ));
+        }
+        return Ok(());
+    }
+
+    if MOUSE_DISPATCHERS.contains(&name)
+        && find_dispatcher(name).is_some_and(|d| d.arg == ArgKind::None)
+    {
+        return Err(anyhow::anyhow!("{} only works in mouse binds", name));
+    }
+
+    let Some(dispatcher) = find_dispatcher(name) else {
+        return Ok(());
+    };
+
+    if arg.is_empty() {
+        if dispatcher.optional {
+            return Ok(());
+        }
+        return Err(anyhow::anyhow!("{} needs an argument", name));
+    }
+
+    let valid = match dispatcher.arg {
+        ArgKind::None => false,
+        ArgKind::Command | ArgKind::Text | ArgKind::Monitor => true,
+        ArgKind::Workspace => is_workspace(arg),
+        ArgKind::Direction => {
+            ["l", "r", "u", "d"].contains(&arg) || (name == "movewindow" && arg.starts_with("mon:"))
+        }
+        ArgKind::Window => is_window(arg),
+        ArgKind::Resize => {
+            let sizes: Vec<&str> = arg
+                .strip_prefix("exact")
+                .unwrap_or(arg)
+                .split_whitespace()
+                .collect();
+            sizes.len() == 2 && sizes.iter().all(|s| is_size(s))
+        }
+        ArgKind::Number => is_number(arg.strip_prefix("exact").unwrap_or(arg).trim()),
+        ArgKind::Choice(choices) => choices.contains(&arg),
+    };
+
+    if valid {
+        Ok(())
+    } else if dispatcher.arg == ArgKind::None {
+        Err(anyhow::anyhow!("{} does not take an argument", name))
+    } else {
+        Err(anyhow::anyhow!("Invalid argument for {}: {}", name, arg))
+    }
+}
+
+/// Run a dispatcher once via `hyprctl dispatch`
+pub fn dispatch(name: &str, arg: &str) -> anyhow::Result<()> {
+    let output = Command::new("hyprctl")
+        .args(["dispatch", name, arg])
+        .output()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    if !output.status.success() || stdout.trim() != "ok" {
+        return Err(anyhow::anyhow!(
+            "Failed to dispatch {} {}: {}",
+            name,
+            arg,
+            stdout.trim()
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn workspace_arguments() {
+        for arg in [
+            "3",
+            "name:web",
+            "special:scratch",
+            "special",
+            "m+1",
+            "e-1",
+            "r+2",
+            "previous",
+        ] {
+            assert!(validate_args("workspace", arg, false).is_ok(), "{}", arg);
+        }
+        assert!(validate_args("movetoworkspace", "3,class:kitty", false).is_ok());
+        assert!(validate_args("workspace", "name:", false).is_err());
+        assert!(validate_args("workspace", "web", false).is_err());
+    }
+
+    #[test]
+    fn window_arguments() {
+        for arg in ["class:kitty", "title:^(.*vim)$", "kitty", "activewindow"] {
+            assert!(validate_args("focuswindow", arg, false).is_ok(), "{}", arg);
+        }
+        assert!(validate_args("focuswindow", "class:", false).is_err());
+        assert!(validate_args("focuswindow", "", false).is_err());
+    }
+
+    #[test]
+    fn mouse_binds_only_move_and_resize() {
+        assert!(validate_args("movewindow", "", true).is_ok());
+        assert!(validate_args("killactive", "", true).is_err());
+        assert!(validate_args("resizewindow", "", false).is_err());
+    }
+}
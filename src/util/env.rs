@@ -0,0 +1,180 @@
+use std::path::PathBuf;
+
+use crate::setting_writer::SettingWriter;
+use crate::util::config_tree::read_config_entries;
+use crate::util::option::set_keyword;
+
+pub const ENV_KEYWORD: &str = "env";
+
+/// A variable worth knowing about with the values it is usually set to
+#[derive(Debug)]
+pub struct KnownEnvVar {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub suggestions: &'static [&'static str],
+}
+
+const fn known(
+    name: &'static str,
+    description: &'static str,
+    suggestions: &'static [&'static str],
+) -> KnownEnvVar {
+    KnownEnvVar {
+        name,
+        description,
+        suggestions,
+    }
+}
+
+/// Variables offered on the environment page, see the Hyprland wiki for more
+pub static KNOWN_ENV_VARS: [KnownEnvVar; 18] = [
+    known(
+        "GDK_BACKEND",
+        "GTK backend, wayland with x11 as fallback.",
+        &["wayland,x11,*"],
+    ),
+    known(
+        "QT_QPA_PLATFORM",
+        "Qt backend, wayland with xcb as fallback.",
+        &["wayland;xcb"],
+    ),
+    known("SDL_VIDEODRIVER", "SDL backend.", &["wayland", "x11"]),
+    known("CLUTTER_BACKEND", "Clutter backend.", &["wayland"]),
+    known(
+        "ELECTRON_OZONE_PLATFORM_HINT",
+        "Run Electron apps natively on Wayland.",
+        &["auto", "wayland"],
+    ),
+    known(
+        "GDK_SCALE",
+        "Scale of GTK apps running under XWayland.",
+        &["1", "2"],
+    ),
+    known(
+        "QT_AUTO_SCREEN_SCALE_FACTOR",
+        "Let Qt scale with the monitor.",
+        &["1"],
+    ),
+    known(
+        "QT_WAYLAND_DISABLE_WINDOWDECORATION",
+        "Hide the title bars Qt draws itself.",
+        &["1"],
+    ),
+    known(
+        "QT_QPA_PLATFORMTHEME",
+        "Theme Qt apps with qt5ct or qt6ct.",
+        &["qt5ct", "qt6ct"],
+    ),
+    known(
+        "XDG_CURRENT_DESKTOP",
+        "Desktop name portals and apps look for.",
+        &["Hyprland"],
+    ),
+    known("XDG_SESSION_TYPE", "Session type.", &["wayland"]),
+    known("XDG_SESSION_DESKTOP", "Session name.", &["Hyprland"]),
+    known("XCURSOR_SIZE", "Cursor size for XCursor themes.", &["24"]),
+    known(
+        "HYPRCURSOR_SIZE",
+        "Cursor size for hyprcursor themes.",
+        &["24"],
+    ),
+    known(
+        "LIBVA_DRIVER_NAME",
+        "Hardware video decoding driver, needed on NVIDIA.",
+        &["nvidia"],
+    ),
+    known(
+        "__GLX_VENDOR_LIBRARY_NAME",
+        "OpenGL vendor library, needed on NVIDIA.",
+        &["nvidia"],
+    ),
+    known(
+        "NVD_BACKEND",
+        "Backend of the NVIDIA VA-API driver.",
+        &["direct"],
+    ),
+    known(
+        "AQ_DRM_DEVICES",
+        "GPUs Hyprland renders with, the first one is the primary.",
+        &["/dev/dri/card1:/dev/dri/card0"],
+    ),
+];
+
+/// An `env = NAME,value` line found in the config tree
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnvVar {
+    pub name: String,
+    pub value: String,
+    /// File the line was found in
+    pub path: PathBuf,
+    /// Whether the line lives in the overrides file
+    pub overridden: bool,
+}
+
+impl EnvVar {
+    pub fn line_value(&self) -> String {
+        format!("{},{}", self.name, self.value)
+    }
+}
+
+pub fn find_known_env_var(name: &str) -> Option<&'static KnownEnvVar> {
+    KNOWN_ENV_VARS.iter().find(|v| v.name == name)
+}
+
+/// Check a variable name, shells only accept letters, digits and underscores
+pub fn validate_env_name(name: &str) -> anyhow::Result<()> {
+    let valid = name
+        .chars()
+        .next()
+        .is_some_and(|c| c.is_ascii_alphabetic() || c == '_')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("Invalid variable name: {}", name))
+    }
+}
+
+/// Every `env` line of the config tree in the order Hyprland reads them, a variable set more
+/// than once ends up with the last value
+pub fn get_env_vars() -> anyhow::Result<Vec<EnvVar>> {
+    Ok(read_config_entries()?
+        .into_iter()
+        .filter(|entry| entry.keyword == ENV_KEYWORD)
+        .filter_map(|entry| {
+            let (name, value) = entry.value.split_once(',')?;
+
+            Some(EnvVar {
+                name: name.trim().to_string(),
+                value: value.trim().to_string(),
+                path: entry.path,
+                overridden: entry.overridden,
+            })
+        })
+        .collect())
+}
+
+/// Write a variable to the overrides file, replacing an earlier override of it, and set it for
+/// apps started from now on
+pub fn set_env_var(name: &str, value: &str) {
+    let line_value = format!("{},{}", name, value);
+
+    if let Err(e) = SettingWriter::build_single(&format!("{} = ", ENV_KEYWORD), &line_value)
+        .and_then(|w| w.write())
+    {
+        eprintln!("Failed to write override: {}", e);
+    }
+    if let Err(e) = set_keyword(ENV_KEYWORD, &line_value) {
+        eprintln!("{}", e);
+    }
+}
+
+/// Remove the override of a variable, the running session keeps its value until Hyprland restarts
+pub fn remove_env_var(var: &EnvVar) {
+    if let Err(e) = SettingWriter::build_single(&format!("{} = ", ENV_KEYWORD), var.line_value())
+        .and_then(|w| w.remove())
+    {
+        eprintln!("Failed to remove override: {}", e);
+    }
+}
@@ -0,0 +1,33 @@
+use crate::option_registry::{read_bool, read_number};
+
+const GESTURES_CATEGORY: &str = "gestures:";
+
+/// Current workspace swipe gesture options as reported by hyprctl
+#[derive(Debug, Clone)]
+pub struct GestureOptions {
+    pub workspace_swipe: bool,
+    pub fingers: i64,
+    pub distance: i64,
+    pub invert: bool,
+    pub cancel_ratio: f32,
+    pub create_new: bool,
+    pub forever: bool,
+}
+
+/// Path of a gesture option, e.g. `gestures:workspace_swipe` for `workspace_swipe`
+pub fn gesture_option(name: &str) -> String {
+    format!("{}{}", GESTURES_CATEGORY, name)
+}
+
+/// Options the running Hyprland does not report fall back to their registered defaults
+pub fn get_gesture_options() -> GestureOptions {
+    GestureOptions {
+        workspace_swipe: read_bool(&gesture_option("workspace_swipe")),
+        fingers: read_number(&gesture_option("workspace_swipe_fingers")),
+        distance: read_number(&gesture_option("workspace_swipe_distance")),
+        invert: read_bool(&gesture_option("workspace_swipe_invert")),
+        cancel_ratio: read_number(&gesture_option("workspace_swipe_cancel_ratio")),
+        create_new: read_bool(&gesture_option("workspace_swipe_create_new")),
+        forever: read_bool(&gesture_option("workspace_swipe_forever")),
+    }
+}
@@ -0,0 +1,336 @@
+use serde::Deserialize;
+use std::process::Command;
+
+use crate::setting_writer::{SettingWriter, SubmapSetting};
+
+/// Modifier names in the order Hyprland's modmask bits are defined
+const MODIFIERS: [(&str, u32); 8] = [
+    ("SHIFT", 1 << 0),
+    ("CAPS", 1 << 1),
+    ("CTRL", 1 << 2),
+    ("ALT", 1 << 3),
+    ("MOD2", 1 << 4),
+    ("MOD3", 1 << 5),
+    ("SUPER", 1 << 6),
+    ("MOD5", 1 << 7),
+];
+
+/// Bind keywords offered when editing a bind, see the Hyprland wiki for every flag
+pub const BIND_KEYWORDS: [&str; 7] = [
+    "bind", "binde", "bindl", "bindr", "bindm", "bindn", "bindel",
+];
+
+/// A bind as reported by `hyprctl binds -j`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Keybind {
+    #[serde(default)]
+    pub locked: bool,
+    #[serde(default)]
+    pub mouse: bool,
+    #[serde(default)]
+    pub release: bool,
+    #[serde(default)]
+    pub repeat: bool,
+    #[serde(rename = "longPress", default)]
+    pub long_press: bool,
+    #[serde(default)]
+    pub non_consuming: bool,
+    #[serde(default)]
+    pub has_description: bool,
+    pub modmask: u32,
+    #[serde(default)]
+    pub submap: String,
+    pub key: String,
+    #[serde(default)]
+    pub keycode: i64,
+    #[serde(default)]
+    pub description: String,
+    pub dispatcher: String,
+    #[serde(default)]
+    pub arg: String,
+}
+
+/// A submap with the bind entering it and the binds active inside it
+#[derive(Debug, Clone, Default)]
+pub struct Submap {
+    pub name: String,
+    /// Global bind running `submap name`
+    pub entry: Option<Keybind>,
+    /// Bind inside the submap running `submap reset`
+    pub exit: Option<Keybind>,
+    pub binds: Vec<Keybind>,
+}
+
+/// Modifier mask for a mods string such as `SUPER SHIFT` or `SUPER_SHIFT`
+pub fn modmask_from_mods(mods: &str) -> u32 {
+    mods.split(|c: char| c.is_whitespace() || c == '_' || c == '+')
+        .filter(|m| !m.is_empty())
+        .map(|m| match m.to_uppercase().as_str() {
+            "CONTROL" => "CTRL".to_string(),
+            "WIN" | "LOGO" | "MOD4" => "SUPER".to_string(),
+            "MOD1" => "ALT".to_string(),
+            m => m.to_string(),
+        })
+        .filter_map(|m| MODIFIERS.iter().find(|(name, _)| *name == m))
+        .fold(0, |mask, (_, bit)| mask | bit)
+}
+
+/// Mods string for a modifier mask, e.g. `SUPER SHIFT`
+pub fn mods_from_modmask(modmask: u32) -> String {
+    MODIFIERS
+        .iter()
+        .rev()
+        .filter(|(_, bit)| modmask & bit != 0)
+        .map(|(name, _)| *name)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Normalized key combo used to compare binds, e.g. `SUPER+SHIFT+Q`
+pub fn combo_key(mods: &str, key: &str) -> String {
+    let mods = mods_from_modmask(modmask_from_mods(mods));
+    let key = key.trim().to_uppercase();
+
+    if mods.is_empty() {
+        key
+    } else {
+        format!("{}+{}", mods.replace(' ', "+"), key)
+    }
+}
+
+/// Translate a key name as reported by gpui into the xkb key name Hyprland expects
+pub fn xkb_key_name(key: &str) -> String {
+    let name = match key {
+        "enter" => "Return",
+        "escape" => "Escape",
+        "tab" => "Tab",
+        "backspace" => "BackSpace",
+        "delete" => "Delete",
+        "insert" => "Insert",
+        "space" => "space",
+        "left" => "Left",
+        "right" => "Right",
+        "up" => "Up",
+        "down" => "Down",
+        "home" => "Home",
+        "end" => "End",
+        "pageup" => "Prior",
+        "pagedown" => "Next",
+        "-" => "minus",
+        "=" => "equal",
+        "," => "comma",
+        "." => "period",
+        "/" => "slash",
+        ";" => "semicolon",
+        "'" => "apostrophe",
+        "[" => "bracketleft",
+        "]" => "bracketright",
+        "\\" => "backslash",
+        "`" => "grave",
+        key => return key.to_uppercase(),
+    };
+    name.to_string()
+}
+
+impl Keybind {
+    pub fn new(modmask: u32, key: String, dispatcher: &str, arg: &str) -> Self {
+        Self {
+            modmask,
+            key,
+            dispatcher: dispatcher.to_string(),
+            arg: arg.to_string(),
+            ..Default::default()
+        }
+    }
+
+    /// Key name, binds made with `code:` keys only report the keycode
+    pub fn key_name(&self) -> String {
+        if self.key.is_empty() && self.keycode != 0 {
+            format!("code:{}", self.keycode)
+        } else {
+            self.key.clone()
+        }
+    }
+
+    pub fn mods(&self) -> String {
+        mods_from_modmask(self.modmask)
+    }
+
+    pub fn combo(&self) -> String {
+        combo_key(&self.mods(), &self.key_name())
+    }
+
+    /// Bind keyword including its flags, e.g. `bindel`
+    pub fn keyword(&self) -> String {
+        let flags = [
+            (self.locked, 'l'),
+            (self.release, 'r'),
+            (self.long_press, 'o'),
+            (self.repeat, 'e'),
+            (self.non_consuming, 'n'),
+            (self.mouse, 'm'),
+            (self.has_description, 'd'),
+        ];
+
+        flags
+            .iter()
+            .filter(|(set, _)| *set)
+            .fold("bind".to_string(), |mut keyword, (_, flag)| {
+                keyword.push(*flag);
+                keyword
+            })
+    }
+
+    /// Set the flags from a bind keyword such as `binde`, keeping the description flag
+    pub fn set_keyword(&mut self, keyword: &str) {
+        let flags = keyword.strip_prefix("bind").unwrap_or_default();
+        self.locked = flags.contains('l');
+        self.release = flags.contains('r');
+        self.long_press = flags.contains('o');
+        self.repeat = flags.contains('e');
+        self.non_consuming = flags.contains('n');
+        self.mouse = flags.contains('m');
+    }
+
+    /// Value of the bind line, e.g. `SUPER SHIFT, Q, killactive,`
+    pub fn line_value(&self) -> String {
+        let mut value = format!("{}, {}, ", self.mods(), self.key_name());
+        if self.has_description {
+            value.push_str(&format!("{}, ", self.description));
+        }
+        value.push_str(&format!("{}, {}", self.dispatcher, self.arg));
+        value
+    }
+
+    /// Value of the `unbind=` line removing this bind
+    pub fn unbind_value(&self) -> String {
+        format!("{}, {}", self.mods(), self.key_name())
+    }
+}
+
+impl Submap {
+    /// The submap region as written to the overrides file. The binds of `replaced` are unbound
+    /// first so binds from the main config do not linger, the exit bind goes last.
+    pub fn setting(&self, replaced: &Submap) -> SubmapSetting {
+        let unbinds = replaced
+            .binds
+            .iter()
+            .chain(replaced.exit.iter())
+            .map(|bind| ("unbind".to_string(), bind.unbind_value()));
+        let binds = self
+            .binds
+            .iter()
+            .chain(self.exit.iter())
+            .map(|bind| (bind.keyword(), bind.line_value()));
+
+        SubmapSetting {
+            name: self.name.clone(),
+            binds: unbinds.chain(binds).collect(),
+        }
+    }
+}
+
+/// Group the binds of every submap with the global bind entering it
+pub fn get_submaps(binds: &[Keybind]) -> Vec<Submap> {
+    let mut submaps: Vec<Submap> = Vec::new();
+
+    for bind in binds.iter().filter(|b| !b.submap.is_empty()) {
+        let idx = match submaps.iter().position(|s| s.name == bind.submap) {
+            Some(idx) => idx,
+            None => {
+                submaps.push(Submap {
+                    name: bind.submap.clone(),
+                    entry: binds
+                        .iter()
+                        .find(|b| {
+                            b.submap.is_empty()
+                                && b.dispatcher == "submap"
+                                && b.arg.trim() == bind.submap
+                        })
+                        .cloned(),
+                    ..Default::default()
+                });
+                submaps.len() - 1
+            }
+        };
+
+        let submap = &mut submaps[idx];
+        if bind.dispatcher == "submap" && bind.arg.trim() == "reset" && submap.exit.is_none() {
+            submap.exit = Some(bind.clone());
+        } else {
+            submap.binds.push(bind.clone());
+        }
+    }
+
+    submaps
+}
+
+/// Stage a global bind change, it is applied to the running compositor once it is saved. The old
+/// bind may come from the main config, so it is unbound as well as removed from the overrides
+/// file, the new bind is always appended after that unbind line.
+pub fn replace_bind(old: Option<&Keybind>, new: Option<&Keybind>) {
+    if let Some(old) = old {
+        if let Err(e) =
+            SettingWriter::build_single(&format!("{} = ", old.keyword()), old.line_value())
+                .and_then(|w| w.remove())
+        {
+            eprintln!("Failed to remove override: {}", e);
+        }
+        if let Err(e) = SettingWriter::build_single("unbind = ", old.unbind_value())
+            .and_then(|w| w.write_live())
+        {
+            eprintln!("Failed to write override: {}", e);
+        }
+    }
+
+    if let Some(new) = new
+        && let Err(e) =
+            SettingWriter::build_single(&format!("{} = ", new.keyword()), new.line_value())
+                .and_then(|w| w.write_live())
+    {
+        eprintln!("Failed to write override: {}", e);
+    }
+}
+
+/// Get all binds currently known to Hyprland
+pub fn get_keybinds() -> anyhow::Result<Vec<Keybind>> {
+    let output = Command::new("hyprctl").args(["binds", "-j"]).output()?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("Failed to execute hyprctl binds"));
+    }
+
+    let json_str = String::from_utf8(output.stdout)?;
+    Ok(serde_json::from_str(&json_str)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn keybind(flags: &str) -> Keybind {
+        serde_json::from_str(&format!(
+            r#"{{ {} "modmask": 64, "key": "Q", "dispatcher": "killactive" }}"#,
+            flags
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn combos_ignore_modifier_spelling() {
+        assert_eq!(combo_key("SUPER SHIFT", "q"), "SUPER+SHIFT+Q");
+        assert_eq!(combo_key("shift_win", " Q "), "SUPER+SHIFT+Q");
+        assert_eq!(combo_key("", "Return"), "RETURN");
+    }
+
+    #[test]
+    fn flags_are_written_in_the_keyword() {
+        assert_eq!(keybind("").keyword(), "bind");
+        assert_eq!(keybind(r#""mouse": true,"#).keyword(), "bindm");
+        assert_eq!(
+            keybind(r#""locked": true, "repeat": true,"#).keyword(),
+            "bindle"
+        );
+        assert_eq!(keybind("").unbind_value(), "SUPER, Q");
+    }
+}
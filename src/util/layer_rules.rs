@@ -0,0 +1,184 @@
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::process::Command;
+
+use crate::setting_writer::SettingWriter;
+use crate::util::config_tree::read_config_entries;
+use crate::util::option::set_keyword;
+
+pub const LAYER_RULE_KEYWORD: &str = "layerrule";
+
+/// Layer shell levels in the order of the keys reported by `hyprctl layers -j`
+pub const LAYER_LEVELS: [&str; 4] = ["background", "bottom", "top", "overlay"];
+
+/// Rules that are either set or not
+pub const LAYER_FLAG_RULES: [(&str, &str); 3] = [
+    ("blur", "Blur whatever is behind the surface."),
+    (
+        "ignorezero",
+        "Do not blur fully transparent pixels, e.g. around rounded bars.",
+    ),
+    ("noanim", "Do not animate the surface."),
+];
+
+/// Animation styles layers can open and close with
+pub const LAYER_ANIMATIONS: [&str; 7] = [
+    "slide",
+    "slide top",
+    "slide bottom",
+    "slide left",
+    "slide right",
+    "popin",
+    "fade",
+];
+
+#[derive(Debug, Deserialize)]
+struct LayerSurface {
+    namespace: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct MonitorLevels {
+    levels: HashMap<String, Vec<LayerSurface>>,
+}
+
+/// A layer shell surface such as a bar or launcher
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layer {
+    pub namespace: String,
+    /// Index into `LAYER_LEVELS`
+    pub level: usize,
+}
+
+/// A `layerrule` line, e.g. `ignorealpha 0.5, waybar`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct LayerRule {
+    /// Rule with its parameters, e.g. `ignorealpha 0.5`
+    pub rule: String,
+    pub namespace: String,
+    /// Whether the rule lives in the overrides file rather than the main config
+    pub overridden: bool,
+}
+
+impl LayerRule {
+    pub fn new(rule: &str, namespace: &str) -> Self {
+        Self {
+            rule: rule.to_string(),
+            namespace: namespace.to_string(),
+            overridden: true,
+        }
+    }
+
+    /// Parse the value of a `layerrule` line
+    pub fn parse(value: &str) -> Option<Self> {
+        let (rule, namespace) = value.split_once(',')?;
+        let (rule, namespace) = (rule.trim(), namespace.trim());
+        if rule.is_empty() || namespace.is_empty() {
+            return None;
+        }
+        Some(Self::new(rule, namespace))
+    }
+
+    /// Rule name without its parameters, e.g. `ignorealpha`
+    pub fn name(&self) -> &str {
+        self.rule.split_whitespace().next().unwrap_or_default()
+    }
+
+    /// Parameters after the rule name, e.g. `0.5`
+    pub fn params(&self) -> &str {
+        self.rule
+            .trim()
+            .split_once(char::is_whitespace)
+            .map(|(_, params)| params.trim())
+            .unwrap_or_default()
+    }
+
+    pub fn line_value(&self) -> String {
+        format!("{}, {}", self.rule, self.namespace)
+    }
+}
+
+/// Check the threshold of an `ignorealpha` rule
+pub fn validate_alpha(alpha: &str) -> anyhow::Result<()> {
+    match alpha.parse::<f32>() {
+        Ok(alpha) if (0.0..=1.0).contains(&alpha) => Ok(()),
+        _ => Err(anyhow::anyhow!(
+            "ignorealpha needs a value between 0 and 1, got {}",
+            alpha
+        )),
+    }
+}
+
+/// Open layer surfaces per monitor, ordered by monitor name and level
+pub fn get_layers() -> anyhow::Result<BTreeMap<String, Vec<Layer>>> {
+    let output = Command::new("hyprctl").args(["layers", "-j"]).output()?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("Failed to execute hyprctl layers"));
+    }
+
+    let json_str = String::from_utf8(output.stdout)?;
+    let monitors: HashMap<String, MonitorLevels> = serde_json::from_str(&json_str)?;
+
+    Ok(monitors
+        .into_iter()
+        .map(|(monitor, levels)| {
+            let mut layers: Vec<Layer> = levels
+                .levels
+                .into_iter()
+                .filter_map(|(level, surfaces)| Some((level.parse::<usize>().ok()?, surfaces)))
+                .flat_map(|(level, surfaces)| {
+                    surfaces.into_iter().map(move |surface| Layer {
+                        namespace: surface.namespace,
+                        level,
+                    })
+                })
+                .collect();
+            layers.sort_by(|a, b| (a.level, &a.namespace).cmp(&(b.level, &b.namespace)));
+            layers.dedup();
+            (monitor, layers)
+        })
+        .collect())
+}
+
+/// Layer rules of the config tree, in the order Hyprland reads them
+pub fn get_layer_rules() -> anyhow::Result<Vec<LayerRule>> {
+    Ok(read_config_entries()?
+        .into_iter()
+        .filter(|entry| entry.keyword == LAYER_RULE_KEYWORD)
+        .filter_map(|entry| {
+            LayerRule::parse(&entry.value).map(|rule| LayerRule {
+                overridden: entry.overridden,
+                ..rule
+            })
+        })
+        .collect())
+}
+
+/// Replace the overridden rules of a namespace. Rules that are gone are removed from the
+/// overrides file, new ones are written and applied, which only affects surfaces opened later.
+pub fn replace_layer_rules(old: &[LayerRule], new: &[LayerRule]) {
+    let prefix = format!("{} = ", LAYER_RULE_KEYWORD);
+
+    for rule in old
+        .iter()
+        .filter(|rule| !new.iter().any(|n| n.name() == rule.name()))
+    {
+        if let Err(e) =
+            SettingWriter::build_single(&prefix, rule.line_value()).and_then(|w| w.remove())
+        {
+            eprintln!("Failed to remove override: {}", e);
+        }
+    }
+
+    for rule in new.iter().filter(|rule| !old.contains(rule)) {
+        if let Err(e) =
+            SettingWriter::build_single(&prefix, rule.line_value()).and_then(|w| w.write())
+        {
+            eprintln!("Failed to write override: {}", e);
+        }
+        if let Err(e) = set_keyword(LAYER_RULE_KEYWORD, &rule.line_value()) {
+            eprintln!("{}", e);
+        }
+    }
+}
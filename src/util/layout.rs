@@ -0,0 +1,314 @@
+use crate::option_registry::{read_bool, read_number, read_text};
+
+pub const LAYOUTS: [&str; 2] = ["dwindle", "master"];
+
+pub const FORCE_SPLIT_MODES: [&str; 3] = [
+    "0 - Follow the cursor",
+    "1 - Always left or top",
+    "2 - Always right or bottom",
+];
+
+pub const MASTER_NEW_STATUSES: [&str; 3] = ["slave", "master", "inherit"];
+
+pub const MASTER_ORIENTATIONS: [&str; 5] = ["left", "right", "top", "bottom", "center"];
+
+/// Current layout options of the dwindle and master layouts as reported by hyprctl
+#[derive(Debug, Clone)]
+pub struct LayoutOptions {
+    pub layout: String,
+    pub pseudotile: bool,
+    pub preserve_split: bool,
+    pub smart_split: bool,
+    pub force_split: i64,
+    pub new_status: String,
+    pub mfact: f32,
+    pub orientation: String,
+}
+
+/// Options the running Hyprland does not report fall back to their registered defaults
+pub fn get_layout_options() -> LayoutOptions {
+    LayoutOptions {
+        layout: read_text("general:layout"),
+        pseudotile: read_bool("dwindle:pseudotile"),
+        preserve_split: read_bool("dwindle:preserve_split"),
+        smart_split: read_bool("dwindle:smart_split"),
+        force_split: read_number("dwindle:force_split"),
+        new_status: read_text("master:new_status"),
+        mfact: read_number("master:mfact"),
+        orientation: read_text("master:orientation"),
+    }
+}
+
+/// A window of the layout preview, position and size are fractions of the monitor
+#[derive(Debug, Clone, PartialEq)]
+pub struct PreviewWindow {
+    /// Order the window was opened in, starting at 1
+    pub number: usize,
+    pub x: f32,
+    pub y: f32,
+    pub width: f32,
+    pub height: f32,
+}
+
+impl PreviewWindow {
+    fn new(number: usize, x: f32, y: f32, width: f32, height: f32) -> Self {
+        Self {
+            number,
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+}
+
+/// Where `count` windows opened one after another end up, assuming each new window takes focus
+/// and the monitor is `aspect` times as wide as it is high
+pub fn preview_windows(options: &LayoutOptions, count: usize, aspect: f32) -> Vec<PreviewWindow> {
+    if options.layout == "master" {
+        master_preview(options, count)
+    } else {
+        dwindle_preview(options, count, aspect)
+    }
+}
+
+/// Dwindle splits the focused window in two, side by side when it is wider than high
+fn dwindle_preview(options: &LayoutOptions, count: usize, aspect: f32) -> Vec<PreviewWindow> {
+    let mut windows: Vec<PreviewWindow> = Vec::new();
+
+    for number in 1..=count {
+        let Some(focused) = windows.last_mut() else {
+            windows.push(PreviewWindow::new(number, 0.0, 0.0, 1.0, 1.0));
+            continue;
+        };
+
+        let mut new = focused.clone();
+        new.number = number;
+        if focused.width * aspect > focused.height {
+            focused.width /= 2.0;
+            new.width = focused.width;
+            new.x += new.width;
+        } else {
+            focused.height /= 2.0;
+            new.height = focused.height;
+            new.y += new.height;
+        }
+
+        // the cursor is assumed in the bottom right, so only forcing left or top swaps them
+        let swap = options.force_split == 1;
+        if swap {
+            std::mem::swap(&mut focused.number, &mut new.number);
+        }
+        windows.push(new);
+
+        // keep the newest window last, it is the one split next
+        if swap {
+            let len = windows.len();
+            windows.swap(len - 2, len - 1);
+        }
+    }
+
+    windows
+}
+
+/// Whether a new window opens in the master area, `inherit` takes the status of the focused window
+fn opens_as_master(new_status: &str, focused_is_master: bool) -> bool {
+    match new_status {
+        "master" => true,
+        "inherit" => focused_is_master,
+        _ => false,
+    }
+}
+
+/// Master keeps one window in the master area and stacks the others next to it
+fn master_preview(options: &LayoutOptions, count: usize) -> Vec<PreviewWindow> {
+    // the master comes first, a new master pushes the old one to the top of the stack
+    let mut order: Vec<usize> = Vec::new();
+    for number in 1..=count {
+        // the window opened last has focus
+        let focused_is_master = order.first() == Some(&(number - 1));
+        if order.is_empty() || opens_as_master(&options.new_status, focused_is_master) {
+            order.insert(0, number);
+        } else {
+            order.push(number);
+        }
+    }
+    let Some((&master, stack)) = order.split_first() else {
+        return Vec::new();
+    };
+
+    if stack.is_empty() {
+        return vec![PreviewWindow::new(master, 0.0, 0.0, 1.0, 1.0)];
+    }
+
+    let mfact = options.mfact.clamp(0.05, 0.95);
+    let rest = 1.0 - mfact;
+    let stack_size = |len: usize| 1.0 / len as f32;
+
+    match options.orientation.as_str() {
+        "center" if stack.len() >= 2 => {
+            // the stack alternates between the right and the left of the centered master
+            let right: Vec<usize> = stack.iter().copied().step_by(2).collect();
+            let left: Vec<usize> = stack.iter().copied().skip(1).step_by(2).collect();
+            let side = rest / 2.0;
+
+            let mut windows = vec![PreviewWindow::new(master, side, 0.0, mfact, 1.0)];
+            for (column, x) in [(&left, 0.0), (&right, side + mfact)] {
+                let height = stack_size(column.len());
+                windows.extend(column.iter().enumerate().map(|(idx, &number)| {
+                    PreviewWindow::new(number, x, idx as f32 * height, side, height)
+                }));
+            }
+            windows
+        }
+        "top" | "bottom" => {
+            let top = options.orientation == "top";
+            let width = stack_size(stack.len());
+            let (master_y, stack_y) = if top { (0.0, mfact) } else { (rest, 0.0) };
+
+            let mut windows = vec![PreviewWindow::new(master, 0.0, master_y, 1.0, mfact)];
+            windows.extend(stack.iter().enumerate().map(|(idx, &number)| {
+                PreviewWindow::new(number, idx as f32 * width, stack_y, width, rest)
+            }));
+            windows
+        }
+        orientation => {
+            let right = orientation == "right";
+            let height = stack_size(stack.len());
+            let (master_x, stack_x) = if right { (rest, 0.0) } else { (0.0, mfact) };
+
+            let mut windows = vec![PreviewWindow::new(master, master_x, 0.0, mfact, 1.0)];
+            windows.extend(stack.iter().enumerate().map(|(idx, &number)| {
+                PreviewWindow::new(number, stack_x, idx as f32 * height, rest, height)
+            }));
+            windows
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn options(layout: &str) -> LayoutOptions {
+        LayoutOptions {
+            layout: layout.to_string(),
+            pseudotile: false,
+            preserve_split: false,
+            smart_split: false,
+            force_split: 0,
+            new_status: "slave".to_string(),
+            mfact: 0.5,
+            orientation: "left".to_string(),
+        }
+    }
+
+    /// Window number and geometry in percent, rounded so float error does not matter
+    fn percent(windows: &[PreviewWindow]) -> Vec<(usize, [i32; 4])> {
+        let mut windows: Vec<_> = windows
+            .iter()
+            .map(|w| {
+                let rect = [w.x, w.y, w.width, w.height].map(|v| (v * 100.0).round() as i32);
+                (w.number, rect)
+            })
+            .collect();
+        windows.sort_by_key(|(number, _)| *number);
+        windows
+    }
+
+    fn master(orientation: &str, new_status: &str) -> Vec<(usize, [i32; 4])> {
+        let options = LayoutOptions {
+            orientation: orientation.to_string(),
+            new_status: new_status.to_string(),
+            ..options("master")
+        };
+        percent(&preview_windows(&options, 3, 16.0 / 9.0))
+    }
+
+    #[test]
+    fn dwindle_follows_the_force_split_mode() {
+        let dwindle = |force_split| {
+            let options = LayoutOptions {
+                force_split,
+                ..options("dwindle")
+            };
+            percent(&preview_windows(&options, 3, 16.0 / 9.0))
+        };
+
+        let right_or_bottom = vec![
+            (1, [0, 0, 50, 100]),
+            (2, [50, 0, 50, 50]),
+            (3, [50, 50, 50, 50]),
+        ];
+        assert_eq!(dwindle(0), right_or_bottom);
+        assert_eq!(dwindle(2), right_or_bottom);
+        assert_eq!(
+            dwindle(1),
+            vec![
+                (1, [50, 0, 50, 100]),
+                (2, [0, 50, 50, 50]),
+                (3, [0, 0, 50, 50]),
+            ]
+        );
+    }
+
+    #[test]
+    fn master_stacks_by_orientation() {
+        assert_eq!(
+            master("left", "slave"),
+            vec![
+                (1, [0, 0, 50, 100]),
+                (2, [50, 0, 50, 50]),
+                (3, [50, 50, 50, 50])
+            ]
+        );
+        assert_eq!(
+            master("right", "slave"),
+            vec![
+                (1, [50, 0, 50, 100]),
+                (2, [0, 0, 50, 50]),
+                (3, [0, 50, 50, 50])
+            ]
+        );
+        assert_eq!(
+            master("top", "slave"),
+            vec![
+                (1, [0, 0, 100, 50]),
+                (2, [0, 50, 50, 50]),
+                (3, [50, 50, 50, 50])
+            ]
+        );
+        assert_eq!(
+            master("bottom", "slave"),
+            vec![
+                (1, [0, 50, 100, 50]),
+                (2, [0, 0, 50, 50]),
+                (3, [50, 0, 50, 50])
+            ]
+        );
+        assert_eq!(
+            master("center", "slave"),
+            vec![
+                (1, [25, 0, 50, 100]),
+                (2, [75, 0, 25, 100]),
+                (3, [0, 0, 25, 100])
+            ]
+        );
+    }
+
+    #[test]
+    fn new_windows_take_the_master_area_by_status() {
+        let newest_first = vec![
+            (1, [50, 50, 50, 50]),
+            (2, [50, 0, 50, 50]),
+            (3, [0, 0, 50, 100]),
+        ];
+        assert_eq!(master("left", "master"), newest_first);
+        // the newest window has focus and is the master, so the next one inherits that
+        assert_eq!(master("left", "inherit"), newest_first);
+
+        assert!(opens_as_master("inherit", true));
+        assert!(!opens_as_master("inherit", false));
+        assert!(!opens_as_master("slave", true));
+    }
+}
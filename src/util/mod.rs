@@ -1,3 +1,24 @@
+pub mod animations;
+pub mod appearance;
+pub mod autostart;
+pub mod backup;
+pub mod colour;
+pub mod config_tree;
+pub mod cursor;
+pub mod descriptions;
+pub mod diff;
+pub mod dispatchers;
+pub mod env;
+pub mod gestures;
+pub mod keybinds;
 pub mod keyboard;
+pub mod layer_rules;
+pub mod layout;
 pub mod monitor;
 pub mod mouse;
+pub mod option;
+pub mod search;
+pub mod tablet;
+pub mod touchpad;
+pub mod window_rules;
+pub mod workspace_rules;
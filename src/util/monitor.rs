@@ -1,4 +1,8 @@
+use std::collections::HashMap;
 use std::process::Command;
+use std::sync::{Mutex, PoisonError};
+
+use crate::util::config_tree::read_config_entries;
 
 #[derive(Debug, Clone)]
 pub struct MonitorInfo {
@@ -16,6 +20,55 @@ pub struct MonitorMode {
     pub refresh_rate: f32,
 }
 
+/// Fields of the `monitor =` line of every monitor, read once since the page only changes the
+/// mode and position and keeps the rest
+static MONITOR_FIELDS: Mutex<Option<HashMap<String, Vec<String>>>> = Mutex::new(None);
+
+/// Fields of the last `monitor =` line of the config tree for each monitor
+fn read_monitor_fields() -> HashMap<String, Vec<String>> {
+    read_config_entries()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|entry| entry.keyword == "monitor")
+        .filter_map(|entry| {
+            let fields: Vec<String> = entry
+                .value
+                .split(',')
+                .map(|field| field.trim().to_string())
+                .collect();
+            Some((fields.first()?.clone(), fields))
+        })
+        .collect()
+}
+
+fn configured_fields(name: &str) -> Vec<String> {
+    let mut fields = MONITOR_FIELDS
+        .lock()
+        .unwrap_or_else(PoisonError::into_inner);
+    fields
+        .get_or_insert_with(read_monitor_fields)
+        .get(name)
+        .cloned()
+        .unwrap_or_default()
+}
+
+/// Replace the mode and position of a monitor line, the scale and any later fields such as
+/// `transform` or `vrr` are kept
+fn with_mode(fields: &[String], name: &str, mode: &str, position: (i32, i32)) -> String {
+    let scale = fields.get(3).map(String::as_str).unwrap_or("1");
+    let mut line = format!("{},{},{}x{},{}", name, mode, position.0, position.1, scale);
+    for field in fields.iter().skip(4) {
+        line.push(',');
+        line.push_str(field);
+    }
+    line
+}
+
+/// Value of a `monitor =` line setting a mode and position, keeping what else the config sets
+pub fn monitor_line_value(name: &str, mode: &str, position: (i32, i32)) -> String {
+    with_mode(&configured_fields(name), name, mode, position)
+}
+
 pub fn get_monitors() -> anyhow::Result<Vec<MonitorInfo>> {
     let output = Command::new("hyprctl").args(["monitors", "all"]).output()?;
 
@@ -106,3 +159,23 @@ fn parse_monitors(output: &str) -> anyhow::Result<Vec<MonitorInfo>> {
 
     Ok(monitors)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extra_monitor_fields_are_kept() {
+        let fields: Vec<String> = ["DP-1", "preferred", "auto", "1.5", "transform", "1"]
+            .map(String::from)
+            .into();
+        assert_eq!(
+            with_mode(&fields, "DP-1", "2560x1440@144", (0, 0)),
+            "DP-1,2560x1440@144,0x0,1.5,transform,1"
+        );
+        assert_eq!(
+            with_mode(&[], "DP-1", "1920x1080@60", (1920, 0)),
+            "DP-1,1920x1080@60,1920x0,1"
+        );
+    }
+}
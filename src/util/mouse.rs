@@ -1,48 +1,46 @@
-use std::process::Command;
-
-pub fn get_current_sensitivity() -> anyhow::Result<f32> {
-    let output = Command::new("hyprctl")
-        .args(["getoption", "input:sensitivity"])
-        .output()?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-
-    parse_sens(&stdout)
+use crate::option_registry::{read_bool, read_number};
+
+pub const FOLLOW_MOUSE_MODES: [&str; 4] = [
+    "0 - Click to focus",
+    "1 - Focus follows mouse",
+    "2 - Click to focus keyboard",
+    "3 - Never follow mouse",
+];
+
+pub const FLOAT_SWITCH_MODES: [&str; 3] = [
+    "0 - Disabled",
+    "1 - Tiled and floating",
+    "2 - Floating to floating too",
+];
+
+/// Focus and pointer options shaping how the cursor interacts with windows
+#[derive(Debug, Clone)]
+pub struct PointerBehaviour {
+    pub follow_mouse: i64,
+    pub mouse_refocus: bool,
+    pub float_switch_override_focus: i64,
+    pub inactive_timeout: f32,
+    pub no_warps: bool,
+    pub hide_on_key_press: bool,
 }
 
-pub fn get_accel_setting() -> anyhow::Result<bool> {
-    let output = Command::new("hyprctl")
-        .args(["getoption", "input:force_no_accel"])
-        .output()?;
-
-    let stdout = String::from_utf8_lossy(&output.stdout);
-    let parts = stdout.split_whitespace().collect::<Vec<&str>>();
-    if parts.len() < 2 {
-        return Err(anyhow::anyhow!("Invalid output from hyprctl getoption"));
-    }
-    let trimmed = parts[1]
-        .parse::<i32>()
-        .map_err(|e| anyhow::anyhow!("Failed to parse force_no_accel: {}", e))?;
-
-    match trimmed {
-        1 => Ok(true),
-        0 => Ok(false),
-        _ => Err(anyhow::anyhow!(
-            "Unexpected output for force_no_accel: {}",
-            trimmed
-        )),
+pub const SENSITIVITY_OPTION: &str = "input:sensitivity";
+pub const FORCE_NO_ACCEL_OPTION: &str = "input:force_no_accel";
+
+pub const FOLLOW_MOUSE_OPTION: &str = "input:follow_mouse";
+pub const MOUSE_REFOCUS_OPTION: &str = "input:mouse_refocus";
+pub const FLOAT_SWITCH_OPTION: &str = "input:float_switch_override_focus";
+pub const INACTIVE_TIMEOUT_OPTION: &str = "cursor:inactive_timeout";
+pub const NO_WARPS_OPTION: &str = "cursor:no_warps";
+pub const HIDE_ON_KEY_PRESS_OPTION: &str = "cursor:hide_on_key_press";
+
+pub fn get_pointer_behaviour() -> PointerBehaviour {
+    PointerBehaviour {
+        follow_mouse: read_number(FOLLOW_MOUSE_OPTION),
+        mouse_refocus: read_bool(MOUSE_REFOCUS_OPTION),
+        float_switch_override_focus: read_number(FLOAT_SWITCH_OPTION),
+        inactive_timeout: read_number(INACTIVE_TIMEOUT_OPTION),
+        no_warps: read_bool(NO_WARPS_OPTION),
+        hide_on_key_press: read_bool(HIDE_ON_KEY_PRESS_OPTION),
     }
 }
-
-fn parse_sens(output: &str) -> anyhow::Result<f32> {
-    let parts: Vec<&str> = output.split_whitespace().collect();
-
-    // assume for now that the first 2 parts is float and the value. the rest are not important
-    if parts.len() < 2 {
-        return Err(anyhow::anyhow!("Invalid output from hyprctl getoption"));
-    }
-
-    parts[1]
-        .parse::<f32>()
-        .map_err(|e| anyhow::anyhow!("Failed to parse sensitivity: {}", e))
-}
@@ -0,0 +1,68 @@
+use std::process::Command;
+
+use serde::Deserialize;
+
+/// Query the current value of a Hyprland option via `hyprctl getoption`.
+/// Only the value of the first line is returned, e.g. `1` for `int: 1`.
+pub fn get_option(name: &str) -> anyhow::Result<String> {
+    let output = Command::new("hyprctl").args(["getoption", name]).output()?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!(
+            "Failed to execute hyprctl getoption {}",
+            name
+        ));
+    }
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    parse_option(&stdout)
+        .ok_or_else(|| anyhow::anyhow!("Invalid output from hyprctl getoption {}", name))
+}
+
+/// Apply an option to the running compositor via `hyprctl keyword` without touching any file.
+pub fn set_keyword(name: &str, value: &str) -> anyhow::Result<()> {
+    let output = Command::new("hyprctl")
+        .args(["keyword", name, value])
+        .output()?;
+
+    let stdout = String::from_utf8_lossy(&output.stdout);
+
+    // hyprctl exits successfully even on config errors, so check the reply as well
+    if !output.status.success() || stdout.trim() != "ok" {
+        return Err(anyhow::anyhow!(
+            "Failed to apply {} = {}: {}",
+            name,
+            value,
+            stdout.trim()
+        ));
+    }
+
+    Ok(())
+}
+
+#[derive(Deserialize)]
+struct Version {
+    tag: String,
+}
+
+/// Version of the running Hyprland via `hyprctl version -j`, e.g. `v0.45.2`
+pub fn get_hyprland_version() -> anyhow::Result<String> {
+    let output = Command::new("hyprctl").args(["version", "-j"]).output()?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("Failed to execute hyprctl version"));
+    }
+
+    let json_str = String::from_utf8(output.stdout)?;
+    let version: Version = serde_json::from_str(&json_str)?;
+    Ok(version.tag)
+}
+
+fn parse_option(output: &str) -> Option<String> {
+    // first line looks like "int: 1", "float: 0.500000" or "str: us"
+    let first_line = output.lines().next()?;
+    let (_, value) = first_line.split_once(':')?;
+
+    Some(value.trim().trim_matches('"').to_string())
+}
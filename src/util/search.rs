@@ -0,0 +1,295 @@
+use std::process::Command;
+
+use crate::ActiveSection;
+use crate::change_set;
+use crate::option_registry::{OptionSpec, registry};
+use crate::setting_writer::{HYPR_OVERRIDES_PATH, config_path};
+
+/// Every page with words people search for instead of its title
+pub const PAGES: [(ActiveSection, &str); 21] = [
+    (
+        ActiveSection::Monitors,
+        "display screen resolution refresh rate position scale",
+    ),
+    (ActiveSection::Keyboard, "language locale layout kb"),
+    (
+        ActiveSection::Mouse,
+        "pointer sensitivity acceleration focus follow",
+    ),
+    (
+        ActiveSection::Touchpad,
+        "trackpad scroll tap gestures swipe",
+    ),
+    (ActiveSection::Tablet, "touchscreen pen drawing region"),
+    (ActiveSection::Cursor, "theme size xcursor hyprcursor"),
+    (
+        ActiveSection::Appearance,
+        "windows gaps border rounding opacity colours",
+    ),
+    (ActiveSection::BlurShadow, "blur shadow transparency"),
+    (ActiveSection::Animations, "bezier curve speed"),
+    (ActiveSection::Keybinds, "shortcuts keys bind dispatcher"),
+    (ActiveSection::Submaps, "modal binds resize mode"),
+    (ActiveSection::WindowRules, "windowrule float class title"),
+    (ActiveSection::LayerRules, "layerrule bar panel namespace"),
+    (ActiveSection::WorkspaceRules, "workspace rule monitor"),
+    (ActiveSection::Autostart, "exec-once startup programs"),
+    (ActiveSection::Environment, "env variables"),
+    (ActiveSection::Layouts, "dwindle master tiling split"),
+    (ActiveSection::Misc, "xwayland vrr swallow logo"),
+    (ActiveSection::AllOptions, "every option search"),
+    (ActiveSection::PendingChanges, "save diff unsaved discard"),
+    (ActiveSection::Backups, "restore copy revert"),
+];
+
+/// Page editing a registered option, the first match wins. Entries ending in `:` cover a
+/// whole category, anything not listed is edited on the all options page.
+const OPTION_PAGES: [(&str, ActiveSection); 29] = [
+    ("input:kb_layout", ActiveSection::Keyboard),
+    ("input:sensitivity", ActiveSection::Mouse),
+    ("input:force_no_accel", ActiveSection::Mouse),
+    ("input:follow_mouse", ActiveSection::Mouse),
+    ("input:mouse_refocus", ActiveSection::Mouse),
+    ("input:float_switch_override_focus", ActiveSection::Mouse),
+    ("cursor:hide_on_key_press", ActiveSection::Mouse),
+    ("cursor:inactive_timeout", ActiveSection::Mouse),
+    ("cursor:no_warps", ActiveSection::Mouse),
+    ("input:touchpad:", ActiveSection::Touchpad),
+    ("gestures:", ActiveSection::Touchpad),
+    ("input:tablet:", ActiveSection::Tablet),
+    ("input:touchdevice:", ActiveSection::Tablet),
+    ("general:gaps_in", ActiveSection::Appearance),
+    ("general:gaps_out", ActiveSection::Appearance),
+    ("general:border_size", ActiveSection::Appearance),
+    ("general:col.active_border", ActiveSection::Appearance),
+    ("general:col.inactive_border", ActiveSection::Appearance),
+    ("decoration:rounding", ActiveSection::Appearance),
+    ("decoration:active_opacity", ActiveSection::Appearance),
+    ("decoration:inactive_opacity", ActiveSection::Appearance),
+    ("decoration:dim_inactive", ActiveSection::Appearance),
+    ("decoration:blur:", ActiveSection::BlurShadow),
+    ("decoration:shadow:", ActiveSection::BlurShadow),
+    ("general:layout", ActiveSection::Layouts),
+    ("dwindle:", ActiveSection::Layouts),
+    ("master:", ActiveSection::Layouts),
+    ("misc:", ActiveSection::Misc),
+    ("xwayland:", ActiveSection::Misc),
+];
+
+/// Things the palette can do besides jumping to a page
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum PaletteAction {
+    ReloadHyprland,
+    OpenOverrides,
+    SaveChanges,
+}
+
+pub const ACTIONS: [PaletteAction; 3] = [
+    PaletteAction::ReloadHyprland,
+    PaletteAction::OpenOverrides,
+    PaletteAction::SaveChanges,
+];
+
+impl PaletteAction {
+    pub fn title(&self) -> &'static str {
+        match self {
+            PaletteAction::ReloadHyprland => "Reload Hyprland",
+            PaletteAction::OpenOverrides => "Open overrides file",
+            PaletteAction::SaveChanges => "Save pending changes",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            PaletteAction::ReloadHyprland => "Re-read every config file, like hyprctl reload",
+            PaletteAction::OpenOverrides => "Open conf-overrides.conf in the default text editor",
+            PaletteAction::SaveChanges => "Write every pending change to the overrides file",
+        }
+    }
+
+    pub fn run(&self) -> anyhow::Result<()> {
+        match self {
+            PaletteAction::ReloadHyprland => {
+                let output = Command::new("hyprctl").arg("reload").output()?;
+                let stdout = String::from_utf8_lossy(&output.stdout);
+
+                if !output.status.success() || stdout.trim() != "ok" {
+                    return Err(anyhow::anyhow!(
+                        "Failed to reload Hyprland: {}",
+                        stdout.trim()
+                    ));
+                }
+            }
+            PaletteAction::OpenOverrides => {
+                // not waited on, the editor may stay open for as long as it likes
+                Command::new("xdg-open")
+                    .arg(config_path(HYPR_OVERRIDES_PATH)?)
+                    .spawn()?;
+            }
+            PaletteAction::SaveChanges => {
+                change_set::apply()?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// A palette entry
+#[derive(Clone, Copy)]
+pub enum SearchTarget {
+    Page(ActiveSection),
+    Option(&'static OptionSpec),
+    Action(PaletteAction),
+}
+
+impl SearchTarget {
+    pub fn title(&self) -> String {
+        match self {
+            SearchTarget::Page(section) => section.to_string(),
+            SearchTarget::Option(option) => option.path.to_string(),
+            SearchTarget::Action(action) => action.title().to_string(),
+        }
+    }
+
+    pub fn detail(&self) -> String {
+        match self {
+            SearchTarget::Page(_) => "Page".to_string(),
+            SearchTarget::Option(option) => {
+                format!("{} · {}", option_section(option.path), option.description)
+            }
+            SearchTarget::Action(action) => action.description().to_string(),
+        }
+    }
+
+    pub fn kind(&self) -> &'static str {
+        match self {
+            SearchTarget::Page(_) => "Page",
+            SearchTarget::Option(_) => "Option",
+            SearchTarget::Action(_) => "Action",
+        }
+    }
+
+    /// Titles count double, descriptions and keywords only match as whole words
+    fn score(&self, query: &str) -> Option<u32> {
+        let (keywords, description) = match self {
+            SearchTarget::Page(section) => (
+                PAGES
+                    .iter()
+                    .find(|(page, _)| page == section)
+                    .map(|(_, keywords)| *keywords)
+                    .unwrap_or_default(),
+                "",
+            ),
+            SearchTarget::Option(option) => ("", option.description),
+            SearchTarget::Action(action) => ("", action.description()),
+        };
+
+        let title = fuzzy_score(query, &self.title()).map(|score| score * 2);
+        let words = [keywords, description]
+            .iter()
+            .any(|text| contains_words(text, query))
+            .then_some(1);
+
+        title.max(words)
+    }
+}
+
+/// Page an option is edited on
+pub fn option_section(path: &str) -> ActiveSection {
+    OPTION_PAGES
+        .iter()
+        .find(|(entry, _)| *entry == path || (entry.ends_with(':') && path.starts_with(entry)))
+        .map(|(_, section)| *section)
+        .unwrap_or(ActiveSection::AllOptions)
+}
+
+/// Score of `query` as a subsequence of `text`, `None` if it is not one.
+/// Runs of matching characters and matches at the start of a word score higher.
+pub fn fuzzy_score(query: &str, text: &str) -> Option<u32> {
+    let text: Vec<char> = text.to_lowercase().chars().collect();
+    let mut score = 0;
+    let mut position = 0;
+    let mut previous: Option<usize> = None;
+
+    for c in query.to_lowercase().chars().filter(|c| !c.is_whitespace()) {
+        let idx = position + text[position..].iter().position(|t| *t == c)?;
+
+        score += 1;
+        if previous.is_some_and(|p| p + 1 == idx) {
+            score += 4;
+        }
+        if idx == 0 || matches!(text[idx - 1], ':' | '_' | '.' | ' ' | '-') {
+            score += 3;
+        }
+
+        previous = Some(idx);
+        position = idx + 1;
+    }
+
+    Some(score)
+}
+
+/// Whether every word of `query` starts a word of `text`, so `acc` finds "acceleration" but
+/// not "inaccurate"
+fn contains_words(text: &str, query: &str) -> bool {
+    let text = text.to_lowercase();
+    let mut words = query.split_whitespace().peekable();
+
+    words.peek().is_some()
+        && words.all(|word| {
+            let word = word.to_lowercase();
+            text.match_indices(&word).any(|(idx, _)| {
+                text[..idx]
+                    .chars()
+                    .next_back()
+                    .is_none_or(|c| !c.is_alphanumeric())
+            })
+        })
+}
+
+/// Best matching pages, actions and options, an empty query lists the pages and actions
+pub fn search(query: &str, limit: usize) -> Vec<SearchTarget> {
+    let query = query.trim();
+    let targets = PAGES
+        .iter()
+        .map(|(section, _)| SearchTarget::Page(*section))
+        .chain(ACTIONS.iter().map(|action| SearchTarget::Action(*action)));
+
+    if query.is_empty() {
+        return targets.take(limit).collect();
+    }
+
+    let mut scored: Vec<(u32, SearchTarget)> = targets
+        .chain(registry().iter().map(SearchTarget::Option))
+        .filter_map(|target| target.score(query).map(|score| (score, target)))
+        .collect();
+
+    // stable, so pages and actions stay ahead of options with the same score
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored
+        .into_iter()
+        .take(limit)
+        .map(|(_, target)| target)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn words_match_at_word_starts() {
+        assert!(contains_words("Disable mouse acceleration", "acc"));
+        assert!(contains_words(
+            "Disable mouse acceleration",
+            "MOUSE disable"
+        ));
+        assert!(contains_words("gaps between windows (px)", "px"));
+        assert!(!contains_words("An inaccurate value", "acc"));
+        assert!(!contains_words(
+            "Disable mouse acceleration",
+            "mouse keyboard"
+        ));
+        assert!(!contains_words("Disable mouse acceleration", "  "));
+    }
+}
@@ -0,0 +1,92 @@
+use serde::Deserialize;
+use std::process::Command;
+
+use crate::option_registry::{read_bool, read_text};
+
+const TABLET_CATEGORY: &str = "input:tablet:";
+/// Monitor touchscreens are mapped to
+pub const TOUCHDEVICE_OUTPUT: &str = "input:touchdevice:output";
+
+#[derive(Debug, Deserialize)]
+struct HyprctlDevices {
+    #[serde(default)]
+    tablets: Vec<TabletDevice>,
+    #[serde(default)]
+    touch: Vec<TouchDevice>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TabletDevice {
+    pub address: String,
+    #[serde(rename = "type", default)]
+    pub kind: String,
+    #[serde(default)]
+    pub name: String,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct TouchDevice {
+    pub address: String,
+    pub name: String,
+}
+
+/// Current tablet options as reported by hyprctl
+#[derive(Debug, Clone, Default)]
+pub struct TabletOptions {
+    pub output: String,
+    pub region_position: (i32, i32),
+    pub region_size: (i32, i32),
+    pub relative_input: bool,
+}
+
+fn get_devices() -> anyhow::Result<HyprctlDevices> {
+    let output = Command::new("hyprctl").args(["devices", "-j"]).output()?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("Failed to execute hyprctl devices"));
+    }
+
+    let json_str = String::from_utf8(output.stdout)?;
+    Ok(serde_json::from_str(&json_str)?)
+}
+
+/// Get all connected tablets from hyprctl. Pads and tools are reported as separate entries
+/// without a name, so only the tablet itself is kept.
+pub fn get_tablets() -> anyhow::Result<Vec<TabletDevice>> {
+    Ok(get_devices()?
+        .tablets
+        .into_iter()
+        .filter(|t| t.kind == "tablet" && !t.name.is_empty())
+        .collect())
+}
+
+/// Get all connected touchscreens from hyprctl
+pub fn get_touch_devices() -> anyhow::Result<Vec<TouchDevice>> {
+    Ok(get_devices()?.touch)
+}
+
+/// Path of a tablet option, e.g. `input:tablet:output` for `output`
+pub fn tablet_option(name: &str) -> String {
+    format!("{}{}", TABLET_CATEGORY, name)
+}
+
+pub fn get_tablet_options() -> TabletOptions {
+    TabletOptions {
+        output: read_text(&tablet_option("output")),
+        region_position: parse_vec2(&read_text(&tablet_option("region_position")))
+            .unwrap_or_default(),
+        region_size: parse_vec2(&read_text(&tablet_option("region_size"))).unwrap_or_default(),
+        relative_input: read_bool(&tablet_option("relative_input")),
+    }
+}
+
+/// Parse a vec2 value as printed by hyprctl, e.g. "[0, 0]" or "0 0"
+fn parse_vec2(value: &str) -> Option<(i32, i32)> {
+    let trimmed = value.trim().trim_start_matches('[').trim_end_matches(']');
+    let mut parts = trimmed
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter(|p| !p.is_empty())
+        .map(|p| p.parse::<f32>().ok().map(|v| v.round() as i32));
+
+    Some((parts.next()??, parts.next()??))
+}
@@ -0,0 +1,35 @@
+use crate::option_registry::{read_bool, read_number};
+
+const TOUCHPAD_CATEGORY: &str = "input:touchpad:";
+
+/// Current touchpad options as reported by hyprctl
+#[derive(Debug, Clone)]
+pub struct TouchpadOptions {
+    pub natural_scroll: bool,
+    pub tap_to_click: bool,
+    pub disable_while_typing: bool,
+    pub clickfinger_behavior: bool,
+    pub scroll_factor: f32,
+    pub drag_lock: bool,
+    pub middle_button_emulation: bool,
+}
+
+/// Path of a touchpad option, e.g. `input:touchpad:natural_scroll` for `natural_scroll`
+pub fn touchpad_option(name: &str) -> String {
+    format!("{}{}", TOUCHPAD_CATEGORY, name)
+}
+
+/// Options the running Hyprland does not report fall back to their registered defaults
+pub fn get_touchpad_options() -> TouchpadOptions {
+    let bool_option = |name: &str| read_bool(&touchpad_option(name));
+
+    TouchpadOptions {
+        natural_scroll: bool_option("natural_scroll"),
+        tap_to_click: bool_option("tap-to-click"),
+        disable_while_typing: bool_option("disable_while_typing"),
+        clickfinger_behavior: bool_option("clickfinger_behavior"),
+        scroll_factor: read_number(&touchpad_option("scroll_factor")),
+        drag_lock: bool_option("drag_lock"),
+        middle_button_emulation: bool_option("middle_button_emulation"),
+    }
+}
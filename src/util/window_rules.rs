@@ -0,0 +1,420 @@
+use regex::Regex;
+use serde::Deserialize;
+use std::process::Command;
+
+use crate::util::colour::ColourValue;
+use crate::util::config_tree::read_config_entries;
+use crate::util::dispatchers::is_workspace;
+
+pub const WINDOW_RULE_KEYWORD: &str = "windowrulev2";
+
+/// What a window rule expects after its name
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum RuleParam {
+    /// Takes no parameters
+    None,
+    /// Width and height in pixels or percent, e.g. `800 600` or `50% 50%`
+    Size,
+    /// Two coordinates, expressions such as `100%-w-10` are allowed
+    Position,
+    /// One to three opacities, each optionally followed by `override`
+    Opacity,
+    /// A workspace, optionally followed by `silent`
+    Workspace,
+    /// A whole number
+    Number,
+    /// A colour or gradient
+    Colour,
+    /// One of a fixed set of values
+    Choice(&'static [&'static str]),
+    /// Free form text
+    Text,
+}
+
+/// A window rule and the parameters it expects
+#[derive(Debug)]
+pub struct Rule {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub param: RuleParam,
+}
+
+const fn rule(name: &'static str, description: &'static str, param: RuleParam) -> Rule {
+    Rule {
+        name,
+        description,
+        param,
+    }
+}
+
+/// Rules offered when adding window rules, see the Hyprland wiki for the full list
+pub static RULES: [Rule; 28] = [
+    rule("float", "Open the window floating.", RuleParam::None),
+    rule("tile", "Open the window tiled.", RuleParam::None),
+    rule("fullscreen", "Open the window fullscreen.", RuleParam::None),
+    rule("maximize", "Open the window maximized.", RuleParam::None),
+    rule("pseudo", "Open the window pseudo tiled.", RuleParam::None),
+    rule(
+        "size",
+        "Size of a floating window, e.g. `800 600` or `50% 50%`.",
+        RuleParam::Size,
+    ),
+    rule(
+        "minsize",
+        "Smallest size of a floating window.",
+        RuleParam::Size,
+    ),
+    rule(
+        "maxsize",
+        "Largest size of a floating window.",
+        RuleParam::Size,
+    ),
+    rule(
+        "move",
+        "Position of a floating window, e.g. `100 100` or `100%-w-10 10`.",
+        RuleParam::Position,
+    ),
+    rule("center", "Center a floating window.", RuleParam::None),
+    rule(
+        "workspace",
+        "Open the window on a workspace, add `silent` to stay where you are.",
+        RuleParam::Workspace,
+    ),
+    rule(
+        "monitor",
+        "Open the window on a monitor, by name or id.",
+        RuleParam::Text,
+    ),
+    rule(
+        "pin",
+        "Show a floating window on every workspace.",
+        RuleParam::None,
+    ),
+    rule(
+        "opacity",
+        "Active, inactive and fullscreen opacity, e.g. `0.9 0.8`.",
+        RuleParam::Opacity,
+    ),
+    rule("noblur", "Do not blur the window.", RuleParam::None),
+    rule("noshadow", "Do not draw a shadow.", RuleParam::None),
+    rule("noborder", "Do not draw a border.", RuleParam::None),
+    rule("noanim", "Do not animate the window.", RuleParam::None),
+    rule("nofocus", "Never focus the window.", RuleParam::None),
+    rule(
+        "noinitialfocus",
+        "Do not focus the window when it opens.",
+        RuleParam::None,
+    ),
+    rule(
+        "stayfocused",
+        "Keep focus on the window while it is visible.",
+        RuleParam::None,
+    ),
+    rule(
+        "dimaround",
+        "Dim everything around the window.",
+        RuleParam::None,
+    ),
+    rule(
+        "keepaspectratio",
+        "Keep the aspect ratio when resizing.",
+        RuleParam::None,
+    ),
+    rule("rounding", "Corner rounding in pixels.", RuleParam::Number),
+    rule("bordersize", "Border size in pixels.", RuleParam::Number),
+    rule(
+        "bordercolor",
+        "Border colour, e.g. `rgb(ff0000)`.",
+        RuleParam::Colour,
+    ),
+    rule(
+        "idleinhibit",
+        "Keep the screen awake while the window is open.",
+        RuleParam::Choice(&["none", "always", "focus", "fullscreen"]),
+    ),
+    rule(
+        "animation",
+        "Animation style, e.g. `popin 80%`.",
+        RuleParam::Text,
+    ),
+];
+
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ClientWorkspace {
+    pub name: String,
+}
+
+/// An open window as reported by `hyprctl clients -j`
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct Client {
+    #[serde(default)]
+    pub class: String,
+    #[serde(default)]
+    pub title: String,
+    #[serde(rename = "initialClass", default)]
+    pub initial_class: String,
+    #[serde(rename = "initialTitle", default)]
+    pub initial_title: String,
+    #[serde(default)]
+    pub workspace: ClientWorkspace,
+    #[serde(default)]
+    pub floating: bool,
+    #[serde(default)]
+    pub xwayland: bool,
+    #[serde(default)]
+    pub pinned: bool,
+}
+
+/// A `windowrulev2` line, e.g. `float, class:^(kitty)$, title:^(htop)$`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WindowRule {
+    /// Rule with its parameters, e.g. `size 800 600`
+    pub rule: String,
+    /// Matchers as `(name, value)` pairs, e.g. `("class", "^(kitty)$")`
+    pub matchers: Vec<(String, String)>,
+    /// Whether the rule lives in the overrides file rather than the main config
+    pub overridden: bool,
+}
+
+pub fn find_rule(name: &str) -> Option<&'static Rule> {
+    RULES.iter().find(|r| r.name == name)
+}
+
+/// Anchored regex for a window class or title, e.g. `^(kitty)$`
+pub fn exact_matcher(value: &str) -> String {
+    format!("^({})$", regex::escape(value))
+}
+
+fn is_number(value: &str) -> bool {
+    value.parse::<f64>().is_ok()
+}
+
+fn is_size(value: &str) -> bool {
+    // minsize and maxsize style bounds such as <50% are allowed as well
+    let value = value.trim_start_matches(['<', '>']);
+    is_number(value.strip_suffix('%').unwrap_or(value))
+}
+
+fn parse_bool(value: &str) -> Option<bool> {
+    match value {
+        "1" | "true" => Some(true),
+        "0" | "false" => Some(false),
+        _ => None,
+    }
+}
+
+/// Check the parameters of a rule such as `size 800 600`, unknown rules are not checked
+pub fn validate_rule(rule: &str) -> anyhow::Result<()> {
+    let mut parts = rule.split_whitespace();
+    let name = parts.next().ok_or_else(|| anyhow::anyhow!("Pick a rule"))?;
+    let params: Vec<&str> = parts.collect();
+
+    let Some(rule) = find_rule(name) else {
+        return Ok(());
+    };
+
+    let valid = match rule.param {
+        // static rules such as `center 1` take an optional flag
+        RuleParam::None => params.len() <= 1,
+        RuleParam::Size => params.len() == 2 && params.iter().all(|p| is_size(p)),
+        RuleParam::Position => {
+            let coords: Vec<&&str> = params
+                .iter()
+                .filter(|p| !["onscreen", "cursor"].contains(*p))
+                .collect();
+            coords.len() == 2
+        }
+        RuleParam::Opacity => {
+            let opacities = params.iter().filter(|p| **p != "override").count();
+            (1..=3).contains(&opacities) && params.iter().all(|p| *p == "override" || is_number(p))
+        }
+        RuleParam::Workspace => match params.as_slice() {
+            [workspace] | [workspace, "silent"] => is_workspace(workspace),
+            _ => false,
+        },
+        RuleParam::Number => params.len() == 1 && params[0].parse::<i64>().is_ok(),
+        RuleParam::Colour => !params.is_empty() && params.join(" ").parse::<ColourValue>().is_ok(),
+        RuleParam::Choice(choices) => params.len() == 1 && choices.contains(&params[0]),
+        RuleParam::Text => !params.is_empty(),
+    };
+
+    if valid {
+        Ok(())
+    } else if params.is_empty() {
+        Err(anyhow::anyhow!("{} needs parameters", name))
+    } else {
+        Err(anyhow::anyhow!(
+            "Invalid parameters for {}: {}",
+            name,
+            params.join(" ")
+        ))
+    }
+}
+
+/// Regex for a class or title matcher, Hyprland matches the whole string
+fn matcher_regex(pattern: &str) -> anyhow::Result<(Regex, bool)> {
+    let (pattern, negative) = match pattern.strip_prefix("negative:") {
+        Some(pattern) => (pattern, true),
+        None => (pattern, false),
+    };
+
+    let regex = Regex::new(&format!("^(?:{})$", pattern))
+        .map_err(|e| anyhow::anyhow!("Invalid regex {}: {}", pattern, e))?;
+    Ok((regex, negative))
+}
+
+fn regex_matches(pattern: &str, text: &str) -> bool {
+    matcher_regex(pattern).is_ok_and(|(regex, negative)| regex.is_match(text) != negative)
+}
+
+/// Split a rule value into the rule and its matchers, commas inside a regex stay in the matcher
+pub fn split_rule(value: &str) -> Option<(&str, Vec<&str>)> {
+    let (rule, rest) = value.split_once(',').unwrap_or((value, ""));
+    let rule = rule.trim();
+    if rule.is_empty() {
+        return None;
+    }
+
+    let mut matchers = Vec::new();
+    let mut start = 0;
+    let mut depth = 0i32;
+    for (idx, c) in rest.char_indices() {
+        match c {
+            '(' | '[' | '{' => depth += 1,
+            ')' | ']' | '}' => depth -= 1,
+            // a new matcher starts with `name:`, anything else is part of the regex
+            ',' if depth <= 0 && starts_matcher(&rest[idx + 1..]) => {
+                matchers.push(rest[start..idx].trim());
+                start = idx + 1;
+            }
+            _ => {}
+        }
+    }
+    matchers.push(rest[start..].trim());
+    matchers.retain(|m| !m.is_empty());
+
+    Some((rule, matchers))
+}
+
+fn starts_matcher(field: &str) -> bool {
+    field.split_once(':').is_some_and(|(name, _)| {
+        let name = name.trim();
+        !name.is_empty() && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+    })
+}
+
+impl WindowRule {
+    /// Parse the value of a `windowrulev2` line
+    pub fn parse(value: &str) -> Option<Self> {
+        let (rule, fields) = split_rule(value)?;
+        let rule = rule.to_string();
+        let matchers = fields
+            .into_iter()
+            .filter_map(|field| field.split_once(':'))
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .collect();
+
+        Some(Self {
+            rule,
+            matchers,
+            overridden: false,
+        })
+    }
+
+    pub fn line_value(&self) -> String {
+        self.matchers
+            .iter()
+            .fold(self.rule.clone(), |mut value, (name, matcher)| {
+                value.push_str(&format!(", {}:{}", name, matcher));
+                value
+            })
+    }
+
+    /// Check the rule parameters and that the matchers can be used
+    pub fn validate(&self) -> anyhow::Result<()> {
+        validate_rule(&self.rule)?;
+
+        if self.matchers.is_empty() {
+            return Err(anyhow::anyhow!("Add at least one matcher"));
+        }
+        for (name, value) in &self.matchers {
+            match name.as_str() {
+                "class" | "title" | "initialClass" | "initialTitle" => {
+                    matcher_regex(value)?;
+                }
+                "floating" | "xwayland" | "pinned" if parse_bool(value).is_none() => {
+                    return Err(anyhow::anyhow!("{} must be 0 or 1", name));
+                }
+                _ => {}
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Whether the rule applies to an open window. Matchers that can not be checked against
+    /// the client list, e.g. `tag`, are ignored.
+    pub fn matches(&self, client: &Client) -> bool {
+        self.matchers
+            .iter()
+            .all(|(name, value)| match name.as_str() {
+                "class" => regex_matches(value, &client.class),
+                "title" => regex_matches(value, &client.title),
+                "initialClass" => regex_matches(value, &client.initial_class),
+                "initialTitle" => regex_matches(value, &client.initial_title),
+                "floating" => parse_bool(value) == Some(client.floating),
+                "xwayland" => parse_bool(value) == Some(client.xwayland),
+                "pinned" => parse_bool(value) == Some(client.pinned),
+                _ => true,
+            })
+    }
+}
+
+/// Get all open windows
+pub fn get_clients() -> anyhow::Result<Vec<Client>> {
+    let output = Command::new("hyprctl").args(["clients", "-j"]).output()?;
+
+    if !output.status.success() {
+        return Err(anyhow::anyhow!("Failed to execute hyprctl clients"));
+    }
+
+    let json_str = String::from_utf8(output.stdout)?;
+    Ok(serde_json::from_str(&json_str)?)
+}
+
+/// `windowrule` and `windowrulev2` lines of the config tree, in the order Hyprland reads them.
+/// hyprctl has no way to list rules, so they are read from the config files.
+pub fn get_window_rules() -> anyhow::Result<Vec<WindowRule>> {
+    Ok(read_config_entries()?
+        .into_iter()
+        .filter_map(|entry| {
+            // rules of the old windowrule syntax put the rule after the matcher, skip those
+            let keyword = entry.keyword.as_str();
+            (keyword == WINDOW_RULE_KEYWORD
+                || (keyword == "windowrule" && entry.value.contains(':')))
+            .then(|| WindowRule::parse(&entry.value))?
+            .map(|rule| WindowRule {
+                overridden: entry.overridden,
+                ..rule
+            })
+        })
+        .collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn commas_in_regexes_stay_in_the_matcher() {
+        let (rule, matchers) = split_rule("float, title:^(a,b)$, class:foo,bar").unwrap();
+        assert_eq!(rule, "float");
+        assert_eq!(matchers, ["title:^(a,b)$", "class:foo,bar"]);
+    }
+
+    #[test]
+    fn rules_without_matchers_are_kept() {
+        assert_eq!(split_rule("size 800 600"), Some(("size 800 600", vec![])));
+        assert_eq!(split_rule(" , class:foo"), None);
+    }
+}
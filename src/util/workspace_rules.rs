@@ -0,0 +1,271 @@
+use crate::util::config_tree::read_config_entries;
+
+pub const WORKSPACE_RULE_KEYWORD: &str = "workspace";
+
+/// What a workspace rule option expects as its value
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum OptionValue {
+    /// `true` or `false`
+    Bool,
+    /// A whole number
+    Number,
+    /// One to four numbers like css, e.g. `10` or `5 10 5 10`
+    Gaps,
+    /// Free form text such as a monitor or a command
+    Text,
+}
+
+/// A workspace rule option such as `gapsin`
+#[derive(Debug)]
+pub struct WorkspaceOption {
+    pub name: &'static str,
+    pub description: &'static str,
+    pub value: OptionValue,
+}
+
+const fn option(
+    name: &'static str,
+    description: &'static str,
+    value: OptionValue,
+) -> WorkspaceOption {
+    WorkspaceOption {
+        name,
+        description,
+        value,
+    }
+}
+
+/// Options offered when editing workspace rules, see the Hyprland wiki for the full list
+pub static WORKSPACE_OPTIONS: [WorkspaceOption; 12] = [
+    option(
+        "monitor",
+        "Monitor the workspace is bound to.",
+        OptionValue::Text,
+    ),
+    option(
+        "default",
+        "Open this workspace first on its monitor.",
+        OptionValue::Bool,
+    ),
+    option(
+        "persistent",
+        "Keep the workspace alive when it is empty.",
+        OptionValue::Bool,
+    ),
+    option(
+        "gapsin",
+        "Gaps between windows, e.g. `5` or `5 10 5 10`.",
+        OptionValue::Gaps,
+    ),
+    option(
+        "gapsout",
+        "Gaps between windows and the monitor edges.",
+        OptionValue::Gaps,
+    ),
+    option("bordersize", "Border size in pixels.", OptionValue::Number),
+    option("border", "Draw window borders.", OptionValue::Bool),
+    option("shadow", "Draw window shadows.", OptionValue::Bool),
+    option("rounding", "Round window corners.", OptionValue::Bool),
+    option("decorate", "Draw window decorations.", OptionValue::Bool),
+    option(
+        "defaultName",
+        "Name the workspace gets when it is created.",
+        OptionValue::Text,
+    ),
+    option(
+        "on-created-empty",
+        "Command to run when the workspace is created empty.",
+        OptionValue::Text,
+    ),
+];
+
+/// A `workspace` line, e.g. `3, monitor:DP-1, gapsout:0`
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WorkspaceRule {
+    /// Workspace the rule applies to, e.g. `3`, `name:web`, `special:term` or `r[1-5]`
+    pub selector: String,
+    /// Options as `(name, value)` pairs
+    pub options: Vec<(String, String)>,
+    /// Whether the rule lives in the overrides file rather than the main config
+    pub overridden: bool,
+}
+
+pub fn find_option(name: &str) -> Option<&'static WorkspaceOption> {
+    WORKSPACE_OPTIONS.iter().find(|o| o.name == name)
+}
+
+fn is_bool(value: &str) -> bool {
+    ["true", "false", "1", "0"].contains(&value)
+}
+
+/// Check a dynamic selector such as `r[1-5]w[t1]`, each letter followed by its bracketed value
+fn validate_dynamic_selector(selector: &str) -> anyhow::Result<()> {
+    let mut rest = selector;
+
+    while !rest.is_empty() {
+        let mut chars = rest.chars();
+        let kind = chars.next().unwrap_or_default();
+        let Some(inner) = chars
+            .as_str()
+            .strip_prefix('[')
+            .and_then(|s| s.split_once(']'))
+        else {
+            return Err(anyhow::anyhow!(
+                "Expected a value in brackets after {} in {}",
+                kind,
+                selector
+            ));
+        };
+        let (value, next) = inner;
+
+        let valid = match kind {
+            'r' => value
+                .split_once('-')
+                .and_then(|(from, to)| Some((from.parse::<i64>().ok()?, to.parse::<i64>().ok()?)))
+                .is_some_and(|(from, to)| from <= to),
+            's' => is_bool(value),
+            'f' => value.parse::<i64>().is_ok_and(|f| (-1..=3).contains(&f)),
+            'm' | 'n' | 'w' => !value.is_empty(),
+            _ => {
+                return Err(anyhow::anyhow!("Unknown selector {} in {}", kind, selector));
+            }
+        };
+        if !valid {
+            return Err(anyhow::anyhow!("Invalid value for {}: {}", kind, value));
+        }
+
+        rest = next;
+    }
+
+    Ok(())
+}
+
+/// Check a workspace selector and describe what it targets
+pub fn selector_kind(selector: &str) -> anyhow::Result<&'static str> {
+    let selector = selector.trim();
+
+    if selector.is_empty() {
+        return Err(anyhow::anyhow!("Enter a workspace"));
+    }
+    if selector.contains(',') {
+        return Err(anyhow::anyhow!("Workspaces can not contain commas"));
+    }
+    if let Ok(id) = selector.parse::<i64>() {
+        if id < 1 {
+            return Err(anyhow::anyhow!("Workspace ids start at 1"));
+        }
+        return Ok("id");
+    }
+    if selector == "special" {
+        return Ok("special");
+    }
+    if let Some(name) = selector.strip_prefix("special:") {
+        return if name.is_empty() {
+            Err(anyhow::anyhow!(
+                "Special workspaces need a name after special:"
+            ))
+        } else {
+            Ok("special")
+        };
+    }
+    if let Some(name) = selector.strip_prefix("name:") {
+        return if name.is_empty() {
+            Err(anyhow::anyhow!("Named workspaces need a name after name:"))
+        } else {
+            Ok("name")
+        };
+    }
+
+    validate_dynamic_selector(selector)?;
+    Ok(if selector.starts_with("m[") {
+        "monitor"
+    } else if selector.starts_with("r[") {
+        "range"
+    } else {
+        "selector"
+    })
+}
+
+/// Check the value of an option, unknown options are not checked
+pub fn validate_option(name: &str, value: &str) -> anyhow::Result<()> {
+    let Some(option) = find_option(name) else {
+        return Ok(());
+    };
+
+    let valid = match option.value {
+        OptionValue::Bool => is_bool(value),
+        OptionValue::Number => value.parse::<i64>().is_ok(),
+        OptionValue::Gaps => {
+            let gaps: Vec<&str> = value.split_whitespace().collect();
+            (1..=4).contains(&gaps.len()) && gaps.iter().all(|g| g.parse::<i64>().is_ok())
+        }
+        OptionValue::Text => !value.is_empty() && !value.contains(','),
+    };
+
+    if valid {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!("Invalid value for {}: {}", name, value))
+    }
+}
+
+impl WorkspaceRule {
+    /// Parse the value of a `workspace` line
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut fields = value.split(',').map(str::trim);
+        let selector = fields.next().filter(|s| !s.is_empty())?.to_string();
+        let options = fields
+            .filter_map(|field| field.split_once(':'))
+            .map(|(name, value)| (name.trim().to_string(), value.trim().to_string()))
+            .collect();
+
+        Some(Self {
+            selector,
+            options,
+            overridden: false,
+        })
+    }
+
+    /// Set an option, replacing an earlier value of the same option
+    pub fn set_option(&mut self, name: &str, value: &str) {
+        match self.options.iter_mut().find(|(n, _)| n == name) {
+            Some((_, existing)) => *existing = value.to_string(),
+            None => self.options.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    pub fn validate(&self) -> anyhow::Result<()> {
+        selector_kind(&self.selector)?;
+        if self.options.is_empty() {
+            return Err(anyhow::anyhow!("Add at least one option"));
+        }
+        for (name, value) in &self.options {
+            validate_option(name, value)?;
+        }
+        Ok(())
+    }
+
+    pub fn line_value(&self) -> String {
+        self.options.iter().fold(
+            self.selector.trim().to_string(),
+            |mut value, (name, option)| {
+                value.push_str(&format!(", {}:{}", name, option));
+                value
+            },
+        )
+    }
+}
+
+/// Workspace rules of the config tree, in the order Hyprland reads them
+pub fn get_workspace_rules() -> anyhow::Result<Vec<WorkspaceRule>> {
+    Ok(read_config_entries()?
+        .into_iter()
+        .filter(|entry| entry.keyword == WORKSPACE_RULE_KEYWORD)
+        .filter_map(|entry| {
+            WorkspaceRule::parse(&entry.value).map(|rule| WorkspaceRule {
+                overridden: entry.overridden,
+                ..rule
+            })
+        })
+        .collect())
+}